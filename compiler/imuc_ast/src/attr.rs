@@ -0,0 +1,13 @@
+/// An attribute annotating an item, an element or an argument, like `#[name]` or `#[name(arg, ...)]`
+pub struct Attr {
+    pub name: crate::StrRef,
+    pub args: Vec<AttrArg>,
+    pub loc: crate::Loc,
+}
+
+/// An argument of an [`Attr`], either a literal or a nested name with its own arguments,
+/// like `"C"` in `#[extern("C")]` or `not(test)` in `#[cfg(not(test))]`
+pub enum AttrArg {
+    Prim(crate::prim::Prim),
+    Attr(Attr),
+}
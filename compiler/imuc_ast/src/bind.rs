@@ -9,6 +9,10 @@ pub enum Bind {
 }
 
 /// "let" binding creates a link from names to values
+///
+/// The pattern destructures the value, like `let (a, Point(x, y: (b, _))) = value`, binding every
+/// name to the part of the value at its place. Since nothing else takes the values it does not
+/// match, the pattern must match every value of its type, and so must the arguments of functions
 pub struct Let {
     pub pat: Pat,
     pub val: Expr,
@@ -11,11 +11,20 @@ pub enum Expr {
     Flow(crate::flow::Flow),
     Tuple(Tuple),
     Struct(Struct),
+    Field(Field),
+    Index(Index),
+    Assign(Assign),
+    Closure(Closure),
+    Call(Call),
+    Array(Array),
+    Cast(Cast),
+    Try(Try),
 }
 
+/// A value referred directly, where a name is kept with the location it is used at
 pub enum Value {
     Unused,
-    Name(crate::StrRef),
+    Name(crate::StrRef, crate::Loc),
     Res(imuc_lexer::token::ResVal),
 }
 
@@ -43,7 +52,82 @@ pub struct Tuple {
     pub elem: Vec<Expr>,
 }
 
+/// Constructing a struct like `Type(name: val)`, or a variant of a sum type like `Type.Name(name: val)`
+///
+/// A struct may end with a base like `Type(name: val, ..base)`, taking the elements left out
+/// from the base value instead of their default values
 pub struct Struct {
     pub ty: crate::pat::Type,
+    pub variant: Option<crate::StrRef>,
     pub elem: BTreeMap<crate::StrRef, Expr>,
+    pub base: Option<Box<Expr>>,
+}
+
+/// An array literal, either listing the elements like `[a, b, c]`,
+/// or repeating an element for a fixed number of times like `[val; len]`
+pub enum Array {
+    List(Vec<Expr>),
+    Repeat(Box<Expr>, u64),
+}
+
+/// Accessing a named field of a value, like `val.name`
+pub struct Field {
+    pub val: Box<Expr>,
+    pub name: crate::StrRef,
+}
+
+/// Indexing into a value, like `val[index]`
+///
+/// Indexing an array or a slice with a range like `val[start..end]` takes a slice of it
+pub struct Index {
+    pub val: Box<Expr>,
+    pub index: Box<Expr>,
+}
+
+/// Assigning a value to a place, like a name, a field or an index, as `place = val`
+///
+/// A compound assignment like `place += val` holds the operator applied to the old value,
+/// so that the place is only evaluated once
+pub struct Assign {
+    pub op: Option<BinOp>,
+    pub place: Box<Expr>,
+    pub val: Box<Expr>,
+}
+
+/// An anonymous function capturing the names it uses from the enclosing body,
+/// like `fun(args) => val` or `fun(args) { ... }`
+pub struct Closure {
+    pub args: crate::pat::Pat,
+    pub body: Box<Expr>,
+}
+
+/// Calling a function value with the arguments, like `fun(arg, ...)`
+pub struct Call {
+    pub fun: Box<Expr>,
+    pub args: Vec<Expr>,
+}
+
+/// Taking the value held by an `Option` or a `Result`, like `val?`
+///
+/// If there is no value, the function returns at once, giving back `Option.None` or the
+/// `Result.Err` holding the same error. Thus the function must return an `Option` for the
+/// former, or a `Result` of the same error type for the latter
+pub struct Try {
+    pub val: Box<Expr>,
+}
+
+/// Converting a numeric value to another numeric type, like `val as Type`
+///
+/// - An integer widens by sign extension, while a `Ptr` or a `Bool` widens by zero extension
+/// - An integer or a `Ptr` narrows by truncation, keeping the low bytes
+/// - An integer and a `Ptr` of the same width keep the bits unchanged
+/// - A float converts to an integer rounding toward zero, saturating at the bounds, and NaN gives 0
+/// - An integer converts to a float rounding to the nearest, ties to even
+/// - `U128` is unsigned in both conversions with floats, and every other integer is signed
+/// - `F64` narrows to `F32` rounding to the nearest, and `F32` widens to `F64` exactly
+///
+/// No other casts are allowed, including those between `Ptr` and floats, or to a `Bool`
+pub struct Cast {
+    pub val: Box<Expr>,
+    pub ty: crate::pat::Type,
 }
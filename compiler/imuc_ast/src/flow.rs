@@ -1,18 +1,56 @@
 use crate::expr::{Body, Expr};
+use crate::pat::Pat;
 
 /// Syntax tree of flow controls
 pub enum Flow {
     If(If),
     Loop(Loop),
+    Break(Break),
+    Continue(Continue),
+    Return(Return),
+    Match(Match),
 }
 
 /// An "if" statement stored in [`Flow`]
+///
+/// The "else" branch is either a [`Body`] or another [`If`] wrapped in an expression
 pub struct If {
     pub cond: Box<Expr>,
     pub body: Body,
+    pub other: Option<Box<Expr>>,
 }
 
-/// A "loop" statement stored in [`Flow`]
+/// A "loop" statement stored in [`Flow`], with an optional label to be referred by inner flows
 pub struct Loop {
+    pub label: Option<crate::StrRef>,
     pub body: Body,
 }
+
+/// A "break" expression leaving the labelled or the innermost loop, carrying an optional value
+pub struct Break {
+    pub label: Option<crate::StrRef>,
+    pub val: Option<Box<Expr>>,
+}
+
+/// A "continue" expression jumping to the next iteration of the labelled or the innermost loop
+pub struct Continue {
+    pub label: Option<crate::StrRef>,
+}
+
+/// A "return" expression leaving the current function, carrying an optional value
+pub struct Return {
+    pub val: Option<Box<Expr>>,
+}
+
+/// A "match" expression, choosing the first arm whose pattern and guard accept the value
+pub struct Match {
+    pub val: Box<Expr>,
+    pub arms: Vec<Arm>,
+}
+
+/// An arm of [`Match`], like `pat if guard => val`
+pub struct Arm {
+    pub pat: Pat,
+    pub guard: Option<Expr>,
+    pub val: Expr,
+}
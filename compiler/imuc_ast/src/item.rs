@@ -1,20 +1,24 @@
-use crate::expr::Body;
+use crate::attr::Attr;
+use crate::expr::{Body, Expr};
 use crate::module::Public;
-use crate::pat::Pat;
+use crate::pat::{Pat, Type};
 use crate::prim::Prim;
 
 /// An item definition directly used in modules, containing different kinds
 pub struct Item {
+    pub attrs: Vec<Attr>,
     pub public: Public,
     pub name: crate::StrRef,
     pub kind: ItemKind,
 }
 
+/// A template parameter of a function, like `T: Show + Eq`, or `_` if never named
 pub enum Templ {
     Item(TemplItem),
     Unused,
 }
 
+/// A named template parameter with the interfaces its arguments must implement
 pub struct TemplItem {
     pub name: crate::StrRef,
     pub req: Vec<crate::pat::Type>,
@@ -23,27 +27,88 @@ pub struct TemplItem {
 /// The internal data of an [`Item`], representing functions, customs types or constant values
 pub enum ItemKind {
     Fun(Fun),
+    Extern(Extern),
     Cus(Cus),
     For(For),
+    Interface(Interface),
     Val(Val),
+    /// A module declared inline, like `mod name { ... }`, holding its own imports and items
+    Mod(crate::module::Module),
 }
 
-/// A function definition with arguments and body
+/// A function definition with template parameters, arguments and body
+///
+/// The attributes of arguments are listed in the order of the top-level arguments. A function
+/// marked like `extern("C") fun name(...) { ... }` is exported with the ABI given
 pub struct Fun {
+    pub templ: Vec<Templ>,
     pub args: Pat,
+    pub attrs: Vec<Vec<Attr>>,
     pub body: Body,
+    pub abi: Option<crate::StrRef>,
 }
 
-/// A custom compound type definition
-pub struct Cus {
-    pub elem: Pat,
+/// A function declared without body, implemented outside of the module with the ABI given,
+/// like `extern("C") fun puts(s: Ptr) => I32`
+///
+/// The ABI defaults to `"C"`, and the function returns unit if no return type is given
+pub struct Extern {
+    pub abi: crate::StrRef,
+    pub args: Pat,
+    pub attrs: Vec<Vec<Attr>>,
+    pub ret: Option<Type>,
+}
+
+/// A custom type definition, either a compound of elements or a sum of variants
+///
+/// The elements of a compound come with their visibility, attributes and default values in
+/// order, like `(#[attr] pub name: Type = val, ...)`, while the elements of variants are as
+/// visible as the type and always given
+pub enum Cus {
+    Struct(Pat, Vec<Elem>),
+    Union(Vec<Variant>),
+}
+
+/// The visibility, attributes and default value of an element of a compound
+///
+/// The default value is evaluated anew for every value of the compound leaving the element out
+pub struct Elem {
+    pub public: Public,
+    pub attrs: Vec<Attr>,
+    pub default: Option<Expr>,
 }
 
-/// A list of implementations for a type
+/// A variant of a sum type, like `Name(elem: Type, ...)` or `Name`
+pub struct Variant {
+    pub name: crate::StrRef,
+    pub elem: Option<Pat>,
+}
+
+/// A list of implementations for a type, like `for Type { ... }`, or of the functions an
+/// interface requires, like `for Type: Interface { ... }`
+///
+/// The item is named after the type, and its functions are called on values of the type like
+/// `value.name(...)`, passing the value as their first argument, usually named `self`
 pub struct For {
+    pub ty: Type,
+    pub interface: Option<Type>,
     pub items: Vec<Item>,
 }
 
+/// An interface listing the functions its implementations define, like
+/// `interface Show { fun show(self) => Str }`, where `Self` is the type implementing it
+pub struct Interface {
+    pub decls: Vec<Decl>,
+}
+
+/// A function required by an [`Interface`], returning unit if no return type is given
+pub struct Decl {
+    pub name: crate::StrRef,
+    pub args: Pat,
+    pub attrs: Vec<Vec<Attr>>,
+    pub ret: Option<Type>,
+}
+
 /// A constant value of primitive
 pub struct Val {
     pub val: Prim,
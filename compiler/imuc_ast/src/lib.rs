@@ -1,10 +1,13 @@
+pub mod attr;
 pub mod bind;
 pub mod expr;
 pub mod flow;
 pub mod item;
+pub mod loc;
 pub mod module;
 pub mod name;
 pub mod pat;
 pub mod prim;
 
+pub use loc::Loc;
 pub use name::StrRef;
@@ -0,0 +1,16 @@
+use std::fmt;
+
+/// A position in the source file, pointing at the first character of a token
+///
+/// Nodes created by desugaring have the default location of line 0
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Loc {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Loc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {} column {}", self.line, self.column)
+    }
+}
@@ -7,9 +7,12 @@ pub struct Module {
 }
 
 /// The level of publicity in item definitions and items
+///
+/// An item marked `pub` is visible everywhere, one marked `pub(mod)` is visible to the files of
+/// the same module and its submodules, and an unmarked one is only visible to its own file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Public {
     Pub,
-    // TODO: Add keywords corresponding to this, if necessary
     Module,
     Priv,
 }
@@ -25,8 +28,29 @@ pub struct ImportItem {
     pub alias: Option<crate::StrRef>,
 }
 
+/// What an import brings from the file into the scope
+pub enum ImportKind {
+    /// The items listed, like `use a.file.(x, Y as Z)`
+    Items(Vec<ImportItem>),
+    /// Every item of the file visible to the importing file, like `use a.file.*`
+    Glob,
+    /// The file itself under the alias, like `use a.file as f`, whose items are referred to
+    /// like `f.item`
+    ///
+    /// Inline modules are imported as items instead, like `use a.file.helpers as h`
+    Alias(crate::StrRef),
+}
+
 /// A single import from the module
+///
+/// The path lists the inline modules inside the file leading to the items, like `helpers` in
+/// `use a.file.helpers.x`, which is empty for items defined in the file directly
+///
+/// An import marked `pub`, like `pub use a.file.x`, also re-exports the items imported,
+/// so that other files may import them from the importing file
 pub struct Import {
+    pub public: Public,
     pub file: imuc_path::File,
-    pub item: Vec<ImportItem>,
+    pub path: Vec<crate::StrRef>,
+    pub kind: ImportKind,
 }
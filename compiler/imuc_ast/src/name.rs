@@ -13,6 +13,12 @@ impl fmt::Display for StrRef {
     }
 }
 
+impl fmt::Debug for StrRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.as_ref().fmt(f)
+    }
+}
+
 impl Borrow<str> for StrRef {
     fn borrow(&self) -> &str {
         self
@@ -1,14 +1,22 @@
+use std::collections::BTreeMap;
+
 /// A pattern to be matched against values
 pub enum Pat {
     Ident(IdentPat),
     Tuple(TuplePat),
     Any(AnyPat),
+    Lit(LitPat),
+    Struct(StructPat),
 }
 
 /// The basic pattern, matching value to a certain type
+///
+/// A binding marked `mut` can be assigned to after its creation
 pub struct IdentPat {
     pub ident: IdentKind,
     pub ty: Option<Type>,
+    pub mutable: bool,
+    pub loc: crate::Loc,
 }
 
 /// A linear group of patterns
@@ -17,6 +25,22 @@ pub struct TuplePat(pub Vec<Pat>);
 /// A tree-like group of patterns, matching one of them
 pub struct AnyPat(pub Vec<Pat>);
 
+/// A literal pattern, matching values equal to it
+pub enum LitPat {
+    Prim(crate::prim::Prim),
+    Bool(bool),
+}
+
+/// A pattern matching the fields of a struct, like `Type(name: pat, ...)`,
+/// or of a variant of a sum type, like `Type.Name(name: pat, ...)`
+///
+/// Fields that are not listed match any value
+pub struct StructPat {
+    pub ty: Type,
+    pub variant: Option<crate::StrRef>,
+    pub elem: BTreeMap<crate::StrRef, Pat>,
+}
+
 /// An enumeration used in [`IdentPat`] for an unused or normal name
 pub enum IdentKind {
     Unused,
@@ -41,4 +65,10 @@ pub enum TypeKind {
     Res(imuc_lexer::token::ResTy),
     Single(crate::StrRef),
     Template(crate::StrRef, Vec<Type>),
+    /// A function type like `fun(Type, ...) => Ret`, returning unit if no return type is given
+    Fun(Vec<Type>, Option<Box<Type>>),
+    /// An array type like `[Type; len]`, holding a fixed number of elements
+    Array(Box<Type>, u64),
+    /// A slice type like `[Type]`, viewing a run of elements whose number is only known at runtime
+    Slice(Box<Type>),
 }
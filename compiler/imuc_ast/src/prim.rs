@@ -1,19 +1,38 @@
-/// Syntax node of a primitive: integer, float, or string
+/// Syntax node of a primitive: integer, float, string, or boolean
 #[derive(Clone)]
 pub enum Prim {
     Integer(Integer),
     Float(Float),
     String(String),
+    Bool(bool),
     Unit,
 }
 
 /// Different sizes of an integer stored in [`Prim`]
+///
+/// Literals are parsed as `I64`, or as the first of `I128` and `U128` holding them if too large
 #[derive(Clone)]
 pub enum Integer {
     I8(i8),
     I16(i16),
     I32(i32),
     I64(i64),
+    I128(i128),
+    U128(u128),
+}
+
+impl Integer {
+    /// Converts the value to another integer type, if it is in the range of that type
+    pub fn to<T: TryFrom<i128> + TryFrom<u128>>(&self) -> Option<T> {
+        match *self {
+            Self::I8(value) => T::try_from(i128::from(value)).ok(),
+            Self::I16(value) => T::try_from(i128::from(value)).ok(),
+            Self::I32(value) => T::try_from(i128::from(value)).ok(),
+            Self::I64(value) => T::try_from(i128::from(value)).ok(),
+            Self::I128(value) => T::try_from(value).ok(),
+            Self::U128(value) => T::try_from(value).ok(),
+        }
+    }
 }
 
 /// Different sizes of a float stored in [`Prim`]
@@ -0,0 +1,126 @@
+use crate::prelude::*;
+
+/// The attributes known to the compiler, which later passes can look up on the nodes they handle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Known {
+    /// `#[inline]`, `#[inline(always)]` or `#[inline(never)]` on functions
+    Inline,
+    /// `#[test]` on functions
+    Test,
+    /// `#[deprecated]` or `#[deprecated("note")]` on anything
+    Deprecated,
+    /// `#[extern]` on custom types meant to be shared with C code
+    ///
+    /// Only the targets and arguments are checked, as no layout is derived from it yet: C
+    /// structs are written as tuples, see `TyInner::c_layout` in the IR. Functions use the
+    /// `extern` keyword instead, see [`item::Extern`]
+    Extern,
+    /// `#[cfg(predicate)]` on anything, compiling the node only if the predicate holds
+    Cfg,
+}
+
+/// The kind of node an attribute is put on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Fun,
+    Cus,
+    Elem,
+    Arg,
+    /// Items other than functions and custom types
+    Item,
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Fun => write!(f, "functions"),
+            Target::Cus => write!(f, "custom types"),
+            Target::Elem => write!(f, "elements"),
+            Target::Arg => write!(f, "arguments"),
+            Target::Item => write!(f, "items"),
+        }
+    }
+}
+
+impl Known {
+    pub const ALL: [Known; 5] = [
+        Known::Inline,
+        Known::Test,
+        Known::Deprecated,
+        Known::Extern,
+        Known::Cfg,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Known::Inline => "inline",
+            Known::Test => "test",
+            Known::Deprecated => "deprecated",
+            Known::Extern => "extern",
+            Known::Cfg => "cfg",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|known| known.name() == name)
+    }
+
+    /// Returns whether the attribute can be put on the target
+    pub fn applies(self, target: Target) -> bool {
+        match self {
+            Known::Inline | Known::Test => target == Target::Fun,
+            Known::Extern => target == Target::Cus,
+            Known::Deprecated | Known::Cfg => true,
+        }
+    }
+
+    /// Checks the arguments of the attribute, returning what is expected if they are malformed
+    fn check_args(self, args: &[attr::AttrArg]) -> std::result::Result<(), &'static str> {
+        let name = |arg: &attr::AttrArg, names: &[&str]| match arg {
+            attr::AttrArg::Attr(attr) => attr.args.is_empty() && names.contains(&&*attr.name),
+            attr::AttrArg::Prim(_) => false,
+        };
+        let string =
+            |arg: &attr::AttrArg| matches!(arg, attr::AttrArg::Prim(prim::Prim::String(_)));
+        match (self, args) {
+            (Known::Test | Known::Extern, []) => Ok(()),
+            (Known::Test | Known::Extern, _) => Err("no arguments"),
+            (Known::Inline, []) => Ok(()),
+            (Known::Inline, [arg]) if name(arg, &["always", "never"]) => Ok(()),
+            (Known::Inline, _) => Err("no arguments, `always` or `never`"),
+            (Known::Deprecated, []) => Ok(()),
+            (Known::Deprecated, [arg]) if string(arg) => Ok(()),
+            (Known::Deprecated, _) => Err("no arguments or a string"),
+            (Known::Cfg, [attr::AttrArg::Attr(_)]) => Ok(()),
+            (Known::Cfg, _) => Err("a single predicate"),
+        }
+    }
+
+    /// Checks that the attribute is put on the right kind of node with well-formed arguments
+    pub fn check(self, input: &attr::Attr, target: Target) -> Result<()> {
+        if !self.applies(target) {
+            return Err(errors::AttrError::Target {
+                name: self.name().to_owned(),
+                target: target.to_string(),
+            }
+            .into());
+        }
+        self.check_args(&input.args).map_err(|expect| {
+            errors::AttrError::Args {
+                name: self.name().to_owned(),
+                expect: expect.to_owned(),
+            }
+            .into()
+        })
+    }
+}
+
+/// Finds the first attribute of the known kind in the list
+pub fn find(attrs: &[attr::Attr], known: Known) -> Option<&attr::Attr> {
+    attrs.iter().find(|attr| &*attr.name == known.name())
+}
+
+/// Returns whether the list has an attribute of the known kind
+pub fn has(attrs: &[attr::Attr], known: Known) -> bool {
+    find(attrs, known).is_some()
+}
@@ -0,0 +1,62 @@
+//! The sum types known to every module without being defined or imported
+//!
+//! They take template arguments, like `Option[I64]`, and are shadowed by the items of the same
+//! names. As custom types take no template parameters of their own, these are the only types
+//! with template arguments
+
+use crate::hir;
+use crate::ty::Ty;
+use imuc_ast::StrRef;
+
+/// The elements of a variant, each typed by the name of a template parameter
+pub type Elems = &'static [(&'static str, &'static str)];
+
+/// A built-in sum type with its template parameters, and its variants with their elements
+/// typed by the names of the parameters
+pub struct Sum {
+    pub name: &'static str,
+    pub templ: &'static [&'static str],
+    pub variants: &'static [(&'static str, Option<Elems>)],
+}
+
+/// `Option[T]`, holding a value as `Option.Some(value: T)`, or nothing as `Option.None`
+pub const OPTION: Sum = Sum {
+    name: "Option",
+    templ: &["T"],
+    variants: &[("Some", Some(&[("value", "T")])), ("None", None)],
+};
+
+/// `Result[T, E]`, holding a value as `Result.Ok(value: T)`, or an error as `Result.Err(error: E)`
+pub const RESULT: Sum = Sum {
+    name: "Result",
+    templ: &["T", "E"],
+    variants: &[
+        ("Ok", Some(&[("value", "T")])),
+        ("Err", Some(&[("error", "E")])),
+    ],
+};
+
+pub const SUMS: [&Sum; 2] = [&OPTION, &RESULT];
+
+/// Finds the built-in sum type of the name
+pub fn find(name: &str) -> Option<&'static Sum> {
+    SUMS.into_iter().find(|sum| sum.name == name)
+}
+
+impl Sum {
+    /// Returns the elements of the variant with the template arguments given, like
+    /// [`hir::Cus::elems`]
+    pub fn elems(&self, variant: Option<&StrRef>, args: &[Ty]) -> Option<hir::Elems> {
+        let variant = variant?;
+        let (_, elems) = self.variants.iter().find(|(name, _)| *name == &**variant)?;
+        let elems = (*elems)?
+            .iter()
+            .map(|(name, ty)| {
+                let at = self.templ.iter().position(|param| param == ty);
+                let ty = at.and_then(|at| args.get(at)).cloned();
+                (StrRef::from(*name), ty.unwrap_or(Ty::Unknown))
+            })
+            .collect();
+        Some(elems)
+    }
+}
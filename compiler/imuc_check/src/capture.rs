@@ -0,0 +1,92 @@
+use crate::prelude::*;
+use crate::scope::Scoped;
+use crate::visit::{self, Visit};
+
+/// Collects the names a closure uses from the enclosing body, in the order of their first use
+struct Captures {
+    /// Names bound inside the closure and visible to the current scope
+    bindings: Vec<StrRef>,
+    captures: Vec<(StrRef, Loc)>,
+}
+
+impl Scoped for Captures {
+    type Binding = StrRef;
+
+    fn bindings(&mut self) -> &mut Vec<StrRef> {
+        &mut self.bindings
+    }
+}
+
+impl Visit for Captures {
+    fn visit_item(&mut self, input: &item::Item) -> Result<()> {
+        // Functions never see the locals of the outer function, thus never capture
+        self.bindings.push(input.name.clone());
+        Ok(())
+    }
+
+    fn visit_body(&mut self, input: &expr::Body) -> Result<()> {
+        self.scoped(|this| visit::walk_body(this, input))
+    }
+
+    fn visit_expr(&mut self, input: &expr::Expr) -> Result<()> {
+        match input {
+            expr::Expr::Value(expr::Value::Name(name, loc)) => {
+                let bound = self.bindings.contains(name)
+                    || self.captures.iter().any(|(capture, _)| capture == name);
+                if !bound {
+                    self.captures.push((name.clone(), *loc));
+                }
+                Ok(())
+            }
+            expr::Expr::Closure(_) => self.scoped(|this| visit::walk_expr(this, input)),
+            _ => visit::walk_expr(self, input),
+        }
+    }
+
+    fn visit_flow(&mut self, input: &flow::Flow) -> Result<()> {
+        match input {
+            flow::Flow::Match(flow) => {
+                self.visit_expr(flow.val.as_ref())?;
+                for arm in flow.arms.iter() {
+                    self.scoped(|this| {
+                        this.visit_pat(&arm.pat)?;
+                        if let Some(guard) = &arm.guard {
+                            this.visit_expr(guard)?;
+                        }
+                        this.visit_expr(&arm.val)
+                    })?;
+                }
+                Ok(())
+            }
+            _ => visit::walk_flow(self, input),
+        }
+    }
+
+    fn visit_pat(&mut self, input: &pat::Pat) -> Result<()> {
+        if let pat::Pat::Ident(pat::IdentPat {
+            ident: pat::IdentKind::Value(name),
+            ..
+        }) = input
+        {
+            self.bindings.push(name.clone());
+        }
+        visit::walk_pat(self, input)
+    }
+}
+
+/// Returns the names used by the closure but not bound inside it, with the location of their
+/// first use, in the order of appearance
+///
+/// The names may refer to locals of the enclosing body, which are captured by the closure,
+/// or to items, which are left to the name resolution
+pub fn captures(input: &expr::Closure) -> Vec<(StrRef, Loc)> {
+    let mut captures = Captures {
+        bindings: Vec::new(),
+        captures: Vec::new(),
+    };
+    captures
+        .visit_pat(&input.args)
+        .and_then(|_| captures.visit_expr(input.body.as_ref()))
+        .expect("collecting captures should not fail");
+    captures.captures
+}
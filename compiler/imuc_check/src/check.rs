@@ -0,0 +1,11 @@
+use crate::prelude::*;
+
+/// Defines a semantic check over a syntax tree node of type `Input`
+///
+/// A checker usually implements this trait for every node it walks through, keeping its own state
+pub trait Check<Input: ?Sized> {
+    type Output;
+
+    /// Checks the node, returning the first error found
+    fn check(&mut self, input: &Input) -> Result<Self::Output>;
+}
@@ -0,0 +1,70 @@
+use crate::attr::{Known, Target};
+use crate::prelude::*;
+use crate::visit::{self, Visit};
+use crate::Warnings;
+
+/// Checks the attributes of items, elements and arguments against the known attributes
+///
+/// Unknown attributes become warnings, as they may be meant for other tools, while known
+/// attributes on the wrong kind of node or with malformed arguments are errors
+#[derive(Default)]
+pub struct AttrCheck {
+    pub warnings: Warnings,
+}
+
+impl AttrCheck {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn check_attrs(&mut self, attrs: &[attr::Attr], target: Target) -> Result<()> {
+        for attr in attrs.iter() {
+            match Known::from_name(&attr.name) {
+                Some(known) => known.check(attr, target)?,
+                None => self
+                    .warnings
+                    .push(errors::AttrError::Unknown(attr.name.to_string())),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Check<module::Module> for AttrCheck {
+    type Output = ();
+
+    fn check(&mut self, input: &module::Module) -> Result<()> {
+        self.visit_module(input)
+    }
+}
+
+impl Visit for AttrCheck {
+    fn visit_item(&mut self, input: &item::Item) -> Result<()> {
+        let mark = self.warnings.len();
+        let result = match &input.kind {
+            item::ItemKind::Fun(item::Fun { attrs, .. })
+            | item::ItemKind::Extern(item::Extern { attrs, .. }) => {
+                self.check_attrs(&input.attrs, Target::Fun).and_then(|()| {
+                    attrs
+                        .iter()
+                        .try_for_each(|attrs| self.check_attrs(attrs, Target::Arg))
+                })
+            }
+            item::ItemKind::Cus(cus) => {
+                self.check_attrs(&input.attrs, Target::Cus)
+                    .and_then(|()| match cus {
+                        item::Cus::Struct(_, elems) => elems
+                            .iter()
+                            .try_for_each(|elem| self.check_attrs(&elem.attrs, Target::Elem)),
+                        item::Cus::Union(_) => Ok(()),
+                    })
+            }
+            _ => self.check_attrs(&input.attrs, Target::Item),
+        }
+        .and_then(|()| visit::walk_item(self, input))
+        .with_context(|| format!("in item `{}`", input.name));
+        self.warnings
+            .context_since(mark, || format!("in item `{}`", input.name));
+        result
+    }
+}
@@ -0,0 +1,199 @@
+use crate::prelude::*;
+use crate::scope::Scoped;
+use crate::visit::{self, Visit};
+use imuc_lexer::token::BinOp;
+
+/// A local binding visible to the current scope, with the length of the array it holds if known
+pub(crate) struct Binding {
+    name: StrRef,
+    len: Option<u64>,
+}
+
+/// Returns the value of an integer literal
+fn literal(input: &expr::Expr) -> Option<i64> {
+    match input {
+        expr::Expr::Prim(prim::Prim::Integer(integer)) => integer.to(),
+        _ => None,
+    }
+}
+
+/// Checks indexes into arrays whose lengths are known, where the index is a literal or
+/// a range of literals
+///
+/// The length is known from an array type annotation or an array literal. Other indexes
+/// are guarded at runtime
+#[derive(Default)]
+pub struct BoundCheck {
+    bindings: Vec<Binding>,
+}
+
+impl BoundCheck {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the length of the array the expression evaluates to, if known
+    fn len(&self, input: &expr::Expr) -> Option<u64> {
+        match input {
+            expr::Expr::Value(expr::Value::Name(name, _)) => self
+                .bindings
+                .iter()
+                .rev()
+                .find(|binding| &binding.name == name)
+                .and_then(|binding| binding.len),
+            expr::Expr::Array(expr::Array::List(elem)) => Some(elem.len() as u64),
+            expr::Expr::Array(expr::Array::Repeat(_, len)) => Some(*len),
+            _ => None,
+        }
+    }
+
+    /// Binds the names in the pattern, taking the length from the annotation or the value
+    fn bind(&mut self, input: &pat::Pat, len: Option<u64>) {
+        match input {
+            pat::Pat::Ident(pat::IdentPat {
+                ident: pat::IdentKind::Value(name),
+                ty,
+                ..
+            }) => {
+                let len = match ty {
+                    Some(pat::Type {
+                        kind: pat::TypeKind::Array(_, len),
+                        ..
+                    }) => Some(*len),
+                    Some(_) => None,
+                    None => len,
+                };
+                self.bindings.push(Binding {
+                    name: name.clone(),
+                    len,
+                });
+            }
+            pat::Pat::Ident(_) | pat::Pat::Lit(_) => {}
+            pat::Pat::Tuple(pat::TuplePat(list)) | pat::Pat::Any(pat::AnyPat(list)) => {
+                for pat in list.iter() {
+                    self.bind(pat, None);
+                }
+            }
+            pat::Pat::Struct(cus) => {
+                for pat in cus.elem.values() {
+                    self.bind(pat, None);
+                }
+            }
+        }
+    }
+
+    fn check_index(&self, input: &expr::Index) -> Result<()> {
+        let Some(len) = self.len(input.val.as_ref()) else {
+            return Ok(());
+        };
+        match input.index.as_ref() {
+            expr::Expr::BinExpr(expr::BinExpr {
+                op: op @ (BinOp::Range | BinOp::RangeEq),
+                lhs,
+                rhs,
+            }) => {
+                let (Some(start), Some(end)) = (literal(lhs), literal(rhs)) else {
+                    return Ok(());
+                };
+                // An inclusive range takes the element at its end as well
+                let end = if *op == BinOp::RangeEq {
+                    end.saturating_add(1)
+                } else {
+                    end
+                };
+                if start > end {
+                    Err(errors::IndexError::Reversed { start, end }.into())
+                } else if start < 0 || u64::try_from(end).is_ok_and(|end| end > len) {
+                    Err(errors::IndexError::RangeOutOfBounds { start, end, len }.into())
+                } else {
+                    Ok(())
+                }
+            }
+            index => match literal(index) {
+                Some(index) if u64::try_from(index).is_ok_and(|index| index < len) => Ok(()),
+                Some(index) => Err(errors::IndexError::OutOfBounds { index, len }.into()),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+impl Scoped for BoundCheck {
+    type Binding = Binding;
+
+    fn bindings(&mut self) -> &mut Vec<Binding> {
+        &mut self.bindings
+    }
+}
+
+impl Check<module::Module> for BoundCheck {
+    type Output = ();
+
+    fn check(&mut self, input: &module::Module) -> Result<()> {
+        self.visit_module(input)
+    }
+}
+
+impl Visit for BoundCheck {
+    fn visit_item(&mut self, input: &item::Item) -> Result<()> {
+        match &input.kind {
+            item::ItemKind::Fun(fun) => self
+                .function(|this| {
+                    this.bind(&fun.args, None);
+                    this.visit_body(&fun.body)
+                })
+                .with_context(|| format!("in function `{}`", input.name)),
+            item::ItemKind::Cus(_) => Ok(()),
+            _ => visit::walk_item(self, input),
+        }
+    }
+
+    fn visit_body(&mut self, input: &expr::Body) -> Result<()> {
+        self.scoped(|this| visit::walk_body(this, input))
+    }
+
+    fn visit_bind(&mut self, input: &bind::Bind) -> Result<()> {
+        match input {
+            bind::Bind::Let(bind) => {
+                self.visit_expr(&bind.val)?;
+                let len = self.len(&bind.val);
+                self.bind(&bind.pat, len);
+                Ok(())
+            }
+            bind::Bind::Item(item) => self.visit_item(item),
+        }
+    }
+
+    fn visit_expr(&mut self, input: &expr::Expr) -> Result<()> {
+        match input {
+            expr::Expr::Index(index) => {
+                self.check_index(index)?;
+                visit::walk_expr(self, input)
+            }
+            expr::Expr::Closure(closure) => self.scoped(|this| {
+                this.bind(&closure.args, None);
+                this.visit_expr(closure.body.as_ref())
+            }),
+            _ => visit::walk_expr(self, input),
+        }
+    }
+
+    fn visit_flow(&mut self, input: &flow::Flow) -> Result<()> {
+        match input {
+            flow::Flow::Match(flow) => {
+                self.visit_expr(flow.val.as_ref())?;
+                for arm in flow.arms.iter() {
+                    self.scoped(|this| {
+                        this.bind(&arm.pat, None);
+                        if let Some(guard) = &arm.guard {
+                            this.visit_expr(guard)?;
+                        }
+                        this.visit_expr(&arm.val)
+                    })?;
+                }
+                Ok(())
+            }
+            _ => visit::walk_flow(self, input),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use crate::prelude::*;
+
+/// Checks that `break` and `continue` only appear inside (labelled) loops of the same function
+///
+/// The types of loops and of their break values are left to the type check
+#[derive(Default)]
+pub struct FlowCheck {
+    /// The labels of the loops around, innermost last
+    loops: Vec<Option<StrRef>>,
+}
+
+impl FlowCheck {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the label refers to a loop around, or whether there is any loop if no
+    /// label is given
+    fn find(&self, label: Option<&StrRef>) -> bool {
+        match label {
+            Some(label) => self.loops.iter().any(|found| found.as_ref() == Some(label)),
+            None => !self.loops.is_empty(),
+        }
+    }
+
+    /// Checks an item in a new function scope, so that no loops of the outer function are visible
+    fn check_scoped(&mut self, input: &item::Item) -> Result<()> {
+        let loops = std::mem::take(&mut self.loops);
+        let result = self.check(input);
+        self.loops = loops;
+        result
+    }
+}
+
+impl Check<module::Module> for FlowCheck {
+    type Output = ();
+
+    fn check(&mut self, input: &module::Module) -> Result<()> {
+        for item in input.items.iter() {
+            self.check_scoped(item)?;
+        }
+        Ok(())
+    }
+}
+
+impl Check<item::Item> for FlowCheck {
+    type Output = ();
+
+    fn check(&mut self, input: &item::Item) -> Result<()> {
+        match &input.kind {
+            item::ItemKind::Fun(fun) => {
+                self.check(&fun.body)
+                    .with_context(|| format!("in function `{}`", input.name))?;
+            }
+            item::ItemKind::For(items) => {
+                for item in items.items.iter() {
+                    self.check_scoped(item)?;
+                }
+            }
+            item::ItemKind::Mod(module) => self.check(module)?,
+            item::ItemKind::Extern(_)
+            | item::ItemKind::Cus(_)
+            | item::ItemKind::Interface(_)
+            | item::ItemKind::Val(_) => {}
+        }
+        Ok(())
+    }
+}
+
+impl Check<expr::Body> for FlowCheck {
+    type Output = ();
+
+    fn check(&mut self, input: &expr::Body) -> Result<()> {
+        for bind in input.bind.iter() {
+            match bind {
+                bind::Bind::Let(bind) => self.check(&bind.val)?,
+                bind::Bind::Item(item) => self.check_scoped(item)?,
+            }
+        }
+        for expr in input.body.iter() {
+            self.check(expr)?;
+        }
+        Ok(())
+    }
+}
+
+impl Check<flow::Flow> for FlowCheck {
+    type Output = ();
+
+    fn check(&mut self, input: &flow::Flow) -> Result<()> {
+        match input {
+            flow::Flow::If(flow) => {
+                self.check(flow.cond.as_ref())?;
+                self.check(&flow.body)?;
+                if let Some(other) = &flow.other {
+                    self.check(other.as_ref())?;
+                }
+            }
+            flow::Flow::Loop(flow) => {
+                self.loops.push(flow.label.clone());
+                let result = self.check(&flow.body);
+                self.loops.pop();
+                result?;
+            }
+            flow::Flow::Break(flow) => {
+                if let Some(val) = &flow.val {
+                    self.check(val.as_ref())?;
+                }
+                if !self.find(flow.label.as_ref()) {
+                    return Err(match &flow.label {
+                        Some(label) => errors::FlowError::UnknownLabel(label.to_string()),
+                        None => errors::FlowError::BreakOutside,
+                    }
+                    .into());
+                }
+            }
+            flow::Flow::Continue(flow) => {
+                if !self.find(flow.label.as_ref()) {
+                    return Err(match &flow.label {
+                        Some(label) => errors::FlowError::UnknownLabel(label.to_string()),
+                        None => errors::FlowError::ContinueOutside,
+                    }
+                    .into());
+                }
+            }
+            flow::Flow::Return(flow) => {
+                if let Some(val) = &flow.val {
+                    self.check(val.as_ref())?;
+                }
+            }
+            flow::Flow::Match(flow) => {
+                self.check(flow.val.as_ref())?;
+                for arm in flow.arms.iter() {
+                    if let Some(guard) = &arm.guard {
+                        self.check(guard)?;
+                    }
+                    self.check(&arm.val)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Check<expr::Expr> for FlowCheck {
+    type Output = ();
+
+    fn check(&mut self, input: &expr::Expr) -> Result<()> {
+        match input {
+            expr::Expr::Prim(_) | expr::Expr::Value(_) => {}
+            expr::Expr::UnExpr(expr) => self.check(expr.val.as_ref())?,
+            expr::Expr::BinExpr(expr) => {
+                self.check(expr.lhs.as_ref())?;
+                self.check(expr.rhs.as_ref())?;
+            }
+            expr::Expr::Body(body) => self.check(body)?,
+            expr::Expr::Flow(flow) => self.check(flow)?,
+            expr::Expr::Tuple(tuple) => {
+                for expr in tuple.elem.iter() {
+                    self.check(expr)?;
+                }
+            }
+            expr::Expr::Struct(cus) => {
+                for expr in cus.elem.values().chain(cus.base.as_deref()) {
+                    self.check(expr)?;
+                }
+            }
+            expr::Expr::Field(field) => self.check(field.val.as_ref())?,
+            expr::Expr::Index(index) => {
+                self.check(index.val.as_ref())?;
+                self.check(index.index.as_ref())?;
+            }
+            expr::Expr::Array(expr::Array::List(elem)) => {
+                for expr in elem.iter() {
+                    self.check(expr)?;
+                }
+            }
+            expr::Expr::Array(expr::Array::Repeat(val, _)) => self.check(val.as_ref())?,
+            expr::Expr::Cast(expr) => self.check(expr.val.as_ref())?,
+            expr::Expr::Try(expr) => self.check(expr.val.as_ref())?,
+            expr::Expr::Assign(assign) => {
+                self.check(assign.place.as_ref())?;
+                self.check(assign.val.as_ref())?;
+            }
+            expr::Expr::Closure(closure) => {
+                // A closure is a function of its own, where no loops of the outer function are visible
+                let loops = std::mem::take(&mut self.loops);
+                let result = self.check(closure.body.as_ref()).context("in closure");
+                self.loops = loops;
+                result?;
+            }
+            expr::Expr::Call(call) => {
+                self.check(call.fun.as_ref())?;
+                for expr in call.args.iter() {
+                    self.check(expr)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,263 @@
+use super::public::visible;
+use crate::prelude::*;
+use crate::Warnings;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// An item brought into the scope of a file, with the file defining it and its name there
+///
+/// Items of inline modules come with the path of the modules inside the file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Imported {
+    pub file: PathBuf,
+    pub module: Vec<StrRef>,
+    pub name: StrRef,
+}
+
+/// The names imported into a file, and the items of the files imported under aliases
+#[derive(Debug, Default)]
+pub struct Scope {
+    items: HashMap<StrRef, Imported>,
+    aliases: HashMap<StrRef, HashMap<StrRef, Imported>>,
+}
+
+impl Scope {
+    /// Returns the item imported under the name
+    pub fn get(&self, name: &str) -> Option<&Imported> {
+        self.items.get(name)
+    }
+
+    /// Returns the item referred to like `alias.name`, if the alias names an imported file
+    pub fn get_in(&self, alias: &str, name: &str) -> Option<&Imported> {
+        self.aliases.get(alias)?.get(name)
+    }
+
+    /// Returns whether the name is an alias of an imported file
+    pub fn is_alias(&self, name: &str) -> bool {
+        self.aliases.contains_key(name)
+    }
+
+    /// Returns an iterator over the names imported and the items they refer to
+    pub fn iter(&self) -> impl Iterator<Item = (&StrRef, &Imported)> {
+        self.items.iter()
+    }
+}
+
+type Exports = HashMap<StrRef, Imported>;
+
+/// Resolves the imports of a file into the names they bring into its scope
+///
+/// A file exports its items and the items it re-exports with `pub use`, each to the files
+/// it is visible to. Explicit imports must not conflict with each other, with aliases or with
+/// the items of the file, while glob imports never override items and explicit imports, which
+/// becomes a warning if shadowed by an explicit import. The same name from different glob
+/// imports is ambiguous, unless they refer to the same item
+///
+/// Inline modules are a part of their file, thus their items are visible to the file like its
+/// own items, while other files also need the modules along the path to be visible to them.
+/// Explicit imports of items invisible to the file are left to [`PubCheck`](super::PubCheck)
+#[derive(Default)]
+pub struct ImportCheck<'a> {
+    pub warnings: Warnings,
+    files: HashMap<&'a Path, &'a module::Module>,
+}
+
+impl<'a> ImportCheck<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the module parsed from the file, so that its items can be imported
+    pub fn insert(&mut self, file: &'a Path, module: &'a module::Module) {
+        self.files.insert(file, module);
+    }
+
+    /// Finds the inline module along the path in the file, whose modules must be visible to the
+    /// user file
+    fn module(&self, file: &Path, path: &[StrRef], user: &Path) -> Result<&'a module::Module> {
+        let mut module = self
+            .files
+            .get(file)
+            .copied()
+            .ok_or_else(|| errors::PathError::ModuleNotFound(file.display().to_string()))?;
+        for name in path.iter() {
+            let found = module.items.iter().find_map(|item| match &item.kind {
+                item::ItemKind::Mod(inner) if &item.name == name => Some((item.public, inner)),
+                _ => None,
+            });
+            let Some((public, inner)) = found else {
+                return Err(errors::ImportError::NoSuchItem {
+                    name: name.to_string(),
+                    file: file.display().to_string(),
+                }
+                .into());
+            };
+            if !visible(public, file, user) {
+                return Err(errors::PubError::PrivateItem {
+                    name: name.to_string(),
+                    file: file.display().to_string(),
+                }
+                .into());
+            }
+            module = inner;
+        }
+        Ok(module)
+    }
+
+    /// Collects the items of the module in the file visible to the user file, including the
+    /// re-exports
+    ///
+    /// Modules already being collected are skipped, so that re-exports in a loop end
+    fn exports(
+        &self,
+        file: &Path,
+        path: &[StrRef],
+        user: &Path,
+        visiting: &mut Vec<(PathBuf, Vec<StrRef>)>,
+    ) -> Result<Exports> {
+        let mut exports = Exports::new();
+        if visiting.iter().any(|(f, p)| f == file && p == path) {
+            return Ok(exports);
+        }
+        visiting.push((file.to_owned(), path.to_vec()));
+        let module = self.module(file, path, user)?;
+        for item in module.items.iter() {
+            if visible(item.public, file, user) {
+                let imported = Imported {
+                    file: file.to_owned(),
+                    module: path.to_vec(),
+                    name: item.name.clone(),
+                };
+                exports.insert(item.name.clone(), imported);
+            }
+        }
+        for import in module.import.iter() {
+            if import.public == module::Public::Priv || !visible(import.public, file, user) {
+                continue;
+            }
+            for (name, imported) in self.resolve(file, import, visiting)? {
+                // Items of the file are never overridden by re-exports
+                exports.entry(name).or_insert(imported);
+            }
+        }
+        visiting.pop();
+        Ok(exports)
+    }
+
+    /// Resolves the names an import of the user file brings, where aliases bring none
+    fn resolve(
+        &self,
+        user: &Path,
+        import: &module::Import,
+        visiting: &mut Vec<(PathBuf, Vec<StrRef>)>,
+    ) -> Result<Vec<(StrRef, Imported)>> {
+        let file = import.file.path();
+        let mut exports = self.exports(file, &import.path, user, visiting)?;
+        match &import.kind {
+            module::ImportKind::Items(items) => {
+                let module = self.module(file, &import.path, user)?;
+                let mut list = Vec::new();
+                for item in items.iter() {
+                    let (module::ImportItemKind::Value(name) | module::ImportItemKind::Type(name)) =
+                        &item.kind;
+                    let imported = match exports.remove(name) {
+                        Some(imported) => imported,
+                        // Invisible items are reported by the visibility check
+                        None if module.items.iter().any(|found| &found.name == name) => Imported {
+                            file: file.to_owned(),
+                            module: import.path.clone(),
+                            name: name.clone(),
+                        },
+                        None => {
+                            return Err(errors::ImportError::NoSuchItem {
+                                name: name.to_string(),
+                                file: file.display().to_string(),
+                            }
+                            .into())
+                        }
+                    };
+                    list.push((item.alias.clone().unwrap_or_else(|| name.clone()), imported));
+                }
+                Ok(list)
+            }
+            module::ImportKind::Glob => Ok(exports.into_iter().collect()),
+            module::ImportKind::Alias(_) => Ok(Vec::new()),
+        }
+    }
+}
+
+impl ImportCheck<'_> {
+    /// Resolves the imports of the inline module along the path in the file added before
+    pub fn scope(&mut self, input: &Path, path: &[StrRef]) -> Result<Scope> {
+        let module = self.module(input, path, input)?;
+        let mut scope = Scope::default();
+        let mut globs = Exports::new();
+        let defined = |name: &StrRef| module.items.iter().any(|item| &item.name == name);
+        for import in module.import.iter() {
+            let mut visiting = Vec::new();
+            match &import.kind {
+                module::ImportKind::Items(_) => {
+                    for (name, imported) in self.resolve(input, import, &mut visiting)? {
+                        let conflict = defined(&name)
+                            || scope.aliases.contains_key(&name)
+                            || scope
+                                .items
+                                .get(&name)
+                                .is_some_and(|found| found != &imported);
+                        if conflict {
+                            return Err(errors::ImportError::Conflict(name.to_string()).into());
+                        }
+                        scope.items.insert(name, imported);
+                    }
+                }
+                module::ImportKind::Glob => {
+                    for (name, imported) in self.resolve(input, import, &mut visiting)? {
+                        match globs.get(&name) {
+                            Some(found) if found != &imported => {
+                                return Err(errors::ImportError::Ambiguous(name.to_string()).into())
+                            }
+                            _ => {
+                                globs.insert(name, imported);
+                            }
+                        }
+                    }
+                }
+                module::ImportKind::Alias(alias) => {
+                    if defined(alias) || scope.items.contains_key(alias) {
+                        return Err(errors::ImportError::Conflict(alias.to_string()).into());
+                    }
+                    let exports =
+                        self.exports(import.file.path(), &import.path, input, &mut visiting)?;
+                    if scope.aliases.insert(alias.clone(), exports).is_some() {
+                        return Err(errors::ImportError::Conflict(alias.to_string()).into());
+                    }
+                }
+            }
+        }
+        for (name, imported) in globs {
+            if defined(&name) {
+                continue;
+            }
+            match scope.items.get(&name) {
+                Some(found) if found != &imported => {
+                    self.warnings
+                        .push(errors::ImportError::Shadowed(name.to_string()));
+                }
+                Some(_) => {}
+                None => {
+                    scope.items.insert(name, imported);
+                }
+            }
+        }
+        Ok(scope)
+    }
+}
+
+impl Check<Path> for ImportCheck<'_> {
+    type Output = Scope;
+
+    /// Resolves the imports of the file added before
+    fn check(&mut self, input: &Path) -> Result<Scope> {
+        self.scope(input, &[])
+    }
+}
@@ -0,0 +1,516 @@
+use crate::builtin;
+use crate::prelude::*;
+use crate::visit::{self, Visit};
+use crate::Warnings;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// A value constructor that patterns are built from
+#[derive(Clone, PartialEq)]
+enum Ctor {
+    Bool(bool),
+    Int(i128),
+    /// An unsigned integer too large for [`Ctor::Int`], of the same type as integers
+    Wide(u128),
+    /// A float compared by its bits
+    Float(u64),
+    Str(String),
+    Unit,
+    Tuple(usize),
+    /// A struct or a variant of a sum type, with the fields mentioned by any pattern of the match
+    Struct {
+        ty: String,
+        variant: Option<StrRef>,
+        fields: Vec<StrRef>,
+    },
+}
+
+impl Ctor {
+    fn arity(&self) -> usize {
+        match self {
+            Self::Tuple(len) => *len,
+            Self::Struct { fields, .. } => fields.len(),
+            _ => 0,
+        }
+    }
+
+    /// Returns whether both constructors build values of the same type
+    fn same_ty(&self, other: &Ctor) -> bool {
+        match (self, other) {
+            (Self::Tuple(lhs), Self::Tuple(rhs)) => lhs == rhs,
+            (Self::Int(_) | Self::Wide(_), Self::Int(_) | Self::Wide(_)) => true,
+            (
+                Self::Struct { ty, variant, .. },
+                Self::Struct {
+                    ty: other,
+                    variant: other_variant,
+                    ..
+                },
+            ) => ty == other && variant.is_some() == other_variant.is_some(),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+/// A pattern lowered onto constructors, with bindings and type annotations erased
+#[derive(Clone)]
+enum Shape {
+    Wild,
+    Ctor(Ctor, Vec<Shape>),
+    Or(Vec<Shape>),
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn list(f: &mut fmt::Formatter<'_>, list: &[Shape]) -> fmt::Result {
+            for (i, shape) in list.iter().enumerate() {
+                if i != 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", shape)?;
+            }
+            Ok(())
+        }
+
+        match self {
+            Self::Wild => write!(f, "_"),
+            Self::Or(alts) => {
+                for (i, shape) in alts.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, " | ")?;
+                    }
+                    write!(f, "{}", shape)?;
+                }
+                Ok(())
+            }
+            Self::Ctor(ctor, sub) => match ctor {
+                Ctor::Bool(val) => write!(f, "{}", val),
+                Ctor::Int(val) => write!(f, "{}", val),
+                Ctor::Wide(val) => write!(f, "{}", val),
+                Ctor::Float(bits) => write!(f, "{:?}", f64::from_bits(*bits)),
+                Ctor::Str(val) => write!(f, "{:?}", val),
+                Ctor::Unit => write!(f, "()"),
+                Ctor::Tuple(len) => {
+                    write!(f, "(")?;
+                    list(f, sub)?;
+                    if *len == 1 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, ")")
+                }
+                Ctor::Struct {
+                    ty,
+                    variant,
+                    fields,
+                } => {
+                    write!(f, "{}", ty)?;
+                    if let Some(variant) = variant {
+                        write!(f, ".{}", variant)?;
+                        if fields.is_empty() {
+                            return Ok(());
+                        }
+                    }
+                    write!(f, "(")?;
+                    for (i, (field, shape)) in fields.iter().zip(sub.iter()).enumerate() {
+                        if i != 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}: {}", field, shape)?;
+                    }
+                    write!(f, ")")
+                }
+            },
+        }
+    }
+}
+
+/// A row of patterns matched against the same number of values
+type Row = Vec<Shape>;
+
+/// Checks every "match" expression for arms that can never be reached, which become warnings,
+/// and for values that no arm matches, which is an error
+///
+/// The patterns of "let" bindings and of arguments destructure values without a choice, thus
+/// they must match every value of their type. Alternatives are allowed there only if one of them
+/// does so, like `(x, _) | (_, x)`
+///
+/// Only the constructors of booleans, unit, tuples, structs and sum types defined in the module
+/// are finite, so matching on numbers and strings needs a catch-all arm. An arm with a guard
+/// may be skipped at runtime, thus it never covers values for the later arms
+#[derive(Default)]
+pub struct MatchCheck {
+    pub warnings: Warnings,
+    /// Variants of every sum type defined so far
+    sums: BTreeMap<String, Vec<StrRef>>,
+    /// Fields of every struct and variant mentioned by the patterns of the current match
+    fields: BTreeMap<(String, Option<StrRef>), BTreeSet<StrRef>>,
+}
+
+/// Returns the name of the type a struct pattern refers to, ignoring template arguments
+fn ty_name(ty: &pat::Type) -> String {
+    match &ty.kind {
+        pat::TypeKind::Single(name) | pat::TypeKind::Template(name, _) => name.to_string(),
+        _ => Ty::from(ty).to_string(),
+    }
+}
+
+impl MatchCheck {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the variants of a sum type definition
+    fn collect_sum(&mut self, input: &item::Item) {
+        if let item::ItemKind::Cus(item::Cus::Union(variants)) = &input.kind {
+            let variants = variants.iter().map(|variant| variant.name.clone());
+            self.sums.insert(input.name.to_string(), variants.collect());
+        }
+    }
+
+    fn collect_fields(&mut self, input: &pat::Pat) {
+        match input {
+            pat::Pat::Ident(_) | pat::Pat::Lit(_) => {}
+            pat::Pat::Tuple(pat::TuplePat(list)) | pat::Pat::Any(pat::AnyPat(list)) => {
+                for pat in list.iter() {
+                    self.collect_fields(pat);
+                }
+            }
+            pat::Pat::Struct(cus) => {
+                self.fields
+                    .entry((ty_name(&cus.ty), cus.variant.clone()))
+                    .or_default()
+                    .extend(cus.elem.keys().cloned());
+                for pat in cus.elem.values() {
+                    self.collect_fields(pat);
+                }
+            }
+        }
+    }
+
+    fn lower(&self, input: &pat::Pat) -> Result<Shape> {
+        let shape = match input {
+            pat::Pat::Ident(_) => Shape::Wild,
+            pat::Pat::Tuple(pat::TuplePat(list)) if list.is_empty() => {
+                Shape::Ctor(Ctor::Unit, Vec::new())
+            }
+            pat::Pat::Tuple(pat::TuplePat(list)) => Shape::Ctor(
+                Ctor::Tuple(list.len()),
+                list.iter()
+                    .map(|pat| self.lower(pat))
+                    .collect::<Result<_>>()?,
+            ),
+            pat::Pat::Any(pat::AnyPat(list)) => Shape::Or(
+                list.iter()
+                    .map(|pat| self.lower(pat))
+                    .collect::<Result<_>>()?,
+            ),
+            pat::Pat::Lit(lit) => {
+                let ctor = match lit {
+                    pat::LitPat::Bool(val) | pat::LitPat::Prim(prim::Prim::Bool(val)) => {
+                        Ctor::Bool(*val)
+                    }
+                    pat::LitPat::Prim(prim::Prim::Integer(integer)) => match integer.to() {
+                        Some(val) => Ctor::Int(val),
+                        None => Ctor::Wide(integer.to().unwrap_or_default()),
+                    },
+                    pat::LitPat::Prim(prim::Prim::Float(float)) => Ctor::Float(match float {
+                        prim::Float::F32(val) => f64::from(*val).to_bits(),
+                        prim::Float::F64(val) => val.to_bits(),
+                    }),
+                    pat::LitPat::Prim(prim::Prim::String(val)) => Ctor::Str(val.clone()),
+                    pat::LitPat::Prim(prim::Prim::Unit) => Ctor::Unit,
+                };
+                Shape::Ctor(ctor, Vec::new())
+            }
+            pat::Pat::Struct(cus) => {
+                let ty = ty_name(&cus.ty);
+                if let (Some(variant), Some(variants)) = (&cus.variant, self.sums.get(&ty)) {
+                    if !variants.contains(variant) {
+                        return Err(errors::MatchError::NoSuchVariant {
+                            ty,
+                            variant: variant.to_string(),
+                        }
+                        .into());
+                    }
+                }
+                let fields: Vec<StrRef> = self.fields[&(ty.clone(), cus.variant.clone())]
+                    .iter()
+                    .cloned()
+                    .collect();
+                let mut sub = Vec::new();
+                for field in fields.iter() {
+                    sub.push(match cus.elem.get(field) {
+                        Some(pat) => self.lower(pat)?,
+                        None => Shape::Wild,
+                    });
+                }
+                let ctor = Ctor::Struct {
+                    ty,
+                    variant: cus.variant.clone(),
+                    fields,
+                };
+                Shape::Ctor(ctor, sub)
+            }
+        };
+        Ok(shape)
+    }
+
+    /// Checks that the pattern matches every value, since nothing else would take the others
+    fn irrefutable(&mut self, input: &pat::Pat, context: &str) -> Result<()> {
+        self.fields.clear();
+        self.collect_fields(input);
+        let matrix = vec![vec![self.lower(input)?]];
+        if let Some(witness) = self.useful(&matrix, &[Shape::Wild])? {
+            return Err(errors::MatchError::Refutable {
+                context: context.to_owned(),
+                witness: witness[0].to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Returns the rows of the matrix with alternatives in their first column split into rows
+    fn expand(matrix: &[Row]) -> Vec<Row> {
+        let mut rows = Vec::new();
+        for row in matrix.iter() {
+            match row.first() {
+                Some(Shape::Or(alts)) => {
+                    let alts: Vec<Row> = alts
+                        .iter()
+                        .map(|alt| {
+                            let mut row = row.clone();
+                            row[0] = alt.clone();
+                            row
+                        })
+                        .collect();
+                    rows.extend(Self::expand(&alts));
+                }
+                _ => rows.push(row.clone()),
+            }
+        }
+        rows
+    }
+
+    /// Keeps the rows whose first column accepts the constructor, replacing it by its fields
+    fn specialize(matrix: &[Row], ctor: &Ctor) -> Vec<Row> {
+        Self::expand(matrix)
+            .into_iter()
+            .filter_map(|row| {
+                let mut iter = row.into_iter();
+                let mut head = match iter.next() {
+                    Some(Shape::Wild) => vec![Shape::Wild; ctor.arity()],
+                    Some(Shape::Ctor(head, sub)) if head == *ctor => sub,
+                    _ => return None,
+                };
+                head.extend(iter);
+                Some(head)
+            })
+            .collect()
+    }
+
+    /// Keeps the rows whose first column accepts anything, removing that column
+    fn default_rows(matrix: &[Row]) -> Vec<Row> {
+        Self::expand(matrix)
+            .into_iter()
+            .filter(|row| matches!(row.first(), Some(Shape::Wild)))
+            .map(|row| row[1..].to_vec())
+            .collect()
+    }
+
+    /// Returns every constructor of the type of the given ones, if the type has finitely many
+    fn all_ctors(&self, present: &[Ctor]) -> Result<Option<Vec<Ctor>>> {
+        let Some(first) = present.first() else {
+            return Ok(None);
+        };
+        if let Some(found) = present.iter().find(|ctor| !first.same_ty(ctor)) {
+            let show = |ctor: &Ctor| {
+                let sub = vec![Shape::Wild; ctor.arity()];
+                Shape::Ctor(ctor.clone(), sub).to_string()
+            };
+            return Err(errors::MatchError::Mismatch {
+                expect: show(first),
+                found: show(found),
+            }
+            .into());
+        }
+        let all = match first {
+            Ctor::Bool(_) => Some(vec![Ctor::Bool(false), Ctor::Bool(true)]),
+            Ctor::Struct {
+                ty,
+                variant: Some(_),
+                ..
+            } => self.sums.get(ty).map(|variants| {
+                variants
+                    .iter()
+                    .map(|variant| {
+                        let key = (ty.clone(), Some(variant.clone()));
+                        let fields = self.fields.get(&key);
+                        Ctor::Struct {
+                            ty: ty.clone(),
+                            variant: Some(variant.clone()),
+                            fields: fields.into_iter().flatten().cloned().collect(),
+                        }
+                    })
+                    .collect()
+            }),
+            Ctor::Unit | Ctor::Tuple(_) | Ctor::Struct { .. } => Some(vec![first.clone()]),
+            Ctor::Int(_) | Ctor::Wide(_) | Ctor::Float(_) | Ctor::Str(_) => None,
+        };
+        Ok(all)
+    }
+
+    /// Returns whether the row matches some values that no row of the matrix matches,
+    /// giving such values as a row of patterns
+    fn useful(&self, matrix: &[Row], row: &[Shape]) -> Result<Option<Row>> {
+        let Some((head, rest)) = row.split_first() else {
+            return Ok(matrix.is_empty().then(Vec::new));
+        };
+        match head {
+            Shape::Or(alts) => {
+                for alt in alts.iter() {
+                    let mut row = vec![alt.clone()];
+                    row.extend_from_slice(rest);
+                    if let Some(witness) = self.useful(matrix, &row)? {
+                        return Ok(Some(witness));
+                    }
+                }
+                Ok(None)
+            }
+            Shape::Ctor(ctor, sub) => {
+                let mut row = sub.clone();
+                row.extend_from_slice(rest);
+                let witness = self.useful(&Self::specialize(matrix, ctor), &row)?;
+                Ok(witness.map(|witness| Self::rebuild(ctor, witness)))
+            }
+            Shape::Wild => {
+                let mut present = Vec::new();
+                for row in Self::expand(matrix) {
+                    if let Some(Shape::Ctor(ctor, _)) = row.first() {
+                        if !present.contains(ctor) {
+                            present.push(ctor.clone());
+                        }
+                    }
+                }
+                let all = self.all_ctors(&present)?;
+                match all {
+                    Some(all) if all.iter().all(|ctor| present.contains(ctor)) => {
+                        for ctor in all.iter() {
+                            let mut row = vec![Shape::Wild; ctor.arity()];
+                            row.extend_from_slice(rest);
+                            let witness = self.useful(&Self::specialize(matrix, ctor), &row)?;
+                            if let Some(witness) = witness {
+                                return Ok(Some(Self::rebuild(ctor, witness)));
+                            }
+                        }
+                        Ok(None)
+                    }
+                    all => {
+                        let Some(witness) = self.useful(&Self::default_rows(matrix), rest)? else {
+                            return Ok(None);
+                        };
+                        let head = all
+                            .and_then(|all| all.into_iter().find(|ctor| !present.contains(ctor)))
+                            .map_or(Shape::Wild, |ctor| {
+                                let sub = vec![Shape::Wild; ctor.arity()];
+                                Shape::Ctor(ctor, sub)
+                            });
+                        let mut row = vec![head];
+                        row.extend(witness);
+                        Ok(Some(row))
+                    }
+                }
+            }
+        }
+    }
+
+    /// Folds the leading fields of a witness back into the constructor
+    fn rebuild(ctor: &Ctor, mut witness: Row) -> Row {
+        let rest = witness.split_off(ctor.arity());
+        let mut row = vec![Shape::Ctor(ctor.clone(), witness)];
+        row.extend(rest);
+        row
+    }
+}
+
+impl Check<module::Module> for MatchCheck {
+    type Output = ();
+
+    fn check(&mut self, input: &module::Module) -> Result<()> {
+        for sum in builtin::SUMS {
+            let variants = sum.variants.iter().map(|(name, _)| StrRef::from(*name));
+            self.sums.insert(sum.name.to_owned(), variants.collect());
+        }
+        // Sum types may be defined after the functions matching on them
+        for item in input.items.iter() {
+            self.collect_sum(item);
+        }
+        self.visit_module(input)
+    }
+}
+
+impl Check<flow::Match> for MatchCheck {
+    type Output = ();
+
+    fn check(&mut self, input: &flow::Match) -> Result<()> {
+        self.fields.clear();
+        for arm in input.arms.iter() {
+            self.collect_fields(&arm.pat);
+        }
+        let mut matrix = Vec::new();
+        for (i, arm) in input.arms.iter().enumerate() {
+            let row = vec![self.lower(&arm.pat)?];
+            if self.useful(&matrix, &row)?.is_none() {
+                self.warnings
+                    .push(errors::MatchError::Unreachable(i + 1, row[0].to_string()));
+            }
+            if arm.guard.is_none() {
+                matrix.push(row);
+            }
+        }
+        if let Some(witness) = self.useful(&matrix, &[Shape::Wild])? {
+            return Err(errors::MatchError::NonExhaustive(witness[0].to_string()).into());
+        }
+        Ok(())
+    }
+}
+
+impl Visit for MatchCheck {
+    fn visit_item(&mut self, input: &item::Item) -> Result<()> {
+        self.collect_sum(input);
+        match &input.kind {
+            item::ItemKind::Fun(fun) => {
+                let mark = self.warnings.len();
+                let result = self
+                    .irrefutable(&fun.args, "arguments")
+                    .and_then(|()| visit::walk_item(self, input))
+                    .with_context(|| format!("in function `{}`", input.name));
+                self.warnings
+                    .context_since(mark, || format!("in function `{}`", input.name));
+                result
+            }
+            _ => visit::walk_item(self, input),
+        }
+    }
+
+    fn visit_bind(&mut self, input: &bind::Bind) -> Result<()> {
+        if let bind::Bind::Let(bind) = input {
+            self.irrefutable(&bind.pat, "`let` binding")?;
+        }
+        visit::walk_bind(self, input)
+    }
+
+    fn visit_expr(&mut self, input: &expr::Expr) -> Result<()> {
+        if let expr::Expr::Closure(closure) = input {
+            self.irrefutable(&closure.args, "closure arguments")?;
+        }
+        visit::walk_expr(self, input)
+    }
+
+    fn visit_flow(&mut self, input: &flow::Flow) -> Result<()> {
+        if let flow::Flow::Match(flow) = input {
+            self.check(flow)?;
+        }
+        visit::walk_flow(self, input)
+    }
+}
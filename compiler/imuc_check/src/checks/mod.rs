@@ -0,0 +1,21 @@
+mod attr;
+mod bounds;
+mod flow;
+mod import;
+mod matching;
+mod mutable;
+mod owner;
+mod public;
+mod resolve;
+mod types;
+
+pub use attr::AttrCheck;
+pub use bounds::BoundCheck;
+pub use flow::FlowCheck;
+pub use import::{ImportCheck, Imported, Scope};
+pub use matching::MatchCheck;
+pub use mutable::MutCheck;
+pub use owner::OwnCheck;
+pub use public::PubCheck;
+pub use resolve::{Def, DefId, DefKind, Resolution, ResolveCheck};
+pub use types::TypeCheck;
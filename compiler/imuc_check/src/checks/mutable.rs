@@ -0,0 +1,127 @@
+use crate::prelude::*;
+use crate::scope::Scoped;
+use crate::visit::{self, Visit};
+
+/// A local binding visible to the current scope
+pub(crate) struct Binding {
+    name: StrRef,
+    mutable: bool,
+    loc: Loc,
+}
+
+/// Checks that assignments only target places rooted at bindings marked `mut`
+///
+/// Places are names, fields of places and indexes into places. Names not bound by the
+/// current function are left to the name resolution
+#[derive(Default)]
+pub struct MutCheck {
+    bindings: Vec<Binding>,
+}
+
+impl MutCheck {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Finds the binding the place is rooted at
+    fn root(&self, place: &expr::Expr) -> Result<Option<&Binding>> {
+        match place {
+            expr::Expr::Value(expr::Value::Name(name, _)) => Ok(self
+                .bindings
+                .iter()
+                .rev()
+                .find(|binding| &binding.name == name)),
+            expr::Expr::Field(field) => self.root(field.val.as_ref()),
+            expr::Expr::Index(index) => self.root(index.val.as_ref()),
+            _ => Err(errors::MutError::NotPlace.into()),
+        }
+    }
+}
+
+impl Scoped for MutCheck {
+    type Binding = Binding;
+
+    fn bindings(&mut self) -> &mut Vec<Binding> {
+        &mut self.bindings
+    }
+}
+
+impl Check<module::Module> for MutCheck {
+    type Output = ();
+
+    fn check(&mut self, input: &module::Module) -> Result<()> {
+        self.visit_module(input)
+    }
+}
+
+impl Visit for MutCheck {
+    fn visit_item(&mut self, input: &item::Item) -> Result<()> {
+        match &input.kind {
+            item::ItemKind::Fun(_) => self
+                .function(|this| visit::walk_item(this, input))
+                .with_context(|| format!("in function `{}`", input.name)),
+            item::ItemKind::Cus(_) => Ok(()),
+            _ => visit::walk_item(self, input),
+        }
+    }
+
+    fn visit_body(&mut self, input: &expr::Body) -> Result<()> {
+        self.scoped(|this| visit::walk_body(this, input))
+    }
+
+    fn visit_expr(&mut self, input: &expr::Expr) -> Result<()> {
+        match input {
+            expr::Expr::Assign(assign) => {
+                if let Some(binding) = self.root(assign.place.as_ref())? {
+                    if !binding.mutable {
+                        return Err(errors::MutError::Immutable {
+                            name: binding.name.to_string(),
+                            site: binding.loc.to_string(),
+                        }
+                        .into());
+                    }
+                }
+                visit::walk_expr(self, input)
+            }
+            // Arguments of a closure are only visible to its body
+            expr::Expr::Closure(_) => self.scoped(|this| visit::walk_expr(this, input)),
+            _ => visit::walk_expr(self, input),
+        }
+    }
+
+    fn visit_flow(&mut self, input: &flow::Flow) -> Result<()> {
+        match input {
+            flow::Flow::Match(flow) => {
+                self.visit_expr(flow.val.as_ref())?;
+                for arm in flow.arms.iter() {
+                    self.scoped(|this| {
+                        this.visit_pat(&arm.pat)?;
+                        if let Some(guard) = &arm.guard {
+                            this.visit_expr(guard)?;
+                        }
+                        this.visit_expr(&arm.val)
+                    })?;
+                }
+                Ok(())
+            }
+            _ => visit::walk_flow(self, input),
+        }
+    }
+
+    fn visit_pat(&mut self, input: &pat::Pat) -> Result<()> {
+        if let pat::Pat::Ident(pat::IdentPat {
+            ident: pat::IdentKind::Value(name),
+            mutable,
+            loc,
+            ..
+        }) = input
+        {
+            self.bindings.push(Binding {
+                name: name.clone(),
+                mutable: *mutable,
+                loc: *loc,
+            });
+        }
+        visit::walk_pat(self, input)
+    }
+}
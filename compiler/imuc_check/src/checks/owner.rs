@@ -0,0 +1,559 @@
+use crate::capture;
+use crate::prelude::*;
+use imuc_lexer::token::UnOp;
+use std::collections::BTreeMap;
+
+/// Locations keyed by the index of bindings, telling where each one is moved or borrowed
+type Sites = BTreeMap<usize, Loc>;
+
+/// A local binding visible to the current scope
+struct Binding {
+    name: StrRef,
+    /// Whether the value is moved on use, instead of copied
+    unique: bool,
+    /// The bindings that the value borrows from, through shared references
+    origins: Sites,
+}
+
+/// What the check knows about the value of an expression
+#[derive(Default)]
+struct Val {
+    unique: bool,
+    origins: Sites,
+}
+
+impl Val {
+    fn merge(&mut self, other: Val) {
+        self.unique |= other.unique;
+        self.origins.extend(other.origins);
+    }
+}
+
+/// Whether an expression is used by value, or only read in place
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Move,
+    Read,
+}
+
+/// A loop collecting the moves that reach its exit and its next iteration
+struct LoopFrame {
+    label: Option<StrRef>,
+    exit: Option<Sites>,
+    next: Option<Sites>,
+    val: Val,
+}
+
+/// Joins the moves of two flows reaching the same point, where [`None`] is an unreachable flow
+fn join(lhs: Option<Sites>, rhs: Option<Sites>) -> Option<Sites> {
+    match (lhs, rhs) {
+        (None, moves) | (moves, None) => moves,
+        (Some(mut lhs), Some(rhs)) => {
+            for (id, loc) in rhs {
+                lhs.entry(id).or_insert(loc);
+            }
+            Some(lhs)
+        }
+    }
+}
+
+/// Checks the ownership of values in function bodies
+///
+/// A binding is unique if its type annotation names a custom type, or if it is initialized by
+/// a unique value, i.e. a struct or a tuple holding one. Using a unique binding by value moves
+/// it, after which it cannot be used until assigned again. Fields, indexes, operands and
+/// borrows only read the value in place. Shared references `@place` borrow the binding at the
+/// root of the place, which cannot be moved while any binding holding the reference is in scope,
+/// and must stay in scope as long as the reference does
+///
+/// Moves are tracked along the control flow, so a value moved in either branch counts as moved
+/// afterwards, and a value moved in a loop cannot be used in the next iteration
+#[derive(Default)]
+pub struct OwnCheck {
+    bindings: Vec<Binding>,
+    /// The moves reaching the current point, or [`None`] if the point is unreachable
+    moves: Option<Sites>,
+    loops: Vec<LoopFrame>,
+}
+
+impl OwnCheck {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn find(&self, name: &StrRef) -> Option<usize> {
+        self.bindings
+            .iter()
+            .rposition(|binding| &binding.name == name)
+    }
+
+    fn push(&mut self, name: StrRef, unique: bool, origins: Sites) {
+        // A new binding reuses the index of an old one that went out of scope
+        if let Some(moves) = &mut self.moves {
+            moves.remove(&self.bindings.len());
+        }
+        self.bindings.push(Binding {
+            name,
+            unique,
+            origins,
+        });
+    }
+
+    /// Leaves the scope starting at the binding of index `mark`,
+    /// checking that no reference to its bindings outlives it
+    fn exit_scope(&mut self, mark: usize, result: &Val) -> Result<()> {
+        if self.moves.is_some() {
+            let outer = self.bindings[..mark].iter().map(|binding| &binding.origins);
+            for origins in std::iter::once(&result.origins).chain(outer) {
+                if let Some((id, loc)) = origins.range(mark..).next() {
+                    return Err(errors::OwnError::Outlive {
+                        name: self.bindings[*id].name.to_string(),
+                        borrowed: loc.to_string(),
+                    }
+                    .into());
+                }
+            }
+        }
+        self.bindings.truncate(mark);
+        if let Some(moves) = &mut self.moves {
+            moves.retain(|id, _| *id < mark);
+        }
+        Ok(())
+    }
+
+    /// Uses a binding by name, moving it if it is unique and used by value
+    fn use_name(&mut self, name: &StrRef, loc: Loc, mode: Mode) -> Result<Val> {
+        // Names not bound in the function are not tracked
+        let Some(id) = self.find(name) else {
+            return Ok(Val::default());
+        };
+        let Some(moves) = &mut self.moves else {
+            return Ok(Val::default());
+        };
+        if let Some(moved) = moves.get(&id) {
+            return Err(errors::OwnError::UseAfterMove {
+                name: name.to_string(),
+                moved: moved.to_string(),
+                used: loc.to_string(),
+            }
+            .into());
+        }
+        let binding = &self.bindings[id];
+        if mode == Mode::Move && binding.unique {
+            let borrowed = self
+                .bindings
+                .iter()
+                .find_map(|binding| binding.origins.get(&id));
+            if let Some(borrowed) = borrowed {
+                return Err(errors::OwnError::MoveBorrowed {
+                    name: name.to_string(),
+                    moved: loc.to_string(),
+                    borrowed: borrowed.to_string(),
+                }
+                .into());
+            }
+            moves.insert(id, loc);
+        }
+        Ok(Val {
+            unique: binding.unique,
+            origins: binding.origins.clone(),
+        })
+    }
+
+    /// Reads a place in place, returning the binding at its root if any
+    fn place(&mut self, input: &expr::Expr) -> Result<Option<(usize, Loc)>> {
+        match input {
+            expr::Expr::Value(expr::Value::Name(name, loc)) => {
+                self.use_name(name, *loc, Mode::Read)?;
+                Ok(self.find(name).map(|id| (id, *loc)))
+            }
+            expr::Expr::Field(field) => self.place(field.val.as_ref()),
+            expr::Expr::Index(index) => {
+                let root = self.place(index.val.as_ref())?;
+                self.eval(index.index.as_ref(), Mode::Read)?;
+                Ok(root)
+            }
+            _ => {
+                self.eval(input, Mode::Read)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Binds the names in the pattern, which is matched against the value
+    fn bind(&mut self, input: &pat::Pat, val: &Val) {
+        match input {
+            pat::Pat::Ident(pat::IdentPat {
+                ident: pat::IdentKind::Value(name),
+                ty,
+                ..
+            }) => {
+                let unique = match ty {
+                    Some(pat::Type {
+                        flags: pat::PatFlags::Shared,
+                        ..
+                    }) => false,
+                    Some(pat::Type {
+                        kind: pat::TypeKind::Single(_) | pat::TypeKind::Template(..),
+                        ..
+                    }) => true,
+                    Some(pat::Type {
+                        kind: pat::TypeKind::Res(_),
+                        ..
+                    }) => false,
+                    Some(_) | None => val.unique,
+                };
+                self.push(name.clone(), unique, val.origins.clone());
+            }
+            pat::Pat::Ident(_) | pat::Pat::Lit(_) => {}
+            pat::Pat::Tuple(pat::TuplePat(list)) => {
+                // Elements of a destructured value are not known to be unique
+                let val = Val {
+                    unique: false,
+                    origins: val.origins.clone(),
+                };
+                for pat in list.iter() {
+                    self.bind(pat, &val);
+                }
+            }
+            pat::Pat::Struct(cus) => {
+                let val = Val {
+                    unique: false,
+                    origins: val.origins.clone(),
+                };
+                for pat in cus.elem.values() {
+                    self.bind(pat, &val);
+                }
+            }
+            pat::Pat::Any(pat::AnyPat(list)) => {
+                // Every alternative binds the same names
+                if let Some(pat) = list.first() {
+                    self.bind(pat, val);
+                }
+            }
+        }
+    }
+
+    fn find_loop(&mut self, label: Option<&StrRef>) -> Option<&mut LoopFrame> {
+        match label {
+            Some(label) => self
+                .loops
+                .iter_mut()
+                .rev()
+                .find(|frame| frame.label.as_ref() == Some(label)),
+            None => self.loops.last_mut(),
+        }
+    }
+
+    fn eval_body(&mut self, input: &expr::Body) -> Result<Val> {
+        let mark = self.bindings.len();
+        for bind in input.bind.iter() {
+            match bind {
+                bind::Bind::Let(bind) => {
+                    let val = self.eval(&bind.val, Mode::Move)?;
+                    self.bind(&bind.pat, &val);
+                }
+                bind::Bind::Item(item) => self.check(item)?,
+            }
+        }
+        let mut last = Val::default();
+        for expr in input.body.iter() {
+            last = self.eval(expr, Mode::Move)?;
+        }
+        if input.unit || self.moves.is_none() {
+            last = Val::default();
+        }
+        self.exit_scope(mark, &last)?;
+        Ok(last)
+    }
+
+    fn eval_loop(&mut self, input: &flow::Loop) -> Result<Val> {
+        let entry = self.moves.clone();
+        self.loops.push(LoopFrame {
+            label: input.label.clone(),
+            exit: None,
+            next: None,
+            val: Val::default(),
+        });
+        self.eval_body(&input.body)?;
+        let mut frame = self.loops.pop().expect("loop frame should be pushed");
+        let again = join(entry.clone(), join(self.moves.take(), frame.next.take()));
+        if again != entry {
+            // Runs the body once more to find uses of values moved by the previous iteration
+            self.moves = again;
+            self.loops.push(frame);
+            self.eval_body(&input.body)?;
+            frame = self.loops.pop().expect("loop frame should be pushed");
+        }
+        self.moves = frame.exit;
+        Ok(frame.val)
+    }
+
+    fn eval_flow(&mut self, input: &flow::Flow) -> Result<Val> {
+        match input {
+            flow::Flow::If(flow) => {
+                self.eval(flow.cond.as_ref(), Mode::Read)?;
+                let entry = self.moves.clone();
+                let mut val = self.eval_body(&flow.body)?;
+                let moves = std::mem::replace(&mut self.moves, entry);
+                if let Some(other) = &flow.other {
+                    val.merge(self.eval(other.as_ref(), Mode::Move)?);
+                }
+                self.moves = join(moves, self.moves.take());
+                Ok(val)
+            }
+            flow::Flow::Loop(flow) => self.eval_loop(flow),
+            flow::Flow::Break(flow) => {
+                let val = match &flow.val {
+                    Some(val) => self.eval(val.as_ref(), Mode::Move)?,
+                    None => Val::default(),
+                };
+                let moves = self.moves.take();
+                // Unknown labels are reported by the flow check
+                if let Some(frame) = self.find_loop(flow.label.as_ref()) {
+                    frame.exit = join(frame.exit.take(), moves);
+                    frame.val.merge(val);
+                }
+                Ok(Val::default())
+            }
+            flow::Flow::Continue(flow) => {
+                let moves = self.moves.take();
+                if let Some(frame) = self.find_loop(flow.label.as_ref()) {
+                    frame.next = join(frame.next.take(), moves);
+                }
+                Ok(Val::default())
+            }
+            flow::Flow::Return(flow) => {
+                if let Some(val) = &flow.val {
+                    let val = self.eval(val.as_ref(), Mode::Move)?;
+                    self.escape(&val)?;
+                }
+                self.moves = None;
+                Ok(Val::default())
+            }
+            flow::Flow::Match(flow) => {
+                // The value is only moved if some arm binds it by value
+                let binds = flow.arms.iter().any(|arm| {
+                    !matches!(
+                        arm.pat,
+                        pat::Pat::Ident(pat::IdentPat {
+                            ident: pat::IdentKind::Unused,
+                            ..
+                        }) | pat::Pat::Lit(_)
+                    )
+                });
+                let mode = if binds { Mode::Move } else { Mode::Read };
+                let scrutinee = self.eval(flow.val.as_ref(), mode)?;
+                let entry = self.moves.take();
+                let mut val = Val::default();
+                let mut moves = None;
+                for arm in flow.arms.iter() {
+                    self.moves = entry.clone();
+                    let mark = self.bindings.len();
+                    self.bind(&arm.pat, &scrutinee);
+                    if let Some(guard) = &arm.guard {
+                        self.eval(guard, Mode::Read)?;
+                    }
+                    let mut arm_val = self.eval(&arm.val, Mode::Move)?;
+                    if self.moves.is_none() {
+                        arm_val = Val::default();
+                    }
+                    self.exit_scope(mark, &arm_val)?;
+                    val.merge(arm_val);
+                    moves = join(moves, self.moves.take());
+                }
+                self.moves = moves;
+                Ok(val)
+            }
+        }
+    }
+
+    fn eval(&mut self, input: &expr::Expr, mode: Mode) -> Result<Val> {
+        let val = match input {
+            expr::Expr::Prim(_) => Val::default(),
+            expr::Expr::Value(expr::Value::Name(name, loc)) => self.use_name(name, *loc, mode)?,
+            expr::Expr::Value(_) => Val::default(),
+            expr::Expr::UnExpr(expr) => match expr.op {
+                UnOp::Ref => {
+                    let mut origins = Sites::new();
+                    if let Some((id, loc)) = self.place(expr.val.as_ref())? {
+                        origins.insert(id, loc);
+                    }
+                    Val {
+                        unique: false,
+                        origins,
+                    }
+                }
+                UnOp::Neg => {
+                    self.eval(expr.val.as_ref(), Mode::Read)?;
+                    Val::default()
+                }
+            },
+            expr::Expr::BinExpr(expr) => {
+                self.eval(expr.lhs.as_ref(), Mode::Read)?;
+                self.eval(expr.rhs.as_ref(), Mode::Read)?;
+                Val::default()
+            }
+            expr::Expr::Cast(cast) => {
+                // Only numbers are cast, which are copied
+                self.eval(cast.val.as_ref(), Mode::Read)?;
+                Val::default()
+            }
+            // The value held is moved out, or the value is moved into the return
+            expr::Expr::Try(expr) => {
+                self.eval(expr.val.as_ref(), Mode::Move)?;
+                Val::default()
+            }
+            expr::Expr::Body(body) => self.eval_body(body)?,
+            expr::Expr::Flow(flow) => self.eval_flow(flow)?,
+            expr::Expr::Tuple(expr::Tuple { elem })
+            | expr::Expr::Array(expr::Array::List(elem)) => {
+                let mut val = Val::default();
+                for expr in elem.iter() {
+                    val.merge(self.eval(expr, Mode::Move)?);
+                }
+                val
+            }
+            expr::Expr::Array(expr::Array::Repeat(val, _)) => {
+                self.eval(val.as_ref(), Mode::Move)?
+            }
+            expr::Expr::Struct(cus) => {
+                let mut val = Val::default();
+                for expr in cus.elem.values().chain(cus.base.as_deref()) {
+                    val.merge(self.eval(expr, Mode::Move)?);
+                }
+                val.unique = true;
+                val
+            }
+            expr::Expr::Field(_) | expr::Expr::Index(_) => {
+                // Reading a part of a value never moves the whole value
+                let origins = match self.place(input)? {
+                    Some((id, _)) => self.bindings[id].origins.clone(),
+                    None => Sites::new(),
+                };
+                Val {
+                    unique: false,
+                    origins,
+                }
+            }
+            expr::Expr::Assign(assign) => {
+                let val = self.eval(assign.val.as_ref(), Mode::Move)?;
+                let root = match (assign.op, assign.place.as_ref()) {
+                    // Assigning a whole binding initializes it again
+                    (None, expr::Expr::Value(expr::Value::Name(name, _))) => {
+                        let id = self.find(name);
+                        if let (Some(id), Some(moves)) = (id, &mut self.moves) {
+                            moves.remove(&id);
+                        }
+                        id
+                    }
+                    (_, place) => self.place(place)?.map(|(id, _)| id),
+                };
+                if let Some(id) = root {
+                    self.bindings[id].origins.extend(val.origins);
+                }
+                Val::default()
+            }
+            expr::Expr::Closure(closure) => self.eval_closure(closure)?,
+            expr::Expr::Call(call) => {
+                // Calling a function value does not consume it
+                self.eval(call.fun.as_ref(), Mode::Read)?;
+                for expr in call.args.iter() {
+                    self.eval(expr, Mode::Move)?;
+                }
+                Val::default()
+            }
+        };
+        Ok(val)
+    }
+
+    /// Runs the closure with a fresh state for the body of another function
+    fn function(&mut self, f: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        let bindings = std::mem::take(&mut self.bindings);
+        let moves = self.moves.replace(Sites::new());
+        let loops = std::mem::take(&mut self.loops);
+        let result = f(self);
+        self.bindings = bindings;
+        self.moves = moves;
+        self.loops = loops;
+        result
+    }
+
+    /// Evaluates a closure, which moves its captures into the environment it holds
+    ///
+    /// The closure is unique if it captures any unique value, and borrows anything its captures borrow
+    fn eval_closure(&mut self, input: &expr::Closure) -> Result<Val> {
+        let mut val = Val::default();
+        let mut captures = Vec::new();
+        for (name, loc) in capture::captures(input) {
+            if self.find(&name).is_some() {
+                let capture = self.use_name(&name, loc, Mode::Move)?;
+                captures.push((name, capture.unique));
+                val.merge(capture);
+            }
+        }
+        self.function(|this| {
+            // Captures are owned by the environment, so the body may borrow them but never return them borrowed
+            for (name, unique) in captures {
+                this.push(name, unique, Sites::new());
+            }
+            this.bind(&input.args, &Val::default());
+            let ret = this.eval(input.body.as_ref(), Mode::Move)?;
+            this.escape(&ret)
+        })
+        .context("in closure")?;
+        Ok(val)
+    }
+
+    /// Checks that a value leaving the function does not borrow any local
+    fn escape(&self, val: &Val) -> Result<()> {
+        match val.origins.iter().next() {
+            Some((id, loc)) => Err(errors::OwnError::Escape {
+                name: self.bindings[*id].name.to_string(),
+                borrowed: loc.to_string(),
+            }
+            .into()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Check<module::Module> for OwnCheck {
+    type Output = ();
+
+    fn check(&mut self, input: &module::Module) -> Result<()> {
+        for item in input.items.iter() {
+            self.check(item)?;
+        }
+        Ok(())
+    }
+}
+
+impl Check<item::Item> for OwnCheck {
+    type Output = ();
+
+    fn check(&mut self, input: &item::Item) -> Result<()> {
+        match &input.kind {
+            item::ItemKind::Fun(fun) => {
+                // Functions never see the locals of the outer function
+                self.function(|this| {
+                    this.bind(&fun.args, &Val::default());
+                    let val = this.eval_body(&fun.body)?;
+                    this.escape(&val)
+                })
+                .with_context(|| format!("in function `{}`", input.name))
+            }
+            item::ItemKind::For(items) => {
+                for item in items.items.iter() {
+                    self.check(item)?;
+                }
+                Ok(())
+            }
+            item::ItemKind::Mod(module) => self.check(module),
+            item::ItemKind::Extern(_)
+            | item::ItemKind::Cus(_)
+            | item::ItemKind::Interface(_)
+            | item::ItemKind::Val(_) => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use crate::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// A type imported into the checked file, with the file defining it and its name there
+struct Imported<'a> {
+    file: &'a Path,
+    /// The module defining the type, which is the file or one of its inline modules
+    module: &'a module::Module,
+    name: StrRef,
+}
+
+/// Returns whether something of the visibility defined in the owner file is visible to the user file
+pub(crate) fn visible(public: module::Public, owner: &Path, user: &Path) -> bool {
+    match public {
+        module::Public::Pub => true,
+        module::Public::Module => owner.parent().is_none_or(|module| user.starts_with(module)),
+        module::Public::Priv => owner == user,
+    }
+}
+
+/// Checks that `use` statements only import items visible to the importing file, and finds
+/// the fields of the imported types that are not visible to it
+///
+/// Files are known by their paths, and a module is the directory holding its files. Fields
+/// are used on values whose types may only be told by the type check, which reports the
+/// fields given by [`Self::hidden`]
+#[derive(Default)]
+pub struct PubCheck<'a> {
+    files: HashMap<&'a Path, &'a module::Module>,
+    /// The file being checked
+    file: PathBuf,
+    types: HashMap<StrRef, Imported<'a>>,
+}
+
+impl<'a> PubCheck<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the module parsed from the file, so that its items can be imported and checked
+    pub fn insert(&mut self, file: &'a Path, module: &'a module::Module) {
+        self.files.insert(file, module);
+    }
+
+    /// Returns the fields of the types imported into the file checked last that are not
+    /// visible to it, by the names the types are imported as, see [`TypeCheck::import`]
+    ///
+    /// [`TypeCheck::import`]: super::TypeCheck::import
+    pub fn hidden(&self) -> HashMap<StrRef, HashSet<StrRef>> {
+        self.types
+            .iter()
+            .map(|(name, imported)| {
+                let elems = imported
+                    .module
+                    .items
+                    .iter()
+                    .find_map(|item| match &item.kind {
+                        item::ItemKind::Cus(item::Cus::Struct(
+                            pat::Pat::Tuple(pat::TuplePat(list)),
+                            elems,
+                        )) if item.name == imported.name => Some(list.iter().zip(elems.iter())),
+                        _ => None,
+                    });
+                let hidden = elems
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(pat, elem)| match pat {
+                        pat::Pat::Ident(pat::IdentPat {
+                            ident: pat::IdentKind::Value(field),
+                            ..
+                        }) if !visible(elem.public, imported.file, &self.file) => {
+                            Some(field.clone())
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                (name.clone(), hidden)
+            })
+            .collect()
+    }
+
+    /// Checks the imports of the module, remembering the types imported
+    fn check_imports(&mut self, input: &'a module::Module) -> Result<()> {
+        self.types.clear();
+        'imports: for import in input.import.iter() {
+            let file = import.file.path();
+            // Files not given are left to the name resolution
+            let Some(mut owner) = self.files.get(file).copied() else {
+                continue;
+            };
+            // Inline modules missing or invisible are reported by the import check
+            for name in import.path.iter() {
+                let inner = owner.items.iter().find_map(|item| match &item.kind {
+                    item::ItemKind::Mod(inner) if &item.name == name => Some(inner),
+                    _ => None,
+                });
+                match inner {
+                    Some(inner) => owner = inner,
+                    None => continue 'imports,
+                }
+            }
+            let items = match &import.kind {
+                module::ImportKind::Items(items) => items,
+                // Globs only import the items visible, and items of aliased files are
+                // referred to by paths, which are not types
+                module::ImportKind::Glob => {
+                    for found in owner.items.iter() {
+                        if let item::ItemKind::Cus(_) = found.kind {
+                            if visible(found.public, file, &self.file) {
+                                let imported = Imported {
+                                    file,
+                                    module: owner,
+                                    name: found.name.clone(),
+                                };
+                                self.types.insert(found.name.clone(), imported);
+                            }
+                        }
+                    }
+                    continue;
+                }
+                module::ImportKind::Alias(_) => continue,
+            };
+            for item in items.iter() {
+                let (name, is_type) = match &item.kind {
+                    module::ImportItemKind::Value(name) => (name, false),
+                    module::ImportItemKind::Type(name) => (name, true),
+                };
+                let Some(found) = owner.items.iter().find(|found| {
+                    &found.name == name && matches!(found.kind, item::ItemKind::Cus(_)) == is_type
+                }) else {
+                    continue;
+                };
+                if !visible(found.public, file, &self.file) {
+                    return Err(errors::PubError::PrivateItem {
+                        name: name.to_string(),
+                        file: file.display().to_string(),
+                    }
+                    .into());
+                }
+                if is_type {
+                    let alias = item.alias.as_ref().unwrap_or(name);
+                    self.types.insert(
+                        alias.clone(),
+                        Imported {
+                            file,
+                            module: owner,
+                            name: name.clone(),
+                        },
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Check<Path> for PubCheck<'_> {
+    type Output = ();
+
+    /// Checks the file added before
+    fn check(&mut self, input: &Path) -> Result<()> {
+        let module = self
+            .files
+            .get(input)
+            .copied()
+            .ok_or_else(|| errors::PathError::ModuleNotFound(input.display().to_string()))?;
+        self.file = input.to_owned();
+        self.check_imports(module)
+    }
+}
@@ -0,0 +1,548 @@
+use super::{Imported, Scope};
+use crate::builtin;
+use crate::prelude::*;
+use crate::scope::Scoped;
+use crate::visit::{self, Visit};
+use crate::Warnings;
+use imuc_lexer::token::ResVal;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Identifies a definition in [`Resolution`], numbered in the order the definitions are found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DefId(pub usize);
+
+/// What a name is defined as
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DefKind {
+    /// A variable bound by a pattern at the location, including the arguments of functions
+    Local(Loc),
+    /// An item of the file, of an inline module or of a body
+    Item,
+    /// A template parameter of a function, naming a type within it
+    Templ,
+    /// An item imported by name or by a glob import, with the item it refers to if known
+    Import(Option<Imported>),
+    /// A file imported under an alias, whose items are referred to like `alias.item`
+    Alias,
+    /// A sum type known to every module, like `Option`, see [`crate::builtin`]
+    Builtin,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Def {
+    pub name: StrRef,
+    pub kind: DefKind,
+}
+
+/// The definitions found in a module, and the definition each name refers to by the location
+/// it is used at
+///
+/// Names created by desugaring share the default location, thus are left out of the uses
+#[derive(Debug, Default)]
+pub struct Resolution {
+    pub defs: Vec<Def>,
+    pub uses: BTreeMap<Loc, DefId>,
+}
+
+impl Resolution {
+    pub fn def(&self, id: DefId) -> &Def {
+        &self.defs[id.0]
+    }
+
+    /// Returns the definition of the name used at the location
+    pub fn get(&self, loc: Loc) -> Option<&Def> {
+        self.uses.get(&loc).map(|id| self.def(*id))
+    }
+}
+
+/// Collects the names bound by the pattern, taking the first of alternatives
+fn names(input: &pat::Pat, names: &mut BTreeSet<StrRef>) {
+    match input {
+        pat::Pat::Ident(pat::IdentPat {
+            ident: pat::IdentKind::Value(name),
+            ..
+        }) => {
+            names.insert(name.clone());
+        }
+        pat::Pat::Ident(_) | pat::Pat::Lit(_) => {}
+        pat::Pat::Tuple(pat::TuplePat(list)) => {
+            for pat in list.iter() {
+                self::names(pat, names);
+            }
+        }
+        pat::Pat::Any(pat::AnyPat(list)) => {
+            if let Some(pat) = list.first() {
+                self::names(pat, names);
+            }
+        }
+        pat::Pat::Struct(cus) => {
+            for pat in cus.elem.values() {
+                self::names(pat, names);
+            }
+        }
+    }
+}
+
+/// A name visible to the current scope, where locals are only visible to their own function
+pub(crate) struct Binding {
+    name: StrRef,
+    id: DefId,
+    local: bool,
+    depth: usize,
+}
+
+/// Resolves every name used in a module to its definition
+///
+/// Items and imports are visible to the whole module including its inline modules, while the
+/// items of a body are visible to the whole body. Arguments and `let` bindings are visible
+/// after they are bound, to closures within but never to nested functions. Types are resolved
+/// among items, imports and the template parameters of the function, and `self` among locals
+///
+/// Names brought by glob imports are found through the [`Scope`] of the file if given. Without
+/// it, names not found in a module with glob imports are assumed to come from them. Unused
+/// variables and imports, and variables shadowing names in scope, are reported as warnings
+#[derive(Default)]
+pub struct ResolveCheck<'a> {
+    pub warnings: Warnings,
+    scope: Option<&'a Scope>,
+    bindings: Vec<Binding>,
+    /// The number of functions entered
+    depth: usize,
+    /// The number of inline modules entered
+    inline: usize,
+    /// The number of enclosing modules whose glob imports are not known
+    unknown: usize,
+    /// Names brought by glob imports of the file, defined when first used
+    globs: HashMap<StrRef, DefId>,
+    /// Names of the items of inline modules by the definitions of the modules
+    mods: HashMap<DefId, HashSet<StrRef>>,
+    used: Vec<bool>,
+    resolution: Resolution,
+}
+
+impl<'a> ResolveCheck<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolves glob imports of the file through the scope given by
+    /// [`ImportCheck`](super::ImportCheck)
+    pub fn with_scope(scope: &'a Scope) -> Self {
+        Self {
+            scope: Some(scope),
+            ..Self::default()
+        }
+    }
+
+    fn define(&mut self, name: &StrRef, kind: DefKind) -> DefId {
+        let id = DefId(self.resolution.defs.len());
+        self.resolution.defs.push(Def {
+            name: name.clone(),
+            kind,
+        });
+        self.used.push(false);
+        id
+    }
+
+    fn bind_def(&mut self, name: &StrRef, id: DefId, local: bool) {
+        self.bindings.push(Binding {
+            name: name.clone(),
+            id,
+            local,
+            depth: self.depth,
+        });
+    }
+
+    /// Drops the bindings made since the mark, warning about the locals never used
+    fn release(&mut self, mark: usize) {
+        for binding in self.bindings.drain(mark..) {
+            let def = &self.resolution.defs[binding.id.0];
+            if let DefKind::Local(loc) = def.kind {
+                // The receiver of a method is never reported
+                if !self.used[binding.id.0] && loc != Loc::default() && &*def.name != "self" {
+                    self.warnings
+                        .push(errors::ResolveError::UnusedVariable(def.name.to_string()));
+                }
+            }
+        }
+    }
+
+    /// Finds the innermost binding of the name, skipping locals if not wanted
+    fn lookup(&self, name: &StrRef, locals: bool) -> Option<DefId> {
+        self.bindings
+            .iter()
+            .rev()
+            .find(|binding| {
+                &binding.name == name && (!binding.local || locals && binding.depth == self.depth)
+            })
+            .map(|binding| binding.id)
+    }
+
+    /// Finds the name among the glob imports of the file
+    fn glob(&mut self, name: &StrRef) -> Option<DefId> {
+        if let Some(id) = self.globs.get(name) {
+            return Some(*id);
+        }
+        let imported = self.scope?.get(name)?.clone();
+        let id = self.define(name, DefKind::Import(Some(imported)));
+        self.globs.insert(name.clone(), id);
+        Some(id)
+    }
+
+    /// Resolves the name, marking its definition used. Names not found are fine only if they
+    /// may come from unknown glob imports
+    fn resolve(&mut self, name: &StrRef, locals: bool) -> Result<Option<DefId>, ()> {
+        match self.lookup(name, locals).or_else(|| self.glob(name)) {
+            Some(id) => {
+                self.used[id.0] = true;
+                Ok(Some(id))
+            }
+            None if self.unknown > 0 => Ok(None),
+            None => Err(()),
+        }
+    }
+
+    fn resolve_value(&mut self, name: &StrRef, loc: Loc) -> Result<Option<DefId>> {
+        let id = self
+            .resolve(name, true)
+            .map_err(|()| errors::ResolveError::Undefined(name.to_string()))?;
+        if let (Some(id), false) = (id, loc == Loc::default()) {
+            self.resolution.uses.insert(loc, id);
+        }
+        Ok(id)
+    }
+
+    fn resolve_type(&mut self, input: &pat::Type) -> Result<()> {
+        match &input.kind {
+            pat::TypeKind::Wildcard | pat::TypeKind::Res(_) => Ok(()),
+            pat::TypeKind::Single(name) => {
+                self.resolve(name, false)
+                    .map_err(|()| errors::ResolveError::UndefinedType(name.to_string()))?;
+                Ok(())
+            }
+            pat::TypeKind::Template(name, args) => {
+                self.resolve(name, false)
+                    .map_err(|()| errors::ResolveError::UndefinedType(name.to_string()))?;
+                for arg in args.iter() {
+                    self.resolve_type(arg)?;
+                }
+                Ok(())
+            }
+            pat::TypeKind::Fun(args, ret) => {
+                for arg in args.iter() {
+                    self.resolve_type(arg)?;
+                }
+                match ret {
+                    Some(ret) => self.resolve_type(ret),
+                    None => Ok(()),
+                }
+            }
+            pat::TypeKind::Array(elem, _) | pat::TypeKind::Slice(elem) => self.resolve_type(elem),
+        }
+    }
+
+    /// Binds the names in the pattern as locals, where the alternatives of a pattern bind the
+    /// same names thus only the first is taken
+    fn bind(&mut self, input: &pat::Pat) {
+        match input {
+            pat::Pat::Ident(pat::IdentPat {
+                ident: pat::IdentKind::Value(name),
+                loc,
+                ..
+            }) => {
+                if *loc != Loc::default() && self.lookup(name, true).is_some() {
+                    self.warnings
+                        .push(errors::ResolveError::Shadowed(name.to_string()));
+                }
+                let id = self.define(name, DefKind::Local(*loc));
+                self.bind_def(name, id, true);
+            }
+            pat::Pat::Ident(_) | pat::Pat::Lit(_) => {}
+            pat::Pat::Tuple(pat::TuplePat(list)) => {
+                for pat in list.iter() {
+                    self.bind(pat);
+                }
+            }
+            pat::Pat::Any(pat::AnyPat(list)) => {
+                if let Some(pat) = list.first() {
+                    self.bind(pat);
+                }
+            }
+            pat::Pat::Struct(cus) => {
+                for pat in cus.elem.values() {
+                    self.bind(pat);
+                }
+            }
+        }
+    }
+
+    /// Binds the names of the items, where implementations bring no names
+    fn bind_items<'i>(&mut self, items: impl Iterator<Item = &'i item::Item>) {
+        for item in items {
+            if let item::ItemKind::For(_) = item.kind {
+                continue;
+            }
+            let id = self.define(&item.name, DefKind::Item);
+            self.bind_def(&item.name, id, false);
+            if let item::ItemKind::Mod(module) = &item.kind {
+                let names = module.items.iter().map(|item| item.name.clone()).collect();
+                self.mods.insert(id, names);
+            }
+        }
+    }
+
+    /// Binds the names of the imports, returning whether the module has glob imports
+    ///
+    /// The scope only knows the imports of the file, not of its inline modules
+    fn bind_imports(&mut self, input: &module::Module) -> bool {
+        let mut glob = false;
+        for import in input.import.iter() {
+            match &import.kind {
+                module::ImportKind::Items(items) => {
+                    for item in items.iter() {
+                        let (module::ImportItemKind::Value(name)
+                        | module::ImportItemKind::Type(name)) = &item.kind;
+                        let name = item.alias.as_ref().unwrap_or(name);
+                        let imported = match (self.scope, self.inline) {
+                            (Some(scope), 0) => scope.get(name).cloned(),
+                            _ => None,
+                        };
+                        let id = self.define(name, DefKind::Import(imported));
+                        self.bind_def(name, id, false);
+                    }
+                }
+                module::ImportKind::Glob => glob = true,
+                module::ImportKind::Alias(alias) => {
+                    let id = self.define(alias, DefKind::Alias);
+                    self.bind_def(alias, id, false);
+                }
+            }
+        }
+        glob
+    }
+
+    /// Checks the item referred to like `module.item`, where the module is either an inline
+    /// module or a file imported under an alias
+    fn check_path(&self, id: DefId, module: &StrRef, name: &StrRef) -> Result<()> {
+        let found = match (&self.resolution.defs[id.0].kind, self.scope) {
+            (DefKind::Alias, Some(scope)) => scope.get_in(module, name).is_some(),
+            (DefKind::Item, _) => self.mods.get(&id).is_none_or(|names| names.contains(name)),
+            _ => true,
+        };
+        if found {
+            Ok(())
+        } else {
+            Err(errors::ResolveError::Undefined(format!("{module}.{name}")).into())
+        }
+    }
+}
+
+impl Scoped for ResolveCheck<'_> {
+    type Binding = Binding;
+
+    fn bindings(&mut self) -> &mut Vec<Binding> {
+        &mut self.bindings
+    }
+
+    fn leave(&mut self, mark: usize) {
+        self.release(mark);
+    }
+}
+
+impl Check<module::Module> for ResolveCheck<'_> {
+    type Output = Resolution;
+
+    fn check(&mut self, input: &module::Module) -> Result<Resolution> {
+        self.globs.clear();
+        self.mods.clear();
+        self.used.clear();
+        let mark = self.bindings.len();
+        for sum in builtin::SUMS {
+            let name = StrRef::from(sum.name);
+            let id = self.define(&name, DefKind::Builtin);
+            self.bind_def(&name, id, false);
+        }
+        let result = self.visit_module(input);
+        self.release(mark);
+        result?;
+        for (def, used) in self.resolution.defs.iter().zip(self.used.iter()) {
+            if let (DefKind::Import(_) | DefKind::Alias, false) = (&def.kind, used) {
+                self.warnings
+                    .push(errors::ResolveError::UnusedImport(def.name.to_string()));
+            }
+        }
+        Ok(std::mem::take(&mut self.resolution))
+    }
+}
+
+impl Visit for ResolveCheck<'_> {
+    fn visit_module(&mut self, input: &module::Module) -> Result<()> {
+        let mark = self.bindings.len();
+        let glob = self.bind_imports(input);
+        self.bind_items(input.items.iter());
+        let unknown = usize::from(glob && (self.scope.is_none() || self.inline > 0));
+        self.unknown += unknown;
+        self.inline += 1;
+        let result = visit::walk_module(self, input);
+        self.inline -= 1;
+        self.unknown -= unknown;
+        self.release(mark);
+        result
+    }
+
+    fn visit_item(&mut self, input: &item::Item) -> Result<()> {
+        match &input.kind {
+            item::ItemKind::Fun(fun) => {
+                let mark = self.warnings.len();
+                self.depth += 1;
+                let result = self
+                    .scoped(|this| {
+                        for templ in fun.templ.iter() {
+                            let item::Templ::Item(templ) = templ else {
+                                continue;
+                            };
+                            for req in templ.req.iter() {
+                                this.resolve_type(req)?;
+                            }
+                            let id = this.define(&templ.name, DefKind::Templ);
+                            this.bind_def(&templ.name, id, false);
+                        }
+                        this.visit_pat(&fun.args)?;
+                        this.bind(&fun.args);
+                        this.visit_body(&fun.body)
+                    })
+                    .with_context(|| format!("in function `{}`", input.name));
+                self.depth -= 1;
+                self.warnings
+                    .context_since(mark, || format!("in function `{}`", input.name));
+                result
+            }
+            item::ItemKind::Extern(ext) => {
+                visit::walk_item(self, input)?;
+                match &ext.ret {
+                    Some(ret) => self.resolve_type(ret),
+                    None => Ok(()),
+                }
+            }
+            item::ItemKind::For(items) => {
+                self.resolve_type(&items.ty)?;
+                if let Some(interface) = &items.interface {
+                    self.resolve_type(interface)?;
+                }
+                visit::walk_item(self, input)
+            }
+            item::ItemKind::Interface(interface) => {
+                visit::walk_item(self, input)?;
+                for ret in interface.decls.iter().filter_map(|decl| decl.ret.as_ref()) {
+                    self.resolve_type(ret)?;
+                }
+                Ok(())
+            }
+            _ => visit::walk_item(self, input),
+        }
+    }
+
+    fn visit_body(&mut self, input: &expr::Body) -> Result<()> {
+        self.scoped(|this| {
+            this.bind_items(input.bind.iter().filter_map(|bind| match bind {
+                bind::Bind::Item(item) => Some(item),
+                bind::Bind::Let(_) => None,
+            }));
+            visit::walk_body(this, input)
+        })
+    }
+
+    fn visit_bind(&mut self, input: &bind::Bind) -> Result<()> {
+        match input {
+            bind::Bind::Let(bind) => {
+                self.visit_expr(&bind.val)?;
+                self.visit_pat(&bind.pat)?;
+                self.bind(&bind.pat);
+                Ok(())
+            }
+            bind::Bind::Item(item) => self.visit_item(item),
+        }
+    }
+
+    fn visit_expr(&mut self, input: &expr::Expr) -> Result<()> {
+        match input {
+            expr::Expr::Value(expr::Value::Name(name, loc)) => {
+                self.resolve_value(name, *loc)?;
+                Ok(())
+            }
+            expr::Expr::Value(expr::Value::Res(ResVal::SelfValue)) => {
+                self.resolve_value(&StrRef::from("self"), Loc::default())?;
+                Ok(())
+            }
+            expr::Expr::Field(field) => match field.val.as_ref() {
+                expr::Expr::Value(expr::Value::Name(name, loc)) => {
+                    match self.resolve_value(name, *loc)? {
+                        Some(id) => self.check_path(id, name, &field.name),
+                        None => Ok(()),
+                    }
+                }
+                _ => visit::walk_expr(self, input),
+            },
+            expr::Expr::Struct(cus) => {
+                self.resolve_type(&cus.ty)?;
+                visit::walk_expr(self, input)
+            }
+            expr::Expr::Cast(cast) => {
+                self.resolve_type(&cast.ty)?;
+                visit::walk_expr(self, input)
+            }
+            expr::Expr::Closure(closure) => self.scoped(|this| {
+                this.visit_pat(&closure.args)?;
+                this.bind(&closure.args);
+                this.visit_expr(closure.body.as_ref())
+            }),
+            _ => visit::walk_expr(self, input),
+        }
+    }
+
+    fn visit_flow(&mut self, input: &flow::Flow) -> Result<()> {
+        match input {
+            flow::Flow::Match(flow) => {
+                self.visit_expr(flow.val.as_ref())?;
+                for arm in flow.arms.iter() {
+                    self.scoped(|this| {
+                        this.visit_pat(&arm.pat)?;
+                        this.bind(&arm.pat);
+                        if let Some(guard) = &arm.guard {
+                            this.visit_expr(guard)?;
+                        }
+                        this.visit_expr(&arm.val)
+                    })?;
+                }
+                Ok(())
+            }
+            _ => visit::walk_flow(self, input),
+        }
+    }
+
+    fn visit_pat(&mut self, input: &pat::Pat) -> Result<()> {
+        match input {
+            pat::Pat::Ident(pat::IdentPat { ty: Some(ty), .. }) => self.resolve_type(ty),
+            pat::Pat::Struct(cus) => {
+                self.resolve_type(&cus.ty)?;
+                visit::walk_pat(self, input)
+            }
+            // Every alternative must bind the same names, as only the first binds them
+            pat::Pat::Any(pat::AnyPat(list)) => {
+                let mut first = BTreeSet::new();
+                if let Some(pat) = list.first() {
+                    names(pat, &mut first);
+                }
+                for pat in list.iter().skip(1) {
+                    let mut found = BTreeSet::new();
+                    names(pat, &mut found);
+                    if let Some(name) = first.symmetric_difference(&found).next() {
+                        return Err(errors::ResolveError::OrPattern(name.to_string()).into());
+                    }
+                }
+                visit::walk_pat(self, input)
+            }
+            _ => visit::walk_pat(self, input),
+        }
+    }
+}
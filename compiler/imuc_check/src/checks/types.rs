@@ -0,0 +1,1937 @@
+use crate::builtin;
+use crate::hir;
+use crate::infer::Infer;
+use crate::prelude::*;
+use crate::scope::Scoped;
+use crate::ty::VarKind;
+use imuc_lexer::token::{BinOp, ResTy, ResVal, UnOp};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// A name visible to the current scope with its type, where locals are only visible to their
+/// own function
+#[derive(Clone)]
+pub(crate) struct Binding {
+    name: StrRef,
+    ty: Ty,
+    local: bool,
+    /// The template parameters of a generic function, given new types on every use
+    templ: Vec<hir::Templ>,
+    /// The bindings of the items of an inline module, used like `module.item`
+    items: Option<Vec<Binding>>,
+}
+
+/// A loop that `break` may refer to, collecting the type of its break values
+struct LoopFrame {
+    label: Option<StrRef>,
+    ty: Ty,
+}
+
+/// A closure being checked, with the number of bindings made outside of it and the locals
+/// it captures
+struct ClosureFrame {
+    mark: usize,
+    captures: Vec<(StrRef, Ty)>,
+}
+
+/// The state of the function being checked, saved while checking a nested function
+struct FunFrame {
+    /// The number of bindings made outside of the function
+    base: usize,
+    loops: Vec<LoopFrame>,
+    closures: Vec<ClosureFrame>,
+    /// The type of the values returned by `return`
+    returns: Ty,
+    templ: Vec<hir::Templ>,
+    /// The types given to the template parameters of the functions used, with the interfaces
+    /// they must implement
+    bounds: Vec<(Ty, StrRef)>,
+    /// The rules on the operands of built-in operations, checked once their types are told
+    rules: Vec<Rule>,
+}
+
+impl Default for FunFrame {
+    fn default() -> Self {
+        Self {
+            base: 0,
+            loops: Vec::new(),
+            closures: Vec::new(),
+            returns: Ty::Never,
+            templ: Vec::new(),
+            bounds: Vec::new(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// A rule on the types of a built-in operation
+enum Rule {
+    /// The type of the operands of the operator, where a negation is written as [`BinOp::Sub`]
+    Operand(BinOp, Ty),
+    /// The types of the value cast and of its target
+    Cast(Ty, Ty),
+    /// The types of the value indexed and of the index, which is unknown for ranges
+    Index(Ty, Ty),
+}
+
+/// Kinds of types taking part in built-in operations
+#[derive(Clone, Copy, PartialEq)]
+enum Num {
+    Int,
+    Float,
+    Ptr,
+    Bool,
+}
+
+impl Num {
+    fn of(ty: &Ty) -> Option<Num> {
+        match ty {
+            Ty::Res(
+                ResTy::I8 | ResTy::I16 | ResTy::I32 | ResTy::I64 | ResTy::I128 | ResTy::U128,
+            ) => Some(Num::Int),
+            Ty::Res(ResTy::F32 | ResTy::F64) => Some(Num::Float),
+            Ty::Res(ResTy::Ptr) => Some(Num::Ptr),
+            Ty::Res(ResTy::Bool) => Some(Num::Bool),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the symbol the operator is written with
+fn symbol(op: BinOp) -> &'static str {
+    match op {
+        BinOp::Add => "+",
+        BinOp::Sub => "-",
+        BinOp::Mul => "*",
+        BinOp::Div => "/",
+        BinOp::Mod => "%",
+        BinOp::Or => "|",
+        BinOp::And => "&",
+        BinOp::Xor => "^",
+        BinOp::Eq => "==",
+        BinOp::Lt => "<",
+        BinOp::Le => "<=",
+        BinOp::Gt => ">",
+        BinOp::Ge => ">=",
+        BinOp::Range => "..",
+        BinOp::RangeEq => "..=",
+    }
+}
+
+/// Returns whether the built-in operator takes operands of the known type
+fn operand(op: BinOp, ty: &Ty) -> bool {
+    let num = Num::of(ty);
+    match op {
+        // Adding strings concatenates them into a new one
+        BinOp::Add => {
+            *ty == Ty::Res(ResTy::Str) || matches!(num, Some(Num::Int | Num::Float | Num::Ptr))
+        }
+        BinOp::Sub => matches!(num, Some(Num::Int | Num::Float | Num::Ptr)),
+        BinOp::Mul | BinOp::Div | BinOp::Mod => matches!(num, Some(Num::Int | Num::Float)),
+        BinOp::Or | BinOp::And | BinOp::Xor => matches!(num, Some(Num::Int | Num::Bool)),
+        BinOp::Eq => true,
+        // Strings are ordered by their bytes
+        BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge => {
+            *ty == Ty::Res(ResTy::Str) || matches!(num, Some(Num::Int | Num::Float | Num::Ptr))
+        }
+        BinOp::Range | BinOp::RangeEq => num == Some(Num::Int),
+    }
+}
+
+/// Checks whether a value of a known type can be cast to the other known type
+///
+/// See [`expr::Cast`] for the casts allowed
+fn cast(from: &Ty, to: &Ty) -> Result<()> {
+    let kinds = (Num::of(from), Num::of(to));
+    let (from, to) = (from.to_string(), to.to_string());
+    match kinds {
+        (Some(Num::Ptr), Some(Num::Float)) | (Some(Num::Float), Some(Num::Ptr)) => {
+            Err(errors::CastError::PtrFloat { from, to }.into())
+        }
+        (Some(Num::Bool), Some(Num::Int | Num::Ptr)) => Ok(()),
+        (Some(lhs), Some(rhs)) if lhs != Num::Bool && rhs != Num::Bool => Ok(()),
+        _ => Err(errors::CastError::NonNumeric { from, to }.into()),
+    }
+}
+
+/// Returns the types of the arguments bound by the pattern
+fn arg_types(args: &hir::Pat) -> Vec<Ty> {
+    match &args.kind {
+        hir::PatKind::Tuple(list) => list.iter().map(|pat| pat.ty.clone()).collect(),
+        _ => vec![args.ty.clone()],
+    }
+}
+
+/// Returns the types annotated to the arguments, where unannotated arguments are unknown but
+/// `self` is of the type `Self`
+fn annotations(args: &pat::Pat) -> Vec<Ty> {
+    match args {
+        pat::Pat::Tuple(pat::TuplePat(list)) => list.iter().map(annotation).collect(),
+        pat => vec![annotation(pat)],
+    }
+}
+
+/// Returns the type an argument is annotated with, where a destructured argument has the type
+/// of its struct pattern, or the tuple of the types of its elements
+fn annotation(arg: &pat::Pat) -> Ty {
+    match arg {
+        pat::Pat::Ident(pat::IdentPat { ty: Some(ty), .. }) => Ty::from(ty),
+        pat::Pat::Ident(pat::IdentPat {
+            ident: pat::IdentKind::Value(name),
+            ty: None,
+            ..
+        }) if &**name == "self" => Ty::Res(ResTy::SelfType),
+        pat::Pat::Tuple(pat::TuplePat(list)) if !list.is_empty() => {
+            Ty::Tuple(list.iter().map(annotation).collect())
+        }
+        pat::Pat::Struct(cus) => Ty::from(&cus.ty),
+        _ => Ty::Unknown,
+    }
+}
+
+/// Returns whether a part of the type could not be told
+fn unknown(ty: &Ty) -> bool {
+    match ty {
+        Ty::Unknown => true,
+        Ty::Named(_, list) | Ty::Tuple(list) => list.iter().any(unknown),
+        Ty::Shared(ty) | Ty::Array(ty, _) | Ty::Slice(ty) => unknown(ty),
+        Ty::Fun(args, ret) => args.iter().any(unknown) || unknown(ret),
+        _ => false,
+    }
+}
+
+/// Returns the template parameters, where bounds are named after interfaces
+fn templ(input: &[item::Templ]) -> Vec<hir::Templ> {
+    input
+        .iter()
+        .filter_map(|templ| match templ {
+            item::Templ::Item(templ) => Some(hir::Templ {
+                name: templ.name.clone(),
+                bounds: templ.req.iter().filter_map(type_name).collect(),
+            }),
+            item::Templ::Unused => None,
+        })
+        .collect()
+}
+
+fn type_name(input: &pat::Type) -> Option<StrRef> {
+    match &input.kind {
+        pat::TypeKind::Single(name) | pat::TypeKind::Template(name, _) => Some(name.clone()),
+        _ => None,
+    }
+}
+
+/// Binds the value to the local name, as `let name = val`
+fn let_bind(name: StrRef, val: hir::Expr) -> hir::Bind {
+    let pat = hir::Pat {
+        kind: hir::PatKind::Ident(Some(name), false),
+        ty: val.ty.clone(),
+    };
+    hir::Bind::Let(pat, val)
+}
+
+/// Binds the indexes of the place to hidden locals, unless they are names or literals, so
+/// that the place can be both read and assigned with the indexes evaluated once
+fn hoist(place: hir::Expr, bind: &mut Vec<hir::Bind>) -> hir::Expr {
+    let kind = match place.kind {
+        hir::ExprKind::Field(val, name) => hir::ExprKind::Field(Box::new(hoist(*val, bind)), name),
+        hir::ExprKind::Index(val, at) => {
+            let val = hoist(*val, bind);
+            let at = match at.kind {
+                hir::ExprKind::Name(_) | hir::ExprKind::Prim(_) => *at,
+                _ => {
+                    let local = StrRef::from(format!("#index{}", bind.len()));
+                    let ty = at.ty.clone();
+                    bind.push(let_bind(local.clone(), *at));
+                    hir::Expr {
+                        kind: hir::ExprKind::Name(local),
+                        ty,
+                    }
+                }
+            };
+            hir::ExprKind::Index(Box::new(val), Box::new(at))
+        }
+        kind => kind,
+    };
+    hir::Expr { kind, ty: place.ty }
+}
+
+/// Splits the two operands given back by [`TypeCheck::overload`]
+fn pair(args: Vec<hir::Expr>) -> (hir::Expr, hir::Expr) {
+    let [lhs, rhs]: [hir::Expr; 2] = args
+        .try_into()
+        .unwrap_or_else(|_| unreachable!("two operands should be given back"));
+    (lhs, rhs)
+}
+
+/// Returns the name methods of the type are found by, which is the name of the type
+/// implementations are written for
+fn type_key(ty: &Ty) -> Option<StrRef> {
+    match ty {
+        Ty::Named(name, _) => Some(name.clone()),
+        Ty::Res(ResTy::SelfType) => None,
+        Ty::Res(res) => Some(format!("{:?}", res).into()),
+        _ => None,
+    }
+}
+
+/// Assigns a type to every expression and pattern of a module, producing its typed
+/// [`hir::Module`]
+///
+/// Types come from annotations, literals and the structure of expressions, and types that
+/// flow into the same place must match, such as the value and the annotation of `let`, the
+/// branches of `if` and `match`, the arguments and the parameters of calls, and the elements
+/// of custom types. Types that cannot be told stay [`Ty::Unknown`] and match anything, but no
+/// function may return them, as one whose only value is a call of itself would
+///
+/// Values of custom types give each element once, except those taken from a base or from
+/// their default values, which are checked once with the type and copied into every value.
+/// Items of inline modules are used like `module.item`, while items imported from other files
+/// are given with [`Self::import`], along with the fields of their types that the module may
+/// not use, neither by name nor through a base
+///
+/// Unannotated bindings and arguments, `_` in annotations and literals get their types by
+/// unification within their function, where integer and float literals take the width of
+/// their use, defaulting to `I64` and `F32`, or to `I128` or `U128` for integers needing them.
+/// Any other type left open is an error
+///
+/// Methods are the functions of `for` blocks, found by the type of the value they are called
+/// on, or through the interfaces a template parameter must implement. Every use of a generic
+/// function gives its template parameters new types, which must implement their interfaces
+/// once inferred. Operators applied to custom types are calls of the methods named after
+/// them, like `add` for `+`, `neg` for `-x`, `eq` for `==` and `index` for `value[index]`.
+/// Built-in operators take numbers, `Bool` for `&`, `|` and `^` and `Str` for `+` and
+/// comparisons, indexes are integers or ranges, and casts convert between numeric types, all
+/// checked once the types of the function are told
+///
+/// A function returns the type of its body joined with its `return` values, and with the
+/// `Option` or `Result` that `?` returns early. As functions may be used before they are
+/// defined, the module is checked again until no function type changes, and only the errors
+/// of the last round are reported. Until then, `?` and enum patterns applied to a value of a
+/// function not checked yet leave their types unknown
+#[derive(Default)]
+pub struct TypeCheck {
+    bindings: Vec<Binding>,
+    fun: FunFrame,
+    /// Custom types by their names
+    cus: HashMap<StrRef, hir::Cus>,
+    /// Default values of the elements of custom types, by the names of the types
+    defaults: HashMap<StrRef, BTreeMap<StrRef, hir::Expr>>,
+    /// Types of the functions by the names of the enclosing items and their own
+    sigs: HashMap<Vec<StrRef>, Ty>,
+    /// Functions required by the interfaces by their names
+    interfaces: HashMap<StrRef, Vec<(StrRef, Ty)>>,
+    /// Types of the methods by their names, by the keys of their types, see [`type_key`]
+    methods: HashMap<StrRef, HashMap<StrRef, Ty>>,
+    /// Keys of types with the interfaces they implement
+    impls: HashSet<(StrRef, StrRef)>,
+    /// The type `Self` stands for within a `for` block
+    self_ty: Option<Ty>,
+    /// Values checked in other files, by the names they are imported as
+    imports: Vec<Binding>,
+    /// Fields of custom types not visible to the module, by the names of the types
+    hidden: HashMap<StrRef, HashSet<StrRef>>,
+    infer: Infer,
+    path: Vec<StrRef>,
+    changed: bool,
+    /// The first error of the round, or one met on a value whose type is not told yet,
+    /// reported only if no function type changes in the round
+    deferred: Option<Error>,
+}
+
+impl TypeCheck {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Brings the item checked in another file into the module under the name it is imported
+    /// as, where the fields of a custom type not visible to the module are given, see
+    /// [`PubCheck::hidden`](super::PubCheck::hidden)
+    ///
+    /// Implementations, interfaces and inline modules are not brought by name
+    pub fn import(&mut self, name: StrRef, item: &hir::Item, hidden: HashSet<StrRef>) {
+        let (ty, templ) = match &item.kind {
+            hir::ItemKind::Fun(fun) => (fun.ty.clone(), fun.templ.clone()),
+            hir::ItemKind::Extern(ext) => (ext.ty.clone(), Vec::new()),
+            hir::ItemKind::Val(_, ty) => (ty.clone(), Vec::new()),
+            hir::ItemKind::Cus(cus) => {
+                self.cus.insert(name.clone(), cus.clone());
+                self.hidden.insert(name, hidden);
+                return;
+            }
+            hir::ItemKind::For(..) | hir::ItemKind::Interface(_) | hir::ItemKind::Mod(_) => return,
+        };
+        self.imports.push(Binding {
+            name,
+            ty,
+            local: false,
+            templ,
+            items: None,
+        });
+    }
+
+    /// Checks that the field of a value of the type is visible to the module
+    fn visible(&self, ty: &Ty, field: &StrRef) -> Result<()> {
+        match ty {
+            Ty::Named(name, _)
+                if self
+                    .hidden
+                    .get(name)
+                    .is_some_and(|hidden| hidden.contains(field)) =>
+            {
+                Err(errors::PubError::PrivateField {
+                    field: field.to_string(),
+                    ty: name.to_string(),
+                }
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the type of the name, recording it as a capture of the closures it is bound
+    /// outside of
+    fn lookup(&mut self, name: &StrRef) -> Result<Ty> {
+        let base = self.fun.base;
+        let found = self
+            .bindings
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(i, binding)| &binding.name == name && (!binding.local || *i >= base));
+        let Some((i, binding)) = found else {
+            return Err(errors::ResolveError::Undefined(name.to_string()).into());
+        };
+        if binding.local {
+            for frame in self.fun.closures.iter_mut() {
+                if i < frame.mark && !frame.captures.iter().any(|(found, _)| found == name) {
+                    frame.captures.push((name.clone(), binding.ty.clone()));
+                }
+            }
+        }
+        let (ty, templ) = (binding.ty.clone(), binding.templ.clone());
+        Ok(self.instantiate(ty, templ))
+    }
+
+    /// Returns the type of the item of an inline module used like `module.item`, or `None` if
+    /// the value is not a path to an inline module
+    fn module_item(&mut self, input: &expr::Field) -> Option<Ty> {
+        let binding = self
+            .module_items(input.val.as_ref())?
+            .iter()
+            .find(|binding| binding.name == input.name)?;
+        let (ty, templ) = (binding.ty.clone(), binding.templ.clone());
+        Some(self.instantiate(ty, templ))
+    }
+
+    /// Returns the bindings of the items of the inline module the path names
+    fn module_items(&self, input: &expr::Expr) -> Option<&[Binding]> {
+        let binding = match input {
+            expr::Expr::Value(expr::Value::Name(name, _)) => {
+                let base = self.fun.base;
+                self.bindings
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .find(|(i, binding)| &binding.name == name && (!binding.local || *i >= base))
+                    .map(|(_, binding)| binding)?
+            }
+            expr::Expr::Field(field) => self
+                .module_items(field.val.as_ref())?
+                .iter()
+                .find(|binding| binding.name == field.name)?,
+            _ => return None,
+        };
+        binding.items.as_deref()
+    }
+
+    /// Gives new types to the template parameters of a generic function
+    fn instantiate(&mut self, ty: Ty, templ: Vec<hir::Templ>) -> Ty {
+        let mut args = HashMap::new();
+        for param in templ {
+            let origin = format!("the template parameter `{}`", param.name);
+            let arg = self.infer.fresh(VarKind::Any, origin);
+            for bound in param.bounds {
+                self.fun.bounds.push((arg.clone(), bound));
+            }
+            args.insert(param.name, arg);
+        }
+        if args.is_empty() {
+            return ty;
+        }
+        ty.subst(&|ty| match ty {
+            Ty::Named(name, list) if list.is_empty() => args.get(name).cloned(),
+            _ => None,
+        })
+    }
+
+    fn bind(&mut self, name: &StrRef, ty: Ty, local: bool) {
+        self.bindings.push(Binding {
+            name: name.clone(),
+            ty,
+            local,
+            templ: Vec::new(),
+            items: None,
+        });
+    }
+
+    /// Converts the annotation, where `Self` stands for the type of the `for` block
+    fn ty(&self, input: &pat::Type) -> Ty {
+        self.with_self(Ty::from(input))
+    }
+
+    fn with_self(&self, ty: Ty) -> Ty {
+        match &self.self_ty {
+            Some(self_ty) => ty.subst(&|ty| match ty {
+                Ty::Res(ResTy::SelfType) => Some(self_ty.clone()),
+                _ => None,
+            }),
+            None => ty,
+        }
+    }
+
+    /// Returns the interface named by the type
+    fn interface(&self, input: &pat::Type) -> Result<StrRef> {
+        match type_name(input) {
+            Some(name) if self.interfaces.contains_key(&name) => Ok(name),
+            _ => Err(errors::TypeError::NotInterface(Ty::from(input).to_string()).into()),
+        }
+    }
+
+    /// Checks the rules of the built-in operations of the function, whose types are told
+    fn rules(&mut self) -> Result<()> {
+        for rule in std::mem::take(&mut self.fun.rules) {
+            match rule {
+                Rule::Operand(op, ty) => {
+                    let ty = self.infer.settle(&ty)?;
+                    if ty.is_known() && !operand(op, &ty) {
+                        return Err(errors::TypeError::Operand {
+                            op: symbol(op).to_owned(),
+                            ty: ty.to_string(),
+                        }
+                        .into());
+                    }
+                }
+                Rule::Cast(from, to) => {
+                    let (from, to) = (self.infer.settle(&from)?, self.infer.settle(&to)?);
+                    if from.is_known() && to.is_known() {
+                        cast(&from, &to)?;
+                    }
+                }
+                Rule::Index(val, at) => {
+                    let (val, at) = (self.infer.settle(&val)?, self.infer.settle(&at)?);
+                    if val.is_known()
+                        && !matches!(val, Ty::Array(..) | Ty::Slice(_) | Ty::Res(ResTy::Str))
+                    {
+                        return Err(errors::TypeError::NotIndexable(val.to_string()).into());
+                    }
+                    if at.is_known() && Num::of(&at) != Some(Num::Int) {
+                        return Err(errors::TypeError::IndexType(at.to_string()).into());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the type given to a template parameter implements the interface
+    fn implements(&self, ty: &Ty, interface: &StrRef) -> Result<()> {
+        let found = match ty {
+            Ty::Unknown | Ty::Never => true,
+            Ty::Named(name, args) if args.is_empty() => {
+                match self.fun.templ.iter().find(|templ| &templ.name == name) {
+                    Some(templ) => templ.bounds.contains(interface),
+                    None => self.impls.contains(&(name.clone(), interface.clone())),
+                }
+            }
+            ty => type_key(ty).is_some_and(|key| self.impls.contains(&(key, interface.clone()))),
+        };
+        if found {
+            Ok(())
+        } else {
+            Err(errors::TypeError::NotImplemented {
+                ty: ty.to_string(),
+                interface: interface.to_string(),
+            }
+            .into())
+        }
+    }
+
+    /// Returns the type of the method of the type, taking the receiver first
+    fn method(&self, ty: &Ty, name: &StrRef) -> Result<Option<Ty>> {
+        // Template parameters only have the methods of their interfaces
+        if let Ty::Named(param, _) = ty {
+            if let Some(templ) = self.fun.templ.iter().find(|templ| &templ.name == param) {
+                let found = templ
+                    .bounds
+                    .iter()
+                    .filter_map(|bound| self.interfaces.get(bound))
+                    .flatten()
+                    .find(|(found, _)| found == name);
+                return match found {
+                    Some((_, found)) => Ok(Some(found.subst(&|found| match found {
+                        Ty::Res(ResTy::SelfType) => Some(ty.clone()),
+                        _ => None,
+                    }))),
+                    None => Err(errors::TypeError::NoMethod {
+                        method: name.to_string(),
+                        ty: ty.to_string(),
+                    }
+                    .into()),
+                };
+            }
+        }
+        let found = type_key(ty).and_then(|key| self.methods.get(&key)?.get(name).cloned());
+        Ok(found)
+    }
+
+    /// Checks that the functions of the `for` block are those the interface requires
+    fn check_impl(&mut self, ty: &Ty, interface: &StrRef, items: &[hir::Item]) -> Result<()> {
+        let decls = self.interfaces.get(interface).cloned().unwrap_or_default();
+        for (name, expect) in decls {
+            let found = items.iter().find_map(|item| match &item.kind {
+                hir::ItemKind::Fun(fun) if item.name == name => Some(fun.ty.clone()),
+                _ => None,
+            });
+            let Some(found) = found else {
+                return Err(errors::TypeError::MissingMethod {
+                    method: name.to_string(),
+                    interface: interface.to_string(),
+                }
+                .into());
+            };
+            let expect = expect.subst(&|expect| match expect {
+                Ty::Res(ResTy::SelfType) => Some(ty.clone()),
+                _ => None,
+            });
+            self.infer
+                .unify(expect, found)
+                .with_context(|| format!("in function `{}`", name))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the elements of the custom type named, or of its variant
+    fn elems(&self, ty: &Ty, variant: Option<&StrRef>) -> Option<hir::Elems> {
+        match ty {
+            Ty::Named(name, args) => match self.cus.get(name) {
+                Some(cus) => cus.elems(variant).cloned(),
+                None => builtin::find(name)?.elems(variant, args),
+            },
+            _ => None,
+        }
+    }
+
+    /// Keeps the error until the end of the round, as the type it is met on may be told by a
+    /// function checked later
+    fn defer(&mut self, err: impl Into<Error>) {
+        self.deferred.get_or_insert_with(|| err.into());
+    }
+
+    /// Gives new types to the template arguments left out of a built-in sum type, like those
+    /// of `Option` in `Option.None`
+    fn generic(&mut self, ty: Ty) -> Ty {
+        match ty {
+            Ty::Named(name, args) if args.is_empty() && !self.cus.contains_key(&name) => {
+                let args = builtin::find(&name).map_or_else(Vec::new, |sum| {
+                    let origin = format!("`{}`", name);
+                    let fresh = |_| self.infer.fresh(VarKind::Any, origin.clone());
+                    sum.templ.iter().map(fresh).collect()
+                });
+                Ty::Named(name, args)
+            }
+            ty => ty,
+        }
+    }
+
+    fn cus_def(input: &item::Cus) -> hir::Cus {
+        let elems = |pat: &pat::Pat| match pat {
+            pat::Pat::Tuple(pat::TuplePat(list)) => list
+                .iter()
+                .filter_map(|pat| match pat {
+                    pat::Pat::Ident(pat::IdentPat {
+                        ident: pat::IdentKind::Value(name),
+                        ty,
+                        ..
+                    }) => Some((name.clone(), ty.as_ref().map_or(Ty::Unknown, Ty::from))),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        match input {
+            item::Cus::Struct(pat, _) => hir::Cus::Struct(elems(pat)),
+            item::Cus::Union(variants) => hir::Cus::Union(
+                variants
+                    .iter()
+                    .map(|variant| (variant.name.clone(), variant.elem.as_ref().map(elems)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the type of a function found so far, or the annotations of its arguments
+    fn fun_ty(&self, name: &StrRef, args: &pat::Pat) -> Ty {
+        let mut path = self.path.clone();
+        path.push(name.clone());
+        match self.sigs.get(&path) {
+            Some(ty) => ty.clone(),
+            None => self.with_self(Ty::Fun(annotations(args), Box::new(Ty::Unknown))),
+        }
+    }
+
+    /// Returns the binding of the item if it is a value, where an inline module holds the
+    /// bindings of its items
+    fn item_binding(&mut self, item: &item::Item) -> Option<Binding> {
+        let (ty, templ, items) = match &item.kind {
+            item::ItemKind::Fun(fun) => {
+                (self.fun_ty(&item.name, &fun.args), templ(&fun.templ), None)
+            }
+            item::ItemKind::Extern(ext) => {
+                let ret = ext.ret.as_ref().map_or(Ty::Unit, Ty::from);
+                let ty = Ty::Fun(annotations(&ext.args), Box::new(ret));
+                (ty, Vec::new(), None)
+            }
+            item::ItemKind::Val(val) => (Ty::from(&val.val), Vec::new(), None),
+            item::ItemKind::Mod(module) => {
+                self.path.push(item.name.clone());
+                let items = module
+                    .items
+                    .iter()
+                    .filter_map(|item| self.item_binding(item))
+                    .collect();
+                self.path.pop();
+                (Ty::Unknown, Vec::new(), Some(items))
+            }
+            item::ItemKind::Cus(_) | item::ItemKind::For(_) | item::ItemKind::Interface(_) => {
+                return None
+            }
+        };
+        Some(Binding {
+            name: item.name.clone(),
+            ty,
+            local: false,
+            templ,
+            items,
+        })
+    }
+
+    /// Binds the names of the items as values, and their custom types with their default values
+    fn bind_items<'i>(&mut self, items: impl Iterator<Item = &'i item::Item>) -> Result<()> {
+        let mut cus_items = Vec::new();
+        for item in items {
+            if let Some(binding) = self.item_binding(item) {
+                self.bindings.push(binding);
+                continue;
+            }
+            match &item.kind {
+                item::ItemKind::Cus(cus) => {
+                    self.cus.insert(item.name.clone(), Self::cus_def(cus));
+                    cus_items.push((&item.name, cus));
+                }
+                item::ItemKind::For(items) => {
+                    let outer = self.self_ty.replace(Ty::from(&items.ty));
+                    self.path.push(item.name.clone());
+                    let methods: Vec<_> = items
+                        .items
+                        .iter()
+                        .filter_map(|item| match &item.kind {
+                            item::ItemKind::Fun(fun) => {
+                                Some((item.name.clone(), self.fun_ty(&item.name, &fun.args)))
+                            }
+                            _ => None,
+                        })
+                        .collect();
+                    self.path.pop();
+                    self.self_ty = outer;
+                    self.methods
+                        .entry(item.name.clone())
+                        .or_default()
+                        .extend(methods);
+                    if let Some(interface) = items.interface.as_ref().and_then(type_name) {
+                        self.impls.insert((item.name.clone(), interface));
+                    }
+                }
+                item::ItemKind::Interface(interface) => {
+                    let decls = interface
+                        .decls
+                        .iter()
+                        .map(|decl| {
+                            let ret = decl.ret.as_ref().map_or(Ty::Unit, Ty::from);
+                            (
+                                decl.name.clone(),
+                                Ty::Fun(annotations(&decl.args), Box::new(ret)),
+                            )
+                        })
+                        .collect();
+                    self.interfaces.insert(item.name.clone(), decls);
+                }
+                _ => {}
+            }
+        }
+        // Default values may use any of the items
+        for (name, cus) in cus_items {
+            self.cus_defaults(name, cus)
+                .with_context(|| format!("in custom type `{}`", name))?;
+        }
+        Ok(())
+    }
+
+    /// Checks the default values of the elements of the compound, each like the body of a
+    /// function without arguments. Elements without annotations take the types of their
+    /// default values
+    fn cus_defaults(&mut self, name: &StrRef, input: &item::Cus) -> Result<()> {
+        let item::Cus::Struct(pat::Pat::Tuple(pat::TuplePat(list)), elems) = input else {
+            return Ok(());
+        };
+        let mut defaults = BTreeMap::new();
+        for (pat, elem) in list.iter().zip(elems.iter()) {
+            let (
+                pat::Pat::Ident(pat::IdentPat {
+                    ident: pat::IdentKind::Value(field),
+                    ty,
+                    ..
+                }),
+                Some(default),
+            ) = (pat, &elem.default)
+            else {
+                continue;
+            };
+            let expect = ty.as_ref().map_or(Ty::Unknown, Ty::from);
+            let outer = std::mem::replace(
+                &mut self.fun,
+                FunFrame {
+                    base: self.bindings.len(),
+                    ..FunFrame::default()
+                },
+            );
+            let result = self.scoped(|this| {
+                let mut val = this.expr(default)?;
+                this.infer.unify(expect, val.ty.clone())?;
+                this.infer.settle_expr(&mut val)?;
+                this.rules()?;
+                Ok::<_, Error>(val)
+            });
+            self.fun = outer;
+            let val = result.with_context(|| format!("in the default value of `{}`", field))?;
+            if let Some(hir::Cus::Struct(elems)) = self.cus.get_mut(name) {
+                for (_, ty) in elems.iter_mut().filter(|(name, _)| name == field) {
+                    *ty = val.ty.clone();
+                }
+            }
+            defaults.insert(field.clone(), val);
+        }
+        self.defaults.insert(name.clone(), defaults);
+        Ok(())
+    }
+
+    /// Records the type of the function, noting whether it changed
+    fn set_sig(&mut self, name: &StrRef, ty: Ty) {
+        let mut path = self.path.clone();
+        path.push(name.clone());
+        if self.sigs.get(&path) != Some(&ty) {
+            self.changed = true;
+            self.sigs.insert(path, ty);
+        }
+    }
+
+    /// Returns the type of the literal, which is open to any width for numbers
+    fn prim(&mut self, input: &prim::Prim) -> Ty {
+        match input {
+            prim::Prim::Integer(integer) => {
+                let ty = self.infer.fresh(VarKind::Int, "the integer literal");
+                if integer.to::<i64>().is_none() {
+                    let res = match integer.to::<i128>() {
+                        Some(_) => ResTy::I128,
+                        None => ResTy::U128,
+                    };
+                    self.infer.widen(&ty, res);
+                }
+                ty
+            }
+            prim::Prim::Float(_) => self.infer.fresh(VarKind::Float, "the float literal"),
+            prim => Ty::from(prim),
+        }
+    }
+
+    fn module(&mut self, input: &module::Module) -> Result<hir::Module> {
+        self.scoped(|this| {
+            this.bind_items(input.items.iter())?;
+            let mut items = Vec::new();
+            for item in input.items.iter() {
+                // The other items are still checked, as their types may be the ones missed
+                match this.item(item) {
+                    Ok(item) => items.push(item),
+                    Err(err) => this.defer(err),
+                }
+            }
+            Ok(hir::Module { items })
+        })
+    }
+
+    fn item(&mut self, input: &item::Item) -> Result<hir::Item> {
+        let kind = match &input.kind {
+            item::ItemKind::Fun(fun) => {
+                let fun = self
+                    .fun(&input.name, fun)
+                    .with_context(|| format!("in function `{}`", input.name))?;
+                hir::ItemKind::Fun(fun)
+            }
+            item::ItemKind::Extern(ext) => {
+                let mut args = self.scoped(|this| this.pat(&ext.args, None))?;
+                self.infer
+                    .settle_pat(&mut args)
+                    .with_context(|| format!("in function `{}`", input.name))?;
+                let ret = ext.ret.as_ref().map_or(Ty::Unit, Ty::from);
+                hir::ItemKind::Extern(hir::Extern {
+                    abi: ext.abi.clone(),
+                    ty: Ty::Fun(arg_types(&args), Box::new(ret)),
+                })
+            }
+            // The elements may have taken the types of their default values
+            item::ItemKind::Cus(cus) => hir::ItemKind::Cus(
+                self.cus
+                    .get(&input.name)
+                    .cloned()
+                    .unwrap_or_else(|| Self::cus_def(cus)),
+            ),
+            item::ItemKind::For(items) => {
+                let ty = Ty::from(&items.ty);
+                let outer = self.self_ty.replace(ty.clone());
+                self.path.push(input.name.clone());
+                let list: Result<Vec<_>> = items.items.iter().map(|item| self.item(item)).collect();
+                self.path.pop();
+                self.self_ty = outer;
+                let list = list?;
+                let interface = match &items.interface {
+                    Some(interface) => {
+                        let interface = self.interface(interface)?;
+                        self.check_impl(&ty, &interface, &list).with_context(|| {
+                            format!("in the implementation of `{}` for `{}`", interface, ty)
+                        })?;
+                        Some(interface)
+                    }
+                    None => None,
+                };
+                hir::ItemKind::For(ty, interface, list)
+            }
+            item::ItemKind::Interface(_) => {
+                let decls = self.interfaces.get(&input.name).cloned();
+                hir::ItemKind::Interface(decls.unwrap_or_default())
+            }
+            item::ItemKind::Val(val) => hir::ItemKind::Val(val.val.clone(), Ty::from(&val.val)),
+            item::ItemKind::Mod(module) => {
+                self.path.push(input.name.clone());
+                let module = self.module(module);
+                self.path.pop();
+                hir::ItemKind::Mod(module?)
+            }
+        };
+        Ok(hir::Item {
+            public: input.public,
+            name: input.name.clone(),
+            kind,
+        })
+    }
+
+    fn fun(&mut self, name: &StrRef, input: &item::Fun) -> Result<hir::Fun> {
+        for req in input.templ.iter().filter_map(|templ| match templ {
+            item::Templ::Item(templ) => Some(&templ.req),
+            item::Templ::Unused => None,
+        }) {
+            for req in req.iter() {
+                self.interface(req)?;
+            }
+        }
+        // Functions never see the locals, loops and closures of the outer function
+        let outer = std::mem::replace(
+            &mut self.fun,
+            FunFrame {
+                base: self.bindings.len(),
+                templ: templ(&input.templ),
+                ..FunFrame::default()
+            },
+        );
+        self.path.push(name.clone());
+        let result = self.scoped(|this| {
+            let mut args = this.pat(&input.args, None)?;
+            let mut body = this.body(&input.body)?;
+            let returns = std::mem::replace(&mut this.fun.returns, Ty::Never);
+            let ret = this.infer.unify(returns, body.ty.clone())?;
+            // Every type of the function is told by now
+            this.infer.settle_pat(&mut args)?;
+            this.infer.settle_body(&mut body)?;
+            let ret = this.infer.settle(&ret)?;
+            for (ty, interface) in std::mem::take(&mut this.fun.bounds) {
+                this.implements(&this.infer.settle(&ty)?, &interface)?;
+            }
+            this.rules()?;
+            Ok::<_, Error>((args, body, ret))
+        });
+        self.path.pop();
+        let templ = std::mem::replace(&mut self.fun, outer).templ;
+        let (args, body, ret) = result?;
+        let ty = Ty::Fun(arg_types(&args), Box::new(ret.clone()));
+        self.set_sig(name, ty.clone());
+        // The function may only return values of its own, like `fun f() { f() }`
+        if unknown(&ret) {
+            let err = Error::from(errors::TypeError::Ambiguous("the return type".to_owned()));
+            self.defer(err.context(format!("in function `{}`", name)));
+        }
+        Ok(hir::Fun {
+            templ,
+            ty,
+            args,
+            body,
+            abi: input.abi.clone(),
+        })
+    }
+
+    fn body(&mut self, input: &expr::Body) -> Result<hir::Body> {
+        self.scoped(|this| {
+            this.bind_items(input.bind.iter().filter_map(|bind| match bind {
+                bind::Bind::Item(item) => Some(item),
+                bind::Bind::Let(_) => None,
+            }))?;
+            let mut never = false;
+            let mut bind = Vec::new();
+            for item in input.bind.iter() {
+                bind.push(match item {
+                    bind::Bind::Let(item) => {
+                        let val = this.expr(&item.val)?;
+                        never |= val.ty == Ty::Never;
+                        let pat = this.pat(&item.pat, Some(val.ty.clone()))?;
+                        hir::Bind::Let(pat, val)
+                    }
+                    bind::Bind::Item(item) => hir::Bind::Item(this.item(item)?),
+                });
+            }
+            let mut body = Vec::new();
+            for expr in input.body.iter() {
+                let expr = this.expr(expr)?;
+                never |= expr.ty == Ty::Never;
+                body.push(expr);
+            }
+            let ty = match body.last() {
+                _ if never => Ty::Never,
+                Some(last) if !input.unit => last.ty.clone(),
+                _ => Ty::Unit,
+            };
+            Ok(hir::Body { bind, body, ty })
+        })
+    }
+
+    /// Checks the pattern against the type of the value it matches, binding its names. Without
+    /// a value, as for arguments, unannotated names get their types from their uses
+    fn pat(&mut self, input: &pat::Pat, expect: Option<Ty>) -> Result<hir::Pat> {
+        let (kind, ty) = match input {
+            pat::Pat::Ident(ident) => {
+                let origin = match &ident.ident {
+                    pat::IdentKind::Value(name) => format!("`{}`", name),
+                    pat::IdentKind::Unused => "`_`".to_owned(),
+                };
+                let ty = match (&ident.ty, expect, &ident.ident) {
+                    (Some(ty), expect, _) => {
+                        let ty = self.infer.instantiate(self.ty(ty), &origin);
+                        self.infer.unify(ty, expect.unwrap_or(Ty::Unknown))?
+                    }
+                    (None, Some(expect), _) => expect,
+                    (None, None, pat::IdentKind::Unused) => Ty::Unknown,
+                    (None, None, pat::IdentKind::Value(name)) if &**name == "self" => {
+                        self.with_self(Ty::Res(ResTy::SelfType))
+                    }
+                    (None, None, _) => self.infer.fresh(VarKind::Any, origin),
+                };
+                let name = match &ident.ident {
+                    pat::IdentKind::Value(name) => {
+                        self.bind(name, ty.clone(), true);
+                        Some(name.clone())
+                    }
+                    pat::IdentKind::Unused => None,
+                };
+                (hir::PatKind::Ident(name, ident.mutable), ty)
+            }
+            pat::Pat::Lit(lit) => {
+                let lit = match lit {
+                    pat::LitPat::Prim(prim) => prim.clone(),
+                    pat::LitPat::Bool(value) => prim::Prim::Bool(*value),
+                };
+                let found = self.prim(&lit);
+                let ty = self.infer.unify(expect.unwrap_or(Ty::Unknown), found)?;
+                (hir::PatKind::Lit(lit), ty)
+            }
+            pat::Pat::Tuple(pat::TuplePat(list)) => {
+                // Elements of a value not known to be a tuple are told by the elements
+                let expect = expect.map(|ty| self.infer.resolve(&ty));
+                let elem = match &expect {
+                    Some(Ty::Tuple(elem)) if elem.len() == list.len() => {
+                        elem.iter().cloned().map(Some).collect()
+                    }
+                    Some(Ty::Unknown) => vec![Some(Ty::Unknown); list.len()],
+                    _ => vec![None; list.len()],
+                };
+                let mut pats = Vec::new();
+                for (pat, expect) in list.iter().zip(elem) {
+                    pats.push(self.pat(pat, expect)?);
+                }
+                let found = Ty::Tuple(pats.iter().map(|pat| pat.ty.clone()).collect());
+                let ty = self.infer.unify(expect.unwrap_or(Ty::Unknown), found)?;
+                (hir::PatKind::Tuple(pats), ty)
+            }
+            pat::Pat::Any(pat::AnyPat(list)) => {
+                // Every alternative binds the same names with the same types, thus only the
+                // first binds them
+                let first = self.bindings.len();
+                let mut pats = Vec::new();
+                let mut ty = expect;
+                for (i, pat) in list.iter().enumerate() {
+                    let mark = self.bindings.len();
+                    let pat = self.pat(pat, ty.clone())?;
+                    if i != 0 {
+                        let found = self.bindings.split_off(mark);
+                        self.alternative(first, found)?;
+                    }
+                    let found = pat.ty.clone();
+                    ty = Some(match ty {
+                        Some(ty) => self.infer.unify(ty, found)?,
+                        None => found,
+                    });
+                    pats.push(pat);
+                }
+                (hir::PatKind::Any(pats), ty.unwrap_or(Ty::Unknown))
+            }
+            pat::Pat::Struct(cus) => {
+                let found = match (
+                    expect.as_ref().map(|ty| self.infer.resolve(ty)),
+                    self.ty(&cus.ty),
+                ) {
+                    // The template arguments of a value not told yet stay unknown
+                    (Some(Ty::Unknown), Ty::Named(name, args))
+                        if args.is_empty() && !self.cus.contains_key(&name) =>
+                    {
+                        match builtin::find(&name) {
+                            Some(sum) => {
+                                self.defer(errors::TypeError::Ambiguous(format!("`{}`", name)));
+                                Ty::Named(name, vec![Ty::Unknown; sum.templ.len()])
+                            }
+                            None => Ty::Named(name, args),
+                        }
+                    }
+                    (_, ty) => self.generic(ty),
+                };
+                let ty = self.infer.unify(expect.unwrap_or(Ty::Unknown), found)?;
+                let elems = self.elems(&ty, cus.variant.as_ref());
+                let mut pats = BTreeMap::new();
+                for (name, pat) in cus.elem.iter() {
+                    let expect = match &elems {
+                        Some(elems) => elems
+                            .iter()
+                            .find(|(found, _)| found == name)
+                            .map(|(_, ty)| ty.clone())
+                            .ok_or_else(|| errors::TypeError::NoField {
+                                field: name.to_string(),
+                                ty: ty.to_string(),
+                            })?,
+                        None => Ty::Unknown,
+                    };
+                    self.visible(&ty, name)?;
+                    pats.insert(name.clone(), self.pat(pat, Some(expect))?);
+                }
+                (hir::PatKind::Struct(cus.variant.clone(), pats), ty)
+            }
+        };
+        Ok(hir::Pat { kind, ty })
+    }
+
+    /// Checks that the bindings of an alternative of an or-pattern are those bound by the
+    /// first alternative from the index given
+    fn alternative(&mut self, first: usize, found: Vec<Binding>) -> Result<()> {
+        let expect = &self.bindings[first..];
+        let missing = expect
+            .iter()
+            .find(|expect| !found.iter().any(|found| found.name == expect.name))
+            .or_else(|| {
+                found
+                    .iter()
+                    .find(|found| !expect.iter().any(|expect| expect.name == found.name))
+            });
+        if let Some(binding) = missing {
+            return Err(errors::ResolveError::OrPattern(binding.name.to_string()).into());
+        }
+        let pairs: Vec<_> = found
+            .into_iter()
+            .filter_map(|found| {
+                let expect = expect
+                    .iter()
+                    .rev()
+                    .find(|expect| expect.name == found.name)?;
+                Some((found.name, expect.ty.clone(), found.ty))
+            })
+            .collect();
+        for (name, expect, found) in pairs {
+            self.infer
+                .unify(expect, found)
+                .with_context(|| format!("in `{}` of the alternatives of the pattern", name))?;
+        }
+        Ok(())
+    }
+
+    fn exprs<'e>(&mut self, input: impl Iterator<Item = &'e expr::Expr>) -> Result<Vec<hir::Expr>> {
+        input.map(|expr| self.expr(expr)).collect()
+    }
+
+    fn expr(&mut self, input: &expr::Expr) -> Result<hir::Expr> {
+        let never = |list: &[&hir::Expr]| list.iter().any(|expr| expr.ty == Ty::Never);
+        let (kind, ty) = match input {
+            expr::Expr::Prim(prim) => (hir::ExprKind::Prim(prim.clone()), self.prim(prim)),
+            expr::Expr::Value(expr::Value::Name(name, _)) => {
+                (hir::ExprKind::Name(name.clone()), self.lookup(name)?)
+            }
+            expr::Expr::Value(expr::Value::Res(res)) => {
+                let ty = match res {
+                    ResVal::True | ResVal::False => Ty::Res(ResTy::Bool),
+                    ResVal::SelfValue => self.lookup(&StrRef::from("self"))?,
+                };
+                (hir::ExprKind::Res(*res), ty)
+            }
+            expr::Expr::Value(expr::Value::Unused) => (hir::ExprKind::Unused, Ty::Unknown),
+            expr::Expr::UnExpr(expr) => {
+                let val = self.expr(expr.val.as_ref())?;
+                let val = match expr.op {
+                    UnOp::Ref => val,
+                    UnOp::Neg => match self.overload("-", "neg", vec![val])? {
+                        Ok(call) => return Ok(call),
+                        Err(mut args) => args.remove(0),
+                    },
+                };
+                let ty = match (expr.op, &val.ty) {
+                    (_, Ty::Never) => Ty::Never,
+                    (UnOp::Ref, ty) => Ty::Shared(Box::new(ty.clone())),
+                    (UnOp::Neg, ty) => {
+                        self.fun.rules.push(Rule::Operand(BinOp::Sub, ty.clone()));
+                        ty.clone()
+                    }
+                };
+                (hir::ExprKind::UnExpr(expr.op, Box::new(val)), ty)
+            }
+            expr::Expr::BinExpr(expr::BinExpr {
+                op: BinOp::Range | BinOp::RangeEq,
+                ..
+            }) => return Err(errors::TypeError::RangeValue.into()),
+            expr::Expr::BinExpr(expr) => {
+                let lhs = self.expr(expr.lhs.as_ref())?;
+                let rhs = self.expr(expr.rhs.as_ref())?;
+                let (lhs, rhs) = match self.bin_overload(expr.op, lhs, rhs)? {
+                    Ok(call) => return Ok(call),
+                    Err(operands) => operands,
+                };
+                let ty = self.infer.unify(lhs.ty.clone(), rhs.ty.clone())?;
+                self.fun.rules.push(Rule::Operand(expr.op, ty.clone()));
+                let ty = match expr.op {
+                    _ if never(&[&lhs, &rhs]) => Ty::Never,
+                    BinOp::Eq | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge => {
+                        Ty::Res(ResTy::Bool)
+                    }
+                    _ => ty,
+                };
+                let kind = hir::ExprKind::BinExpr(expr.op, Box::new(lhs), Box::new(rhs));
+                (kind, ty)
+            }
+            expr::Expr::Body(body) => {
+                let body = self.body(body)?;
+                let ty = body.ty.clone();
+                (hir::ExprKind::Body(body), ty)
+            }
+            expr::Expr::Flow(flow) => return self.flow(flow),
+            expr::Expr::Tuple(tuple) => {
+                let elem = self.exprs(tuple.elem.iter())?;
+                let ty = if elem.iter().any(|expr| expr.ty == Ty::Never) {
+                    Ty::Never
+                } else {
+                    Ty::Tuple(elem.iter().map(|expr| expr.ty.clone()).collect())
+                };
+                (hir::ExprKind::Tuple(elem), ty)
+            }
+            expr::Expr::Struct(cus) => return self.struct_expr(cus),
+            expr::Expr::Field(field) => {
+                let val = self.expr(field.val.as_ref())?;
+                let ty = match self.module_item(field) {
+                    Some(ty) => ty,
+                    None => self.field(&self.infer.resolve(&val.ty), &field.name)?,
+                };
+                (hir::ExprKind::Field(Box::new(val), field.name.clone()), ty)
+            }
+            expr::Expr::Index(index) => {
+                let val = self.expr(index.val.as_ref())?;
+                let (at, range) = match index.index.as_ref() {
+                    expr::Expr::BinExpr(
+                        range @ expr::BinExpr {
+                            op: BinOp::Range | BinOp::RangeEq,
+                            ..
+                        },
+                    ) => (self.range(range)?, true),
+                    at => (self.expr(at)?, false),
+                };
+                let (val, at) = match self.overload("[]", "index", vec![val, at])? {
+                    Ok(call) => return Ok(call),
+                    Err(args) => pair(args),
+                };
+                self.fun
+                    .rules
+                    .push(Rule::Index(val.ty.clone(), at.ty.clone()));
+                let ty = match self.infer.resolve(&val.ty) {
+                    _ if never(&[&val, &at]) => Ty::Never,
+                    // Indexing with a range takes a slice
+                    Ty::Array(item, _) | Ty::Slice(item) if range => Ty::Slice(item),
+                    Ty::Array(item, _) | Ty::Slice(item) => *item,
+                    // Strings are indexed by bytes, and sliced into strings
+                    Ty::Res(ResTy::Str) if range => Ty::Res(ResTy::Str),
+                    Ty::Res(ResTy::Str) => Ty::Res(ResTy::I8),
+                    _ => Ty::Unknown,
+                };
+                (hir::ExprKind::Index(Box::new(val), Box::new(at)), ty)
+            }
+            expr::Expr::Array(expr::Array::List(list)) => {
+                let elem = self.exprs(list.iter())?;
+                let mut item = self.infer.fresh(VarKind::Any, "the array element");
+                for expr in elem.iter() {
+                    item = self.infer.unify(item, expr.ty.clone())?;
+                }
+                let ty = if elem.iter().any(|expr| expr.ty == Ty::Never) {
+                    Ty::Never
+                } else {
+                    Ty::Array(Box::new(item), elem.len() as u64)
+                };
+                (hir::ExprKind::Array(elem), ty)
+            }
+            expr::Expr::Array(expr::Array::Repeat(val, len)) => {
+                let val = self.expr(val.as_ref())?;
+                let ty = match &val.ty {
+                    Ty::Never => Ty::Never,
+                    item => Ty::Array(Box::new(item.clone()), *len),
+                };
+                (hir::ExprKind::Repeat(Box::new(val), *len), ty)
+            }
+            expr::Expr::Cast(cast) => {
+                let val = self.expr(cast.val.as_ref())?;
+                let ty = match &val.ty {
+                    Ty::Never => Ty::Never,
+                    from => {
+                        let ty = self.infer.instantiate(self.ty(&cast.ty), "the cast");
+                        self.fun.rules.push(Rule::Cast(from.clone(), ty.clone()));
+                        ty
+                    }
+                };
+                (hir::ExprKind::Cast(Box::new(val)), ty)
+            }
+            expr::Expr::Try(expr) => {
+                let val = self.expr(expr.val.as_ref())?;
+                return self.try_expr(val);
+            }
+            expr::Expr::Assign(assign) => {
+                let place = self.expr(assign.place.as_ref())?;
+                let val = self.expr(assign.val.as_ref())?;
+                // An overloaded operator assigns its result, like `place = place + val`, where
+                // the indexes of the place are bound first to be evaluated once
+                let mut bind = Vec::new();
+                let (op, place, val) = match assign.op {
+                    Some(op) if matches!(self.infer.resolve(&place.ty), Ty::Named(..)) => {
+                        let place = hoist(place, &mut bind);
+                        match self.bin_overload(op, place.clone(), val)? {
+                            Ok(call) => (None, place, call),
+                            Err((place, val)) => (Some(op), place, val),
+                        }
+                    }
+                    op => (op, place, val),
+                };
+                let ty = self.infer.unify(place.ty.clone(), val.ty.clone())?;
+                if let Some(op) = op {
+                    self.fun.rules.push(Rule::Operand(op, ty));
+                }
+                let ty = if never(&[&place, &val]) {
+                    Ty::Never
+                } else {
+                    Ty::Unit
+                };
+                let kind = hir::ExprKind::Assign(op, Box::new(place), Box::new(val));
+                if bind.is_empty() {
+                    (kind, ty)
+                } else {
+                    let body = vec![hir::Expr {
+                        kind,
+                        ty: ty.clone(),
+                    }];
+                    (
+                        hir::ExprKind::Body(hir::Body {
+                            bind,
+                            body,
+                            ty: ty.clone(),
+                        }),
+                        ty,
+                    )
+                }
+            }
+            expr::Expr::Closure(closure) => {
+                let (closure, ty) = self.closure(closure).context("in closure")?;
+                (hir::ExprKind::Closure(closure), ty)
+            }
+            expr::Expr::Call(call) => {
+                let (fun, args) = self.callee(call)?;
+                return self.call(fun, args);
+            }
+        };
+        Ok(hir::Expr { kind, ty })
+    }
+
+    /// Checks the bounds of a range, which has no type of its own as it only takes part in
+    /// indexes and `for` loops
+    fn range(&mut self, input: &expr::BinExpr) -> Result<hir::Expr> {
+        let lhs = self.expr(input.lhs.as_ref())?;
+        let rhs = self.expr(input.rhs.as_ref())?;
+        let ty = self.infer.unify(lhs.ty.clone(), rhs.ty.clone())?;
+        self.fun.rules.push(Rule::Operand(input.op, ty));
+        let ty = if lhs.ty == Ty::Never || rhs.ty == Ty::Never {
+            Ty::Never
+        } else {
+            Ty::Unknown
+        };
+        let kind = hir::ExprKind::BinExpr(input.op, Box::new(lhs), Box::new(rhs));
+        Ok(hir::Expr { kind, ty })
+    }
+
+    /// Checks the arguments of the call against the parameters of the function
+    fn call(&mut self, fun: hir::Expr, args: Vec<hir::Expr>) -> Result<hir::Expr> {
+        // A function not told yet takes the types of its first call
+        let callee = match self.infer.resolve(&fun.ty) {
+            Ty::Var(_, VarKind::Any) => {
+                let params = args.iter().map(|arg| arg.ty.clone()).collect();
+                let ret = self.infer.fresh(VarKind::Any, "the result of the call");
+                self.infer
+                    .unify(fun.ty.clone(), Ty::Fun(params, Box::new(ret)))?
+            }
+            ty => ty,
+        };
+        let ty = match &callee {
+            _ if fun.ty == Ty::Never || args.iter().any(|arg| arg.ty == Ty::Never) => Ty::Never,
+            Ty::Fun(params, ret) => {
+                if params.len() != args.len() {
+                    return Err(errors::TypeError::ArgCount {
+                        expect: params.len(),
+                        found: args.len(),
+                    }
+                    .into());
+                }
+                for (param, arg) in params.iter().zip(args.iter()) {
+                    self.infer.unify(param.clone(), arg.ty.clone())?;
+                }
+                *ret.clone()
+            }
+            Ty::Unknown => Ty::Unknown,
+            ty => return Err(errors::TypeError::NotCallable(ty.to_string()).into()),
+        };
+        let kind = hir::ExprKind::Call(Box::new(fun), args);
+        Ok(hir::Expr { kind, ty })
+    }
+
+    /// Lowers the operator applied to a value of a custom type to a call of the method named,
+    /// taking the operands as its arguments. Returns the operands back for other types
+    fn overload(
+        &mut self,
+        op: &str,
+        name: &str,
+        args: Vec<hir::Expr>,
+    ) -> Result<std::result::Result<hir::Expr, Vec<hir::Expr>>> {
+        let ty = self.infer.resolve(&args[0].ty);
+        if !matches!(ty, Ty::Named(..)) {
+            return Ok(Err(args));
+        }
+        let name = StrRef::from(name);
+        let Some(method) = self.method(&ty, &name)? else {
+            return Err(errors::TypeError::NoOperator {
+                op: op.to_owned(),
+                ty: ty.to_string(),
+                method: name.to_string(),
+            }
+            .into());
+        };
+        let fun = hir::Expr {
+            kind: hir::ExprKind::Method(ty, name),
+            ty: method,
+        };
+        Ok(Ok(self.call(fun, args)?))
+    }
+
+    /// Lowers the binary operator like [`Self::overload`], where comparisons must give `Bool`
+    fn bin_overload(
+        &mut self,
+        op: BinOp,
+        lhs: hir::Expr,
+        rhs: hir::Expr,
+    ) -> Result<std::result::Result<hir::Expr, (hir::Expr, hir::Expr)>> {
+        let name = match op {
+            BinOp::Add => "add",
+            BinOp::Sub => "sub",
+            BinOp::Mul => "mul",
+            BinOp::Div => "div",
+            BinOp::Mod => "rem",
+            BinOp::Eq => "eq",
+            BinOp::Lt => "lt",
+            BinOp::Le => "le",
+            BinOp::Gt => "gt",
+            BinOp::Ge => "ge",
+            _ => return Ok(Err((lhs, rhs))),
+        };
+        match self.overload(symbol(op), name, vec![lhs, rhs])? {
+            Ok(call) => {
+                if matches!(
+                    op,
+                    BinOp::Eq | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge
+                ) {
+                    self.infer.unify(Ty::Res(ResTy::Bool), call.ty.clone())?;
+                }
+                Ok(Ok(call))
+            }
+            Err(args) => Ok(Err(pair(args))),
+        }
+    }
+
+    /// Checks the function called and its arguments, where a method called like
+    /// `value.name(...)` takes the value as its first argument
+    fn callee(&mut self, input: &expr::Call) -> Result<(hir::Expr, Vec<hir::Expr>)> {
+        let expr::Expr::Field(field) = input.fun.as_ref() else {
+            let fun = self.expr(input.fun.as_ref())?;
+            return Ok((fun, self.exprs(input.args.iter())?));
+        };
+        let val = self.expr(field.val.as_ref())?;
+        if let Some(fun_ty) = self.module_item(field) {
+            let fun = hir::Expr {
+                kind: hir::ExprKind::Field(Box::new(val), field.name.clone()),
+                ty: fun_ty,
+            };
+            return Ok((fun, self.exprs(input.args.iter())?));
+        }
+        let ty = self.infer.resolve(&val.ty);
+        // Elements are found before methods
+        let elem = self
+            .elems(&ty, None)
+            .is_some_and(|elems| elems.iter().any(|(found, _)| found == &field.name));
+        let method = if elem {
+            None
+        } else {
+            self.method(&ty, &field.name)?
+        };
+        let Some(method) = method else {
+            let fun_ty = self.field(&ty, &field.name)?;
+            let fun = hir::Expr {
+                kind: hir::ExprKind::Field(Box::new(val), field.name.clone()),
+                ty: fun_ty,
+            };
+            return Ok((fun, self.exprs(input.args.iter())?));
+        };
+        let fun = hir::Expr {
+            kind: hir::ExprKind::Method(ty, field.name.clone()),
+            ty: method,
+        };
+        let mut args = vec![val];
+        args.extend(self.exprs(input.args.iter())?);
+        Ok((fun, args))
+    }
+
+    /// Returns the type of the field of a value of the type, looking through shared references
+    fn field(&self, ty: &Ty, name: &StrRef) -> Result<Ty> {
+        match ty {
+            Ty::Never => Ok(Ty::Never),
+            Ty::Shared(ty) => self.field(ty, name),
+            // The number of elements, or of bytes of a string
+            Ty::Array(..) | Ty::Slice(_) | Ty::Res(ResTy::Str) if &**name == "len" => {
+                Ok(Ty::Res(ResTy::I64))
+            }
+            Ty::Named(..) => match self.elems(ty, None) {
+                Some(elems) => {
+                    let found = elems
+                        .iter()
+                        .find(|(found, _)| found == name)
+                        .map(|(_, ty)| ty.clone())
+                        .ok_or_else(|| errors::TypeError::NoField {
+                            field: name.to_string(),
+                            ty: ty.to_string(),
+                        })?;
+                    self.visible(ty, name)?;
+                    Ok(found)
+                }
+                None => Ok(Ty::Unknown),
+            },
+            ty if ty.is_known() => Err(errors::TypeError::NoField {
+                field: name.to_string(),
+                ty: ty.to_string(),
+            }
+            .into()),
+            _ => Ok(Ty::Unknown),
+        }
+    }
+
+    /// Checks the elements given against those of the custom type, filling in the elements
+    /// left out from the base or from their default values
+    ///
+    /// With a base, the elements given are bound first, so that they are evaluated before the
+    /// base like they are written
+    fn struct_expr(&mut self, input: &expr::Struct) -> Result<hir::Expr> {
+        let ty = self.generic(self.ty(&input.ty));
+        let display = match &input.variant {
+            Some(variant) => format!("{}.{}", ty, variant),
+            None => ty.to_string(),
+        };
+        // The elements of types not found are left to the resolution check
+        let known = matches!(&ty, Ty::Named(name, _)
+            if self.cus.contains_key(name) || builtin::find(name).is_some());
+        let elems = self.elems(&ty, input.variant.as_ref());
+        if input.base.is_some() && input.variant.is_some() {
+            return Err(errors::TypeError::VariantBase(display).into());
+        }
+
+        let mut bind = Vec::new();
+        let mut elem = BTreeMap::new();
+        let mut never = false;
+        for (name, expr) in input.elem.iter() {
+            let val = self.expr(expr)?;
+            let expect = match elems.iter().flatten().find(|(found, _)| found == name) {
+                Some((_, ty)) => ty.clone(),
+                None if known => {
+                    return Err(errors::TypeError::NoField {
+                        field: name.to_string(),
+                        ty: display,
+                    }
+                    .into())
+                }
+                None => Ty::Unknown,
+            };
+            self.visible(&ty, name)?;
+            self.infer
+                .unify(expect, val.ty.clone())
+                .with_context(|| format!("in field `{}`", name))?;
+            never |= val.ty == Ty::Never;
+            let val = match input.base {
+                Some(_) => {
+                    let local = StrRef::from(format!("#{}", name));
+                    let ty = val.ty.clone();
+                    bind.push(let_bind(local.clone(), val));
+                    hir::Expr {
+                        kind: hir::ExprKind::Name(local),
+                        ty,
+                    }
+                }
+                None => val,
+            };
+            elem.insert(name.clone(), val);
+        }
+
+        let base = match &input.base {
+            Some(base) => {
+                let base = self.expr(base)?;
+                self.infer
+                    .unify(ty.clone(), base.ty.clone())
+                    .context("in the base")?;
+                never |= base.ty == Ty::Never;
+                let local = StrRef::from("#base");
+                bind.push(let_bind(local.clone(), base));
+                Some(local)
+            }
+            None => None,
+        };
+        let defaults = match (&ty, &input.variant) {
+            (Ty::Named(name, _), None) => self.defaults.get(name),
+            _ => None,
+        };
+        for (name, elem_ty) in elems.iter().flatten() {
+            if elem.contains_key(name) {
+                continue;
+            }
+            // The base gives its elements as if they were written
+            if base.is_some() {
+                self.visible(&ty, name)?;
+            }
+            let val = match (&base, defaults.and_then(|defaults| defaults.get(name))) {
+                (Some(base), _) => hir::Expr {
+                    kind: hir::ExprKind::Field(
+                        Box::new(hir::Expr {
+                            kind: hir::ExprKind::Name(base.clone()),
+                            ty: ty.clone(),
+                        }),
+                        name.clone(),
+                    ),
+                    ty: elem_ty.clone(),
+                },
+                (None, Some(default)) => default.clone(),
+                (None, None) => {
+                    return Err(errors::TypeError::MissingField {
+                        field: name.to_string(),
+                        ty: display,
+                    }
+                    .into())
+                }
+            };
+            elem.insert(name.clone(), val);
+        }
+
+        let ty = if never { Ty::Never } else { ty };
+        let val = hir::Expr {
+            kind: hir::ExprKind::Struct(input.variant.clone(), elem),
+            ty: ty.clone(),
+        };
+        if bind.is_empty() {
+            return Ok(val);
+        }
+        Ok(hir::Expr {
+            kind: hir::ExprKind::Body(hir::Body {
+                bind,
+                body: vec![val],
+                ty: ty.clone(),
+            }),
+            ty,
+        })
+    }
+
+    /// Lowers `val?` to a match taking the value held, or returning early with `Option.None`
+    /// or with the `Result.Err` holding the error, which the function must return
+    fn try_expr(&mut self, val: hir::Expr) -> Result<hir::Expr> {
+        let ty = self.infer.resolve(&val.ty);
+        let (sum, args) = match &ty {
+            Ty::Never => return Ok(val),
+            Ty::Unknown => {
+                self.defer(errors::TypeError::Ambiguous("the value of `?`".to_owned()));
+                return Ok(val);
+            }
+            Ty::Named(name, args) if !self.cus.contains_key(name) => match builtin::find(name) {
+                Some(sum) => (sum, args.clone()),
+                None => return Err(errors::TypeError::NotTry(ty.to_string()).into()),
+            },
+            Ty::Var(_, VarKind::Any) => {
+                return Err(errors::TypeError::Ambiguous("the value of `?`".to_owned()).into())
+            }
+            _ => return Err(errors::TypeError::NotTry(ty.to_string()).into()),
+        };
+        let [(held, Some([(field, _)])), (other, rest)] = sum.variants else {
+            unreachable!("built-in sum types hold a value in their first variant");
+        };
+        // The value returned keeps the error, while the value held may differ
+        let mut ret = args.clone();
+        ret[0] = self.infer.fresh(VarKind::Any, "the value of `?`");
+        let ret = Ty::Named(sum.name.into(), ret);
+        let returns = self.infer.resolve(&self.fun.returns);
+        self.fun.returns = self
+            .infer
+            .unify(self.fun.returns.clone(), ret.clone())
+            .map_err(|_| errors::TypeError::TryReturn {
+                expect: returns.to_string(),
+                found: ret.to_string(),
+            })?;
+
+        let name = StrRef::from("#try");
+        let bind = |ty: &Ty, field: &str| {
+            let pat = hir::Pat {
+                kind: hir::PatKind::Ident(Some(name.clone()), false),
+                ty: ty.clone(),
+            };
+            BTreeMap::from([(StrRef::from(field), pat)])
+        };
+        let value = args[0].clone();
+        let rest = rest.map(|rest| (rest[0].0, args[1].clone()));
+        let arms = vec![
+            hir::Arm {
+                pat: hir::Pat {
+                    kind: hir::PatKind::Struct(Some((*held).into()), bind(&value, field)),
+                    ty: ty.clone(),
+                },
+                guard: None,
+                val: hir::Expr {
+                    kind: hir::ExprKind::Name(name.clone()),
+                    ty: value.clone(),
+                },
+            },
+            hir::Arm {
+                pat: hir::Pat {
+                    kind: hir::PatKind::Struct(
+                        Some((*other).into()),
+                        rest.as_ref()
+                            .map_or_else(BTreeMap::new, |(field, ty)| bind(ty, field)),
+                    ),
+                    ty: ty.clone(),
+                },
+                guard: None,
+                val: hir::Expr {
+                    kind: hir::ExprKind::Return(Some(Box::new(hir::Expr {
+                        kind: hir::ExprKind::Struct(
+                            Some((*other).into()),
+                            rest.map_or_else(BTreeMap::new, |(field, ty)| {
+                                let val = hir::Expr {
+                                    kind: hir::ExprKind::Name(name.clone()),
+                                    ty,
+                                };
+                                BTreeMap::from([(StrRef::from(field), val)])
+                            }),
+                        ),
+                        ty: ret,
+                    }))),
+                    ty: Ty::Never,
+                },
+            },
+        ];
+        Ok(hir::Expr {
+            kind: hir::ExprKind::Match(Box::new(val), arms),
+            ty: value,
+        })
+    }
+
+    fn closure(&mut self, input: &expr::Closure) -> Result<(hir::Closure, Ty)> {
+        // A closure returns on its own, where no loops of the outer function are visible
+        let loops = std::mem::take(&mut self.fun.loops);
+        let returns = std::mem::replace(&mut self.fun.returns, Ty::Never);
+        self.fun.closures.push(ClosureFrame {
+            mark: self.bindings.len(),
+            captures: Vec::new(),
+        });
+        let result = self.scoped(|this| {
+            let args = this.pat(&input.args, None)?;
+            let body = this.expr(input.body.as_ref())?;
+            let ret = this
+                .infer
+                .unify(this.fun.returns.clone(), body.ty.clone())?;
+            Ok::<_, Error>((args, body, ret))
+        });
+        let frame = self
+            .fun
+            .closures
+            .pop()
+            .expect("closure frame should be pushed");
+        self.fun.loops = loops;
+        self.fun.returns = returns;
+        let (args, body, ret) = result?;
+        let ty = Ty::Fun(arg_types(&args), Box::new(ret));
+        let closure = hir::Closure {
+            args,
+            body: Box::new(body),
+            captures: frame.captures,
+        };
+        Ok((closure, ty))
+    }
+
+    fn flow(&mut self, input: &flow::Flow) -> Result<hir::Expr> {
+        let (kind, ty) = match input {
+            flow::Flow::If(flow) => {
+                let cond = self.expr(flow.cond.as_ref())?;
+                self.infer
+                    .unify(Ty::Res(ResTy::Bool), cond.ty.clone())
+                    .context("in the condition")?;
+                let body = self.body(&flow.body)?;
+                let other = match &flow.other {
+                    Some(other) => Some(Box::new(self.expr(other.as_ref())?)),
+                    None => None,
+                };
+                let ty = match &other {
+                    _ if cond.ty == Ty::Never => Ty::Never,
+                    Some(other) => self.infer.unify(body.ty.clone(), other.ty.clone())?,
+                    None => Ty::Unit,
+                };
+                (hir::ExprKind::If(Box::new(cond), body, other), ty)
+            }
+            flow::Flow::Loop(flow) => {
+                self.fun.loops.push(LoopFrame {
+                    label: flow.label.clone(),
+                    ty: Ty::Never,
+                });
+                let body = self.body(&flow.body);
+                let frame = self.fun.loops.pop().expect("loop frame should be pushed");
+                (hir::ExprKind::Loop(flow.label.clone(), body?), frame.ty)
+            }
+            flow::Flow::Break(flow) => {
+                let val = match &flow.val {
+                    Some(val) => Some(Box::new(self.expr(val.as_ref())?)),
+                    None => None,
+                };
+                let ty = val.as_ref().map_or(Ty::Unit, |val| val.ty.clone());
+                // Breaks outside of loops are left to the flow check
+                let frame = match &flow.label {
+                    Some(label) => self
+                        .fun
+                        .loops
+                        .iter_mut()
+                        .rev()
+                        .find(|frame| frame.label.as_ref() == Some(label)),
+                    None => self.fun.loops.last_mut(),
+                };
+                if let Some(frame) = frame {
+                    frame.ty = self.infer.unify(frame.ty.clone(), ty)?;
+                }
+                (hir::ExprKind::Break(flow.label.clone(), val), Ty::Never)
+            }
+            flow::Flow::Continue(flow) => (hir::ExprKind::Continue(flow.label.clone()), Ty::Never),
+            flow::Flow::Return(flow) => {
+                let val = match &flow.val {
+                    Some(val) => Some(Box::new(self.expr(val.as_ref())?)),
+                    None => None,
+                };
+                let ty = val.as_ref().map_or(Ty::Unit, |val| val.ty.clone());
+                self.fun.returns = self.infer.unify(self.fun.returns.clone(), ty)?;
+                (hir::ExprKind::Return(val), Ty::Never)
+            }
+            flow::Flow::Match(flow) => {
+                let val = self.expr(flow.val.as_ref())?;
+                let mut ty = Ty::Never;
+                let mut arms = Vec::new();
+                for arm in flow.arms.iter() {
+                    let arm = self.scoped(|this| {
+                        let pat = this.pat(&arm.pat, Some(val.ty.clone()))?;
+                        let guard = match &arm.guard {
+                            Some(guard) => {
+                                let guard = this.expr(guard)?;
+                                this.infer
+                                    .unify(Ty::Res(ResTy::Bool), guard.ty.clone())
+                                    .context("in the match guard")?;
+                                Some(guard)
+                            }
+                            None => None,
+                        };
+                        let val = this.expr(&arm.val)?;
+                        Ok::<_, Error>(hir::Arm { pat, guard, val })
+                    })?;
+                    ty = self.infer.unify(ty, arm.val.ty.clone())?;
+                    arms.push(arm);
+                }
+                let ty = if val.ty == Ty::Never { Ty::Never } else { ty };
+                (hir::ExprKind::Match(Box::new(val), arms), ty)
+            }
+        };
+        Ok(hir::Expr { kind, ty })
+    }
+}
+
+impl Scoped for TypeCheck {
+    type Binding = Binding;
+
+    fn bindings(&mut self) -> &mut Vec<Binding> {
+        &mut self.bindings
+    }
+}
+
+impl Check<module::Module> for TypeCheck {
+    type Output = hir::Module;
+
+    fn check(&mut self, input: &module::Module) -> Result<hir::Module> {
+        // Every round settles at least one more function type, unless none changes
+        let mut rounds = 0;
+        loop {
+            self.changed = false;
+            self.deferred = None;
+            self.infer.clear();
+            self.bindings.clone_from(&self.imports);
+            let module = self.module(input);
+            rounds += 1;
+            // Errors may come from function types told later in the round
+            if !self.changed || rounds > self.sigs.len() {
+                return match self.deferred.take() {
+                    Some(err) => module.and(Err(err)),
+                    None => module,
+                };
+            }
+        }
+    }
+}
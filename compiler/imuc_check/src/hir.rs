@@ -0,0 +1,173 @@
+//! The typed intermediate representation produced by [`TypeCheck`](crate::checks::TypeCheck)
+//!
+//! It mirrors the syntax tree, where every expression and pattern carries its [`Ty`], flows are
+//! flattened into expressions, and closures list the locals they capture. Code generation
+//! consumes it instead of the syntax tree
+
+use crate::ty::Ty;
+use imuc_ast::{module::Public, prim::Prim, StrRef};
+use imuc_lexer::token::{BinOp, ResVal, UnOp};
+use std::collections::BTreeMap;
+
+#[derive(Clone)]
+pub struct Module {
+    pub items: Vec<Item>,
+}
+
+#[derive(Clone)]
+pub struct Item {
+    pub public: Public,
+    pub name: StrRef,
+    pub kind: ItemKind,
+}
+
+#[derive(Clone)]
+pub enum ItemKind {
+    Fun(Fun),
+    Extern(Extern),
+    Cus(Cus),
+    /// The functions implemented for the type, and the interface they implement if any
+    For(Ty, Option<StrRef>, Vec<Item>),
+    /// The functions required by an interface by their names, where `Self` is the type
+    /// implementing it
+    Interface(Vec<(StrRef, Ty)>),
+    Val(Prim, Ty),
+    Mod(Module),
+}
+
+/// A function with typed arguments and body, whose type is [`Ty::Fun`]
+///
+/// The template parameters are named types within the function, each given a type where the
+/// function is used
+#[derive(Clone)]
+pub struct Fun {
+    pub templ: Vec<Templ>,
+    pub args: Pat,
+    pub body: Body,
+    pub abi: Option<StrRef>,
+    pub ty: Ty,
+}
+
+/// A template parameter with the interfaces its types must implement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Templ {
+    pub name: StrRef,
+    pub bounds: Vec<StrRef>,
+}
+
+/// A function declared without body, whose type is [`Ty::Fun`]
+#[derive(Clone)]
+pub struct Extern {
+    pub abi: StrRef,
+    pub ty: Ty,
+}
+
+/// The elements of a custom type in order, by their names and types
+pub type Elems = Vec<(StrRef, Ty)>;
+
+/// A custom type, either a compound of elements or a sum of variants
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cus {
+    Struct(Elems),
+    Union(Vec<(StrRef, Option<Elems>)>),
+}
+
+impl Cus {
+    /// Returns the elements of the compound, or of the variant of the sum
+    pub fn elems(&self, variant: Option<&StrRef>) -> Option<&Elems> {
+        match (self, variant) {
+            (Self::Struct(elems), None) => Some(elems),
+            (Self::Union(variants), Some(variant)) => variants
+                .iter()
+                .find(|(name, _)| name == variant)
+                .and_then(|(_, elems)| elems.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// A body of bindings followed by expressions, whose type follows the rules of
+/// [`imuc_ast::expr::Body`]
+#[derive(Clone)]
+pub struct Body {
+    pub bind: Vec<Bind>,
+    pub body: Vec<Expr>,
+    pub ty: Ty,
+}
+
+#[derive(Clone)]
+pub enum Bind {
+    Let(Pat, Expr),
+    Item(Item),
+}
+
+#[derive(Clone)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub ty: Ty,
+}
+
+#[derive(Clone)]
+pub enum ExprKind {
+    Prim(Prim),
+    /// A placeholder `_` taking any value
+    Unused,
+    Name(StrRef),
+    Res(ResVal),
+    /// The function of the name implemented for the type, or required by the interfaces of a
+    /// template parameter, taking the receiver as its first argument
+    Method(Ty, StrRef),
+    UnExpr(UnOp, Box<Expr>),
+    BinExpr(BinOp, Box<Expr>, Box<Expr>),
+    Body(Body),
+    If(Box<Expr>, Body, Option<Box<Expr>>),
+    Loop(Option<StrRef>, Body),
+    Break(Option<StrRef>, Option<Box<Expr>>),
+    Continue(Option<StrRef>),
+    Return(Option<Box<Expr>>),
+    Match(Box<Expr>, Vec<Arm>),
+    Tuple(Vec<Expr>),
+    /// A value of the custom type of the expression, or of its variant
+    Struct(Option<StrRef>, BTreeMap<StrRef, Expr>),
+    Field(Box<Expr>, StrRef),
+    Cast(Box<Expr>),
+    Index(Box<Expr>, Box<Expr>),
+    /// An assignment, with the operator of a compound assignment like `+=`
+    Assign(Option<BinOp>, Box<Expr>, Box<Expr>),
+    Array(Vec<Expr>),
+    Repeat(Box<Expr>, u64),
+    Closure(Closure),
+    Call(Box<Expr>, Vec<Expr>),
+}
+
+#[derive(Clone)]
+pub struct Arm {
+    pub pat: Pat,
+    pub guard: Option<Expr>,
+    pub val: Expr,
+}
+
+/// A closure with the locals it captures from the enclosing function, in the order of first use
+#[derive(Clone)]
+pub struct Closure {
+    pub args: Pat,
+    pub body: Box<Expr>,
+    pub captures: Vec<(StrRef, Ty)>,
+}
+
+#[derive(Clone)]
+pub struct Pat {
+    pub kind: PatKind,
+    pub ty: Ty,
+}
+
+#[derive(Clone)]
+pub enum PatKind {
+    /// A binding of the name, or of nothing for an unused name
+    Ident(Option<StrRef>, bool),
+    Lit(Prim),
+    Tuple(Vec<Pat>),
+    Any(Vec<Pat>),
+    /// A value of the custom type of the pattern, or of its variant
+    Struct(Option<StrRef>, BTreeMap<StrRef, Pat>),
+}
@@ -0,0 +1,357 @@
+use crate::hir;
+use crate::prelude::*;
+use crate::ty::VarKind;
+use imuc_lexer::token::ResTy;
+
+/// An inference variable, bound to a type once found, with what it is the type of
+struct Var {
+    ty: Option<Ty>,
+    kind: VarKind,
+    origin: String,
+    /// The type a literal left open settles on
+    default: Option<ResTy>,
+}
+
+/// Returns whether a variable of the kind may stand for the type
+fn fits(kind: VarKind, ty: &Ty) -> bool {
+    match kind {
+        VarKind::Any => true,
+        VarKind::Int => matches!(
+            ty,
+            Ty::Res(ResTy::I8 | ResTy::I16 | ResTy::I32 | ResTy::I64 | ResTy::I128 | ResTy::U128)
+        ),
+        VarKind::Float => matches!(ty, Ty::Res(ResTy::F32 | ResTy::F64)),
+    }
+}
+
+/// Converts the literal to the width of the type inferred for it
+fn literal(input: &prim::Prim, ty: &Ty) -> Result<prim::Prim> {
+    let integer = match (input, ty) {
+        (prim::Prim::Integer(integer), Ty::Res(res)) => {
+            let range = || errors::TypeError::LiteralRange {
+                value: match integer.to::<i128>() {
+                    Some(value) => value.to_string(),
+                    None => integer.to::<u128>().unwrap_or_default().to_string(),
+                },
+                ty: ty.to_string(),
+            };
+            match res {
+                ResTy::I8 => prim::Integer::I8(integer.to().ok_or_else(range)?),
+                ResTy::I16 => prim::Integer::I16(integer.to().ok_or_else(range)?),
+                ResTy::I32 => prim::Integer::I32(integer.to().ok_or_else(range)?),
+                ResTy::I64 => prim::Integer::I64(integer.to().ok_or_else(range)?),
+                ResTy::I128 => prim::Integer::I128(integer.to().ok_or_else(range)?),
+                ResTy::U128 => prim::Integer::U128(integer.to().ok_or_else(range)?),
+                _ => return Ok(input.clone()),
+            }
+        }
+        (prim::Prim::Float(prim::Float::F32(value)), Ty::Res(ResTy::F64)) => {
+            return Ok(prim::Prim::Float(prim::Float::F64(f64::from(*value))))
+        }
+        _ => return Ok(input.clone()),
+    };
+    Ok(prim::Prim::Integer(integer))
+}
+
+/// The inference variables of the functions being checked, unified with the types they meet
+///
+/// A variable of any type meeting [`Ty::Unknown`] is bound to it, as nothing more can be
+/// learned, while the variables of literals keep waiting for a width. Once a function is
+/// checked, its types are settled, where the literals left default to `I64` and `F32`, or to
+/// the first of `I128` and `U128` holding the widest integer met, and any other variable left
+/// is ambiguous
+#[derive(Default)]
+pub(crate) struct Infer {
+    vars: Vec<Var>,
+}
+
+impl Infer {
+    pub fn clear(&mut self) {
+        self.vars.clear();
+    }
+
+    /// Creates a variable for the type of the origin, like "`x`"
+    pub fn fresh(&mut self, kind: VarKind, origin: impl Into<String>) -> Ty {
+        let default = match kind {
+            VarKind::Any => None,
+            VarKind::Int => Some(ResTy::I64),
+            VarKind::Float => Some(ResTy::F32),
+        };
+        self.vars.push(Var {
+            ty: None,
+            kind,
+            origin: origin.into(),
+            default,
+        });
+        Ty::Var(self.vars.len() - 1, kind)
+    }
+
+    /// Makes the variable of an integer literal settle on the type at least, if left open
+    pub fn widen(&mut self, ty: &Ty, res: ResTy) {
+        if let Ty::Var(id, _) = self.shallow(ty.clone()) {
+            let var = &mut self.vars[id];
+            var.default = var.default.max(Some(res));
+        }
+    }
+
+    /// Replaces the parts of an annotation written as `_` with fresh variables
+    pub fn instantiate(&mut self, ty: Ty, origin: &str) -> Ty {
+        let mut each = |list: Vec<Ty>| {
+            list.into_iter()
+                .map(|ty| self.instantiate(ty, origin))
+                .collect()
+        };
+        match ty {
+            Ty::Unknown => self.fresh(VarKind::Any, origin),
+            Ty::Named(name, args) => Ty::Named(name, each(args)),
+            Ty::Tuple(elem) => Ty::Tuple(each(elem)),
+            Ty::Fun(args, ret) => {
+                let args = each(args);
+                Ty::Fun(args, Box::new(self.instantiate(*ret, origin)))
+            }
+            Ty::Shared(ty) => Ty::Shared(Box::new(self.instantiate(*ty, origin))),
+            Ty::Array(item, len) => Ty::Array(Box::new(self.instantiate(*item, origin)), len),
+            Ty::Slice(item) => Ty::Slice(Box::new(self.instantiate(*item, origin))),
+            ty => ty,
+        }
+    }
+
+    /// Follows the bound variables at the top of the type
+    fn shallow(&self, mut ty: Ty) -> Ty {
+        while let Ty::Var(id, _) = ty {
+            match &self.vars[id].ty {
+                Some(bound) => ty = bound.clone(),
+                None => return Ty::Var(id, self.vars[id].kind),
+            }
+        }
+        ty
+    }
+
+    /// Replaces the bound variables in the type with what they are bound to
+    pub fn resolve(&self, ty: &Ty) -> Ty {
+        let each = |list: &[Ty]| list.iter().map(|ty| self.resolve(ty)).collect();
+        match self.shallow(ty.clone()) {
+            Ty::Named(name, args) => Ty::Named(name, each(&args)),
+            Ty::Tuple(elem) => Ty::Tuple(each(&elem)),
+            Ty::Fun(args, ret) => Ty::Fun(each(&args), Box::new(self.resolve(&ret))),
+            Ty::Shared(ty) => Ty::Shared(Box::new(self.resolve(&ty))),
+            Ty::Array(item, len) => Ty::Array(Box::new(self.resolve(&item)), len),
+            Ty::Slice(item) => Ty::Slice(Box::new(self.resolve(&item))),
+            ty => ty,
+        }
+    }
+
+    /// Returns whether the variable appears in the type, which would make it infinite
+    fn occurs(&self, id: usize, ty: &Ty) -> bool {
+        match self.resolve(ty) {
+            Ty::Var(found, _) => found == id,
+            ty => {
+                let mut found = false;
+                let mut visit = |ty: &Ty| found |= self.occurs(id, ty);
+                match &ty {
+                    Ty::Named(_, list) | Ty::Tuple(list) => list.iter().for_each(&mut visit),
+                    Ty::Fun(args, ret) => {
+                        args.iter().for_each(&mut visit);
+                        visit(ret);
+                    }
+                    Ty::Shared(ty) | Ty::Array(ty, _) | Ty::Slice(ty) => visit(ty),
+                    _ => {}
+                }
+                found
+            }
+        }
+    }
+
+    fn join_all(&mut self, lhs: Vec<Ty>, rhs: Vec<Ty>) -> Option<Vec<Ty>> {
+        if lhs.len() != rhs.len() {
+            return None;
+        }
+        lhs.into_iter()
+            .zip(rhs)
+            .map(|(lhs, rhs)| self.join(lhs, rhs))
+            .collect()
+    }
+
+    /// Joins two types like [`Ty::join`], binding the variables met to the other side
+    fn join(&mut self, lhs: Ty, rhs: Ty) -> Option<Ty> {
+        match (self.shallow(lhs), self.shallow(rhs)) {
+            (Ty::Var(lhs, _), Ty::Var(rhs, kind)) if lhs == rhs => Some(Ty::Var(rhs, kind)),
+            (Ty::Var(lhs, lhs_kind), Ty::Var(rhs, rhs_kind)) => {
+                let kind = match (lhs_kind, rhs_kind) {
+                    (VarKind::Any, kind) | (kind, VarKind::Any) => kind,
+                    (lhs, rhs) if lhs == rhs => lhs,
+                    _ => return None,
+                };
+                self.vars[rhs].kind = kind;
+                self.vars[rhs].default = self.vars[rhs].default.max(self.vars[lhs].default);
+                self.vars[lhs].ty = Some(Ty::Var(rhs, kind));
+                Some(Ty::Var(rhs, kind))
+            }
+            (Ty::Var(id, kind), ty) | (ty, Ty::Var(id, kind)) => match ty {
+                Ty::Never => Some(Ty::Var(id, kind)),
+                Ty::Unknown if kind != VarKind::Any => Some(Ty::Var(id, kind)),
+                ty if fits(kind, &ty) && !self.occurs(id, &ty) => {
+                    self.vars[id].ty = Some(ty.clone());
+                    Some(ty)
+                }
+                _ => None,
+            },
+            (Ty::Never, other) | (other, Ty::Never) => Some(other),
+            (Ty::Unknown, other) | (other, Ty::Unknown) => Some(other),
+            (Ty::Named(lhs, lhs_args), Ty::Named(rhs, rhs_args)) if lhs == rhs => {
+                Some(Ty::Named(lhs, self.join_all(lhs_args, rhs_args)?))
+            }
+            (Ty::Tuple(lhs), Ty::Tuple(rhs)) => Some(Ty::Tuple(self.join_all(lhs, rhs)?)),
+            (Ty::Shared(lhs), Ty::Shared(rhs)) => {
+                Some(Ty::Shared(Box::new(self.join(*lhs, *rhs)?)))
+            }
+            (Ty::Fun(lhs_args, lhs), Ty::Fun(rhs_args, rhs)) => Some(Ty::Fun(
+                self.join_all(lhs_args, rhs_args)?,
+                Box::new(self.join(*lhs, *rhs)?),
+            )),
+            (Ty::Array(lhs, lhs_len), Ty::Array(rhs, rhs_len)) if lhs_len == rhs_len => {
+                Some(Ty::Array(Box::new(self.join(*lhs, *rhs)?), lhs_len))
+            }
+            (Ty::Slice(lhs), Ty::Slice(rhs)) => Some(Ty::Slice(Box::new(self.join(*lhs, *rhs)?))),
+            (lhs, rhs) if lhs == rhs => Some(lhs),
+            _ => None,
+        }
+    }
+
+    /// Joins the type found into the type expected, failing if they cannot be the same
+    pub fn unify(&mut self, expect: Ty, found: Ty) -> Result<Ty> {
+        match self.join(expect.clone(), found.clone()) {
+            Some(ty) => Ok(ty),
+            None => Err(errors::TypeError::Mismatch {
+                expect: self.resolve(&expect).to_string(),
+                found: self.resolve(&found).to_string(),
+            }
+            .into()),
+        }
+    }
+
+    /// Resolves the type for good, defaulting the literals left and failing on other variables
+    pub fn settle(&self, ty: &Ty) -> Result<Ty> {
+        let each = |list: &[Ty]| list.iter().map(|ty| self.settle(ty)).collect::<Result<_>>();
+        let ty = match self.shallow(ty.clone()) {
+            Ty::Var(id, VarKind::Int | VarKind::Float) => Ty::Res(
+                self.vars[id]
+                    .default
+                    .expect("variables of literals should have a default"),
+            ),
+            Ty::Var(id, VarKind::Any) => {
+                return Err(errors::TypeError::Ambiguous(self.vars[id].origin.clone()).into())
+            }
+            Ty::Named(name, args) => Ty::Named(name, each(&args)?),
+            Ty::Tuple(elem) => Ty::Tuple(each(&elem)?),
+            Ty::Fun(args, ret) => Ty::Fun(each(&args)?, Box::new(self.settle(&ret)?)),
+            Ty::Shared(ty) => Ty::Shared(Box::new(self.settle(&ty)?)),
+            Ty::Array(item, len) => Ty::Array(Box::new(self.settle(&item)?), len),
+            Ty::Slice(item) => Ty::Slice(Box::new(self.settle(&item)?)),
+            ty => ty,
+        };
+        Ok(ty)
+    }
+
+    pub fn settle_pat(&self, input: &mut hir::Pat) -> Result<()> {
+        input.ty = self.settle(&input.ty)?;
+        match &mut input.kind {
+            hir::PatKind::Ident(..) => {}
+            hir::PatKind::Lit(lit) => *lit = literal(lit, &input.ty)?,
+            hir::PatKind::Tuple(list) | hir::PatKind::Any(list) => {
+                for pat in list.iter_mut() {
+                    self.settle_pat(pat)?;
+                }
+            }
+            hir::PatKind::Struct(_, elem) => {
+                for pat in elem.values_mut() {
+                    self.settle_pat(pat)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Settles the types of the body, where its items are settled on their own
+    pub fn settle_body(&self, input: &mut hir::Body) -> Result<()> {
+        input.ty = self.settle(&input.ty)?;
+        for bind in input.bind.iter_mut() {
+            if let hir::Bind::Let(pat, val) = bind {
+                self.settle_expr(val)?;
+                self.settle_pat(pat)?;
+            }
+        }
+        for expr in input.body.iter_mut() {
+            self.settle_expr(expr)?;
+        }
+        Ok(())
+    }
+
+    pub fn settle_expr(&self, input: &mut hir::Expr) -> Result<()> {
+        input.ty = self.settle(&input.ty)?;
+        match &mut input.kind {
+            hir::ExprKind::Prim(prim) => *prim = literal(prim, &input.ty)?,
+            hir::ExprKind::Method(ty, _) => *ty = self.settle(ty)?,
+            hir::ExprKind::Unused
+            | hir::ExprKind::Name(_)
+            | hir::ExprKind::Res(_)
+            | hir::ExprKind::Continue(_)
+            | hir::ExprKind::Break(_, None)
+            | hir::ExprKind::Return(None) => {}
+            hir::ExprKind::UnExpr(_, val)
+            | hir::ExprKind::Break(_, Some(val))
+            | hir::ExprKind::Return(Some(val))
+            | hir::ExprKind::Field(val, _)
+            | hir::ExprKind::Cast(val)
+            | hir::ExprKind::Repeat(val, _) => self.settle_expr(val)?,
+            hir::ExprKind::BinExpr(_, lhs, rhs)
+            | hir::ExprKind::Index(lhs, rhs)
+            | hir::ExprKind::Assign(_, lhs, rhs) => {
+                self.settle_expr(lhs)?;
+                self.settle_expr(rhs)?;
+            }
+            hir::ExprKind::Body(body) | hir::ExprKind::Loop(_, body) => self.settle_body(body)?,
+            hir::ExprKind::If(cond, body, other) => {
+                self.settle_expr(cond)?;
+                self.settle_body(body)?;
+                if let Some(other) = other {
+                    self.settle_expr(other)?;
+                }
+            }
+            hir::ExprKind::Match(val, arms) => {
+                self.settle_expr(val)?;
+                for arm in arms.iter_mut() {
+                    self.settle_pat(&mut arm.pat)?;
+                    if let Some(guard) = &mut arm.guard {
+                        self.settle_expr(guard)?;
+                    }
+                    self.settle_expr(&mut arm.val)?;
+                }
+            }
+            hir::ExprKind::Tuple(list) | hir::ExprKind::Array(list) => {
+                for expr in list.iter_mut() {
+                    self.settle_expr(expr)?;
+                }
+            }
+            hir::ExprKind::Struct(_, elem) => {
+                for expr in elem.values_mut() {
+                    self.settle_expr(expr)?;
+                }
+            }
+            hir::ExprKind::Closure(closure) => {
+                self.settle_pat(&mut closure.args)?;
+                self.settle_expr(&mut closure.body)?;
+                for (_, ty) in closure.captures.iter_mut() {
+                    *ty = self.settle(ty)?;
+                }
+            }
+            hir::ExprKind::Call(fun, args) => {
+                self.settle_expr(fun)?;
+                for arg in args.iter_mut() {
+                    self.settle_expr(arg)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+pub mod attr;
+pub mod builtin;
+pub mod capture;
+mod check;
+pub mod checks;
+pub mod hir;
+mod infer;
+mod prelude;
+mod scope;
+pub mod ty;
+pub mod visit;
+mod warn;
+
+pub use check::Check;
+pub use warn::Warnings;
@@ -0,0 +1,4 @@
+pub(crate) use crate::ty::Ty;
+pub(crate) use crate::Check;
+pub(crate) use imuc_ast::*;
+pub(crate) use imuc_error::*;
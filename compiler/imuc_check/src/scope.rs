@@ -0,0 +1,32 @@
+/// A check keeping the bindings visible to the current scope, innermost last
+///
+/// Bodies, match arms and closures are scopes whose bindings are dropped when they end, while
+/// nested functions never see the locals of the outer function
+pub(crate) trait Scoped: Sized {
+    type Binding;
+
+    fn bindings(&mut self) -> &mut Vec<Self::Binding>;
+
+    /// Leaves the scope entered when there were as many bindings as the mark, dropping the
+    /// bindings made since
+    fn leave(&mut self, mark: usize) {
+        self.bindings().truncate(mark);
+    }
+
+    /// Runs the closure in a new scope, dropping the bindings it creates afterwards
+    fn scoped<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let mark = self.bindings().len();
+        let result = f(self);
+        self.leave(mark);
+        result
+    }
+
+    /// Runs the closure for the body of a nested function, where no binding of the outer
+    /// function is visible
+    fn function<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let outer = std::mem::take(self.bindings());
+        let result = f(self);
+        *self.bindings() = outer;
+        result
+    }
+}
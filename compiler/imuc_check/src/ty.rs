@@ -0,0 +1,195 @@
+use imuc_ast::{pat, prim, StrRef};
+use imuc_lexer::token::ResTy;
+use std::fmt;
+
+/// A type as seen by the semantic checks, built from type annotations and evident expressions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ty {
+    /// The type of expressions that never produce a value, such as `break` or endless loops
+    Never,
+    /// A type that cannot be told by the check, compatible with any other type
+    Unknown,
+    Unit,
+    Res(ResTy),
+    Named(StrRef, Vec<Ty>),
+    Tuple(Vec<Ty>),
+    Shared(Box<Ty>),
+    Fun(Vec<Ty>, Box<Ty>),
+    Array(Box<Ty>, u64),
+    Slice(Box<Ty>),
+    /// A type being inferred by the type check, which never leaves it
+    Var(usize, VarKind),
+}
+
+/// The types an inference variable may stand for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarKind {
+    Any,
+    /// Any integer, as the type of an integer literal
+    Int,
+    /// Any float, as the type of a float literal
+    Float,
+}
+
+impl Ty {
+    /// Returns whether the type is known to the check, i.e. not [`Ty::Unknown`], [`Ty::Never`]
+    /// or [`Ty::Var`]
+    pub fn is_known(&self) -> bool {
+        !matches!(self, Self::Unknown | Self::Never | Self::Var(..))
+    }
+
+    /// Joins two types that flow into the same place
+    ///
+    /// [`Ty::Never`] joins into anything, and [`Ty::Unknown`] gives way to a known type, also
+    /// as a part of compound types. Returns [`None`] if both types are known but different
+    pub fn join(self, other: Ty) -> Option<Ty> {
+        match (self, other) {
+            (Self::Never, other) | (other, Self::Never) => Some(other),
+            (Self::Unknown, other) | (other, Self::Unknown) => Some(other),
+            (Self::Named(lhs, lhs_args), Self::Named(rhs, rhs_args)) if lhs == rhs => {
+                Some(Self::Named(lhs, join_all(lhs_args, rhs_args)?))
+            }
+            (Self::Tuple(lhs), Self::Tuple(rhs)) => Some(Self::Tuple(join_all(lhs, rhs)?)),
+            (Self::Shared(lhs), Self::Shared(rhs)) => Some(Self::Shared(Box::new(lhs.join(*rhs)?))),
+            (Self::Fun(lhs_args, lhs), Self::Fun(rhs_args, rhs)) => Some(Self::Fun(
+                join_all(lhs_args, rhs_args)?,
+                Box::new(lhs.join(*rhs)?),
+            )),
+            (Self::Array(lhs, lhs_len), Self::Array(rhs, rhs_len)) if lhs_len == rhs_len => {
+                Some(Self::Array(Box::new(lhs.join(*rhs)?), lhs_len))
+            }
+            (Self::Slice(lhs), Self::Slice(rhs)) => Some(Self::Slice(Box::new(lhs.join(*rhs)?))),
+            (lhs, rhs) if lhs == rhs => Some(lhs),
+            _ => None,
+        }
+    }
+
+    /// Replaces the parts of the type the function gives a type for, such as `Self` or
+    /// template parameters
+    pub fn subst(&self, f: &impl Fn(&Ty) -> Option<Ty>) -> Ty {
+        if let Some(ty) = f(self) {
+            return ty;
+        }
+        let each = |list: &[Ty]| list.iter().map(|ty| ty.subst(f)).collect();
+        match self {
+            Self::Named(name, args) => Self::Named(name.clone(), each(args)),
+            Self::Tuple(elem) => Self::Tuple(each(elem)),
+            Self::Shared(ty) => Self::Shared(Box::new(ty.subst(f))),
+            Self::Fun(args, ret) => Self::Fun(each(args), Box::new(ret.subst(f))),
+            Self::Array(item, len) => Self::Array(Box::new(item.subst(f)), *len),
+            Self::Slice(item) => Self::Slice(Box::new(item.subst(f))),
+            ty => ty.clone(),
+        }
+    }
+}
+
+/// Joins the types pairwise, which must be as many
+fn join_all(lhs: Vec<Ty>, rhs: Vec<Ty>) -> Option<Vec<Ty>> {
+    if lhs.len() != rhs.len() {
+        return None;
+    }
+    lhs.into_iter()
+        .zip(rhs)
+        .map(|(lhs, rhs)| lhs.join(rhs))
+        .collect()
+}
+
+impl From<&prim::Prim> for Ty {
+    fn from(value: &prim::Prim) -> Self {
+        match value {
+            prim::Prim::Integer(integer) => Self::Res(match integer {
+                prim::Integer::I8(_) => ResTy::I8,
+                prim::Integer::I16(_) => ResTy::I16,
+                prim::Integer::I32(_) => ResTy::I32,
+                prim::Integer::I64(_) => ResTy::I64,
+                prim::Integer::I128(_) => ResTy::I128,
+                prim::Integer::U128(_) => ResTy::U128,
+            }),
+            prim::Prim::Float(float) => Self::Res(match float {
+                prim::Float::F32(_) => ResTy::F32,
+                prim::Float::F64(_) => ResTy::F64,
+            }),
+            prim::Prim::String(_) => Self::Res(ResTy::Str),
+            prim::Prim::Bool(_) => Self::Res(ResTy::Bool),
+            prim::Prim::Unit => Self::Unit,
+        }
+    }
+}
+
+impl From<&pat::Type> for Ty {
+    fn from(value: &pat::Type) -> Self {
+        let ty = match &value.kind {
+            pat::TypeKind::Wildcard => Self::Unknown,
+            pat::TypeKind::Res(res) => Self::Res(*res),
+            pat::TypeKind::Single(name) => Self::Named(name.clone(), Vec::new()),
+            pat::TypeKind::Template(name, args) => {
+                Self::Named(name.clone(), args.iter().map(Ty::from).collect())
+            }
+            pat::TypeKind::Fun(args, ret) => Self::Fun(
+                args.iter().map(Ty::from).collect(),
+                Box::new(ret.as_deref().map_or(Self::Unit, Ty::from)),
+            ),
+            pat::TypeKind::Array(item, len) => Self::Array(Box::new(Ty::from(item.as_ref())), *len),
+            pat::TypeKind::Slice(item) => Self::Slice(Box::new(Ty::from(item.as_ref()))),
+        };
+        match value.flags {
+            pat::PatFlags::Unique => ty,
+            pat::PatFlags::Shared => Self::Shared(Box::new(ty)),
+        }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Never => write!(f, "!"),
+            Self::Unknown => write!(f, "_"),
+            Self::Unit => write!(f, "()"),
+            Self::Res(ResTy::SelfType) => write!(f, "Self"),
+            Self::Res(res) => write!(f, "{:?}", res),
+            Self::Named(name, args) => {
+                write!(f, "{}", name)?;
+                if !args.is_empty() {
+                    write!(f, "[")?;
+                    for (i, arg) in args.iter().enumerate() {
+                        if i != 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}", arg)?;
+                    }
+                    write!(f, "]")?;
+                }
+                Ok(())
+            }
+            Self::Tuple(elem) => {
+                write!(f, "(")?;
+                for (i, ty) in elem.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", ty)?;
+                }
+                if elem.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Self::Shared(ty) => write!(f, "@{}", ty),
+            Self::Fun(args, ret) => {
+                write!(f, "fun(")?;
+                for (i, ty) in args.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", ty)?;
+                }
+                write!(f, ") => {}", ret)
+            }
+            Self::Array(item, len) => write!(f, "[{}; {}]", item, len),
+            Self::Slice(item) => write!(f, "[{}]", item),
+            Self::Var(_, VarKind::Any) => write!(f, "_"),
+            Self::Var(_, VarKind::Int) => write!(f, "{{integer}}"),
+            Self::Var(_, VarKind::Float) => write!(f, "{{float}}"),
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use crate::prelude::*;
+
+/// Walks through a syntax tree, calling back on every node
+///
+/// Every method defaults to the corresponding `walk_*` function that visits the children,
+/// so a visitor only overrides the nodes it cares about and calls `walk_*` to keep going
+pub trait Visit {
+    fn visit_module(&mut self, input: &module::Module) -> Result<()> {
+        walk_module(self, input)
+    }
+
+    fn visit_item(&mut self, input: &item::Item) -> Result<()> {
+        walk_item(self, input)
+    }
+
+    fn visit_body(&mut self, input: &expr::Body) -> Result<()> {
+        walk_body(self, input)
+    }
+
+    fn visit_bind(&mut self, input: &bind::Bind) -> Result<()> {
+        walk_bind(self, input)
+    }
+
+    fn visit_expr(&mut self, input: &expr::Expr) -> Result<()> {
+        walk_expr(self, input)
+    }
+
+    fn visit_flow(&mut self, input: &flow::Flow) -> Result<()> {
+        walk_flow(self, input)
+    }
+
+    fn visit_pat(&mut self, input: &pat::Pat) -> Result<()> {
+        walk_pat(self, input)
+    }
+}
+
+pub fn walk_module<V: Visit + ?Sized>(visitor: &mut V, input: &module::Module) -> Result<()> {
+    for item in input.items.iter() {
+        visitor.visit_item(item)?;
+    }
+    Ok(())
+}
+
+pub fn walk_item<V: Visit + ?Sized>(visitor: &mut V, input: &item::Item) -> Result<()> {
+    match &input.kind {
+        item::ItemKind::Fun(fun) => {
+            visitor.visit_pat(&fun.args)?;
+            visitor.visit_body(&fun.body)
+        }
+        item::ItemKind::Extern(ext) => visitor.visit_pat(&ext.args),
+        item::ItemKind::Cus(item::Cus::Struct(elem, elems)) => {
+            visitor.visit_pat(elem)?;
+            for expr in elems.iter().filter_map(|elem| elem.default.as_ref()) {
+                visitor.visit_expr(expr)?;
+            }
+            Ok(())
+        }
+        item::ItemKind::Cus(item::Cus::Union(variants)) => {
+            for elem in variants.iter().filter_map(|variant| variant.elem.as_ref()) {
+                visitor.visit_pat(elem)?;
+            }
+            Ok(())
+        }
+        item::ItemKind::For(items) => {
+            for item in items.items.iter() {
+                visitor.visit_item(item)?;
+            }
+            Ok(())
+        }
+        item::ItemKind::Interface(interface) => {
+            for decl in interface.decls.iter() {
+                visitor.visit_pat(&decl.args)?;
+            }
+            Ok(())
+        }
+        item::ItemKind::Val(_) => Ok(()),
+        item::ItemKind::Mod(module) => visitor.visit_module(module),
+    }
+}
+
+pub fn walk_body<V: Visit + ?Sized>(visitor: &mut V, input: &expr::Body) -> Result<()> {
+    for bind in input.bind.iter() {
+        visitor.visit_bind(bind)?;
+    }
+    for expr in input.body.iter() {
+        visitor.visit_expr(expr)?;
+    }
+    Ok(())
+}
+
+pub fn walk_bind<V: Visit + ?Sized>(visitor: &mut V, input: &bind::Bind) -> Result<()> {
+    match input {
+        bind::Bind::Item(item) => visitor.visit_item(item),
+        bind::Bind::Let(bind) => {
+            visitor.visit_expr(&bind.val)?;
+            visitor.visit_pat(&bind.pat)
+        }
+    }
+}
+
+pub fn walk_expr<V: Visit + ?Sized>(visitor: &mut V, input: &expr::Expr) -> Result<()> {
+    match input {
+        expr::Expr::Prim(_) | expr::Expr::Value(_) => Ok(()),
+        expr::Expr::UnExpr(expr) => visitor.visit_expr(expr.val.as_ref()),
+        expr::Expr::BinExpr(expr) => {
+            visitor.visit_expr(expr.lhs.as_ref())?;
+            visitor.visit_expr(expr.rhs.as_ref())
+        }
+        expr::Expr::Body(body) => visitor.visit_body(body),
+        expr::Expr::Flow(flow) => visitor.visit_flow(flow),
+        expr::Expr::Tuple(tuple) => {
+            for expr in tuple.elem.iter() {
+                visitor.visit_expr(expr)?;
+            }
+            Ok(())
+        }
+        expr::Expr::Struct(cus) => {
+            for expr in cus.elem.values().chain(cus.base.as_deref()) {
+                visitor.visit_expr(expr)?;
+            }
+            Ok(())
+        }
+        expr::Expr::Field(field) => visitor.visit_expr(field.val.as_ref()),
+        expr::Expr::Cast(cast) => visitor.visit_expr(cast.val.as_ref()),
+        expr::Expr::Try(expr) => visitor.visit_expr(expr.val.as_ref()),
+        expr::Expr::Index(index) => {
+            visitor.visit_expr(index.val.as_ref())?;
+            visitor.visit_expr(index.index.as_ref())
+        }
+        expr::Expr::Assign(assign) => {
+            visitor.visit_expr(assign.place.as_ref())?;
+            visitor.visit_expr(assign.val.as_ref())
+        }
+        expr::Expr::Closure(closure) => {
+            visitor.visit_pat(&closure.args)?;
+            visitor.visit_expr(closure.body.as_ref())
+        }
+        expr::Expr::Array(expr::Array::List(elem)) => {
+            for expr in elem.iter() {
+                visitor.visit_expr(expr)?;
+            }
+            Ok(())
+        }
+        expr::Expr::Array(expr::Array::Repeat(val, _)) => visitor.visit_expr(val.as_ref()),
+        expr::Expr::Call(call) => {
+            visitor.visit_expr(call.fun.as_ref())?;
+            for expr in call.args.iter() {
+                visitor.visit_expr(expr)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn walk_flow<V: Visit + ?Sized>(visitor: &mut V, input: &flow::Flow) -> Result<()> {
+    match input {
+        flow::Flow::If(flow) => {
+            visitor.visit_expr(flow.cond.as_ref())?;
+            visitor.visit_body(&flow.body)?;
+            if let Some(other) = &flow.other {
+                visitor.visit_expr(other.as_ref())?;
+            }
+            Ok(())
+        }
+        flow::Flow::Loop(flow) => visitor.visit_body(&flow.body),
+        flow::Flow::Break(flow::Break { val, .. }) | flow::Flow::Return(flow::Return { val }) => {
+            if let Some(val) = val {
+                visitor.visit_expr(val.as_ref())?;
+            }
+            Ok(())
+        }
+        flow::Flow::Continue(_) => Ok(()),
+        flow::Flow::Match(flow) => {
+            visitor.visit_expr(flow.val.as_ref())?;
+            for arm in flow.arms.iter() {
+                visitor.visit_pat(&arm.pat)?;
+                if let Some(guard) = &arm.guard {
+                    visitor.visit_expr(guard)?;
+                }
+                visitor.visit_expr(&arm.val)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn walk_pat<V: Visit + ?Sized>(visitor: &mut V, input: &pat::Pat) -> Result<()> {
+    match input {
+        pat::Pat::Ident(_) | pat::Pat::Lit(_) => Ok(()),
+        pat::Pat::Tuple(pat::TuplePat(list)) | pat::Pat::Any(pat::AnyPat(list)) => {
+            for pat in list.iter() {
+                visitor.visit_pat(pat)?;
+            }
+            Ok(())
+        }
+        pat::Pat::Struct(cus) => {
+            for pat in cus.elem.values() {
+                visitor.visit_pat(pat)?;
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::prelude::*;
+
+/// Problems found by checks that do not stop the compilation, in the order they are found
+#[derive(Debug, Default)]
+pub struct Warnings {
+    list: Vec<Error>,
+}
+
+impl Warnings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, warn: impl Into<Error>) {
+        self.list.push(warn.into());
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Error> {
+        self.list.iter()
+    }
+
+    /// Adds a context to the warnings pushed since the list had `mark` warnings
+    pub fn context_since<C, F>(&mut self, mark: usize, f: F)
+    where
+        C: std::fmt::Display + Send + Sync + 'static,
+        F: Fn() -> C,
+    {
+        let list = std::mem::take(&mut self.list);
+        self.list = list
+            .into_iter()
+            .enumerate()
+            .map(|(i, warn)| if i < mark { warn } else { warn.context(f()) })
+            .collect();
+    }
+}
+
+impl IntoIterator for Warnings {
+    type Item = Error;
+    type IntoIter = std::vec::IntoIter<Error>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ast::item;
+    use imuc_check::attr::{self, Known};
+    use imuc_check::checks::AttrCheck;
+    use imuc_check::Check;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+
+    fn parse(name: &str, content: &str) -> imuc_ast::module::Module {
+        let mut parser = Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+        rules::ModuleRules
+            .parse(&mut parser)
+            .expect("parsing should not fail")
+            .expect("module rule should always match")
+    }
+
+    /// Checks the content, returning the result and the messages of warnings
+    fn check(name: &str, content: &str) -> (Result<(), String>, Vec<String>) {
+        let module = parse(name, content);
+        let mut checker = AttrCheck::new();
+        let result = checker
+            .check(&module)
+            .map_err(|err| err.root_cause().to_string());
+        let warnings = checker
+            .warnings
+            .iter()
+            .map(|warn| warn.root_cause().to_string())
+            .collect();
+        (result, warnings)
+    }
+
+    #[test]
+    fn check_known() {
+        let (result, warnings) = check(
+            "check_known: ok",
+            r#"
+            #[inline(never)] #[deprecated("use cat")]
+            fun dog(#[cfg(test)] x) {
+                #[test] fun inner() {}
+            }
+            #[cfg(not(test))] cus Point(#[deprecated] x: I64)
+            #[extern] cus Pair(x: I32, y: I64)
+            #[deprecated] extern fun puts(#[cfg(test)] s: Ptr) => I32
+            "#,
+        );
+        assert_eq!(result, Ok(()));
+        assert!(warnings.is_empty(), "unexpected warnings: {:?}", warnings);
+
+        let (result, _) = check("check_known: target", "#[inline] cus Point(x: I64)");
+        assert_eq!(
+            result,
+            Err("attribute `inline` cannot be used on custom types".to_owned())
+        );
+        let (result, _) = check("check_known: args", "#[test(1)] fun dog() {}");
+        assert_eq!(
+            result,
+            Err("attribute `test` expects no arguments".to_owned())
+        );
+        let (result, _) = check("check_known: extern", "#[extern] fun dog() {}");
+        assert_eq!(
+            result,
+            Err("attribute `extern` cannot be used on functions".to_owned())
+        );
+        let (result, _) = check("check_known: cfg", "#[cfg] fun dog() {}");
+        assert_eq!(
+            result,
+            Err("attribute `cfg` expects a single predicate".to_owned())
+        );
+    }
+
+    #[test]
+    fn check_unknown() {
+        let (result, warnings) = check(
+            "check_unknown",
+            "#[dog] fun dog(#[cat] x) {} cus Point(#[bird(1)] x: I64)",
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            warnings,
+            [
+                "unknown attribute `dog`",
+                "unknown attribute `cat`",
+                "unknown attribute `bird`"
+            ]
+        );
+    }
+
+    #[test]
+    fn find_known() {
+        let module = parse(
+            "find_known",
+            r#"#[deprecated("use cat")] #[inline] fun dog() {} #[extern] cus Point(x: I32)"#,
+        );
+        let [dog, point] = module.items.as_slice() else {
+            panic!("expected two items");
+        };
+        assert!(matches!(dog.kind, item::ItemKind::Fun(_)));
+        assert!(attr::has(&dog.attrs, Known::Inline));
+        assert!(attr::find(&dog.attrs, Known::Deprecated).is_some_and(|attr| attr.args.len() == 1));
+        assert!(!attr::has(&dog.attrs, Known::Test));
+        assert!(attr::has(&point.attrs, Known::Extern));
+        assert!(!attr::has(&point.attrs, Known::Inline));
+        assert_eq!(Known::from_name("cfg"), Some(Known::Cfg));
+        assert_eq!(Known::from_name("dog"), None);
+    }
+}
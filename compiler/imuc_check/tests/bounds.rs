@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use imuc_check::checks::BoundCheck;
+    use imuc_check::Check;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+
+    /// Checks the content, returning the message of the root cause of the error
+    fn check(name: &str, content: &str) -> Result<(), String> {
+        let mut parser = Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+        let module = rules::ModuleRules
+            .parse(&mut parser)
+            .expect("parsing should not fail")
+            .expect("module rule should always match");
+        BoundCheck::new()
+            .check(&module)
+            .map_err(|err| err.root_cause().to_string())
+    }
+
+    #[test]
+    fn check_bounds() {
+        assert!(check(
+            "check_bounds: literal",
+            "fun f() { let a = [1, 2, 3]; a[2] }"
+        )
+        .is_ok());
+        assert!(check(
+            "check_bounds: dynamic",
+            "fun f(i: I64) { let a = [0; 4]; a[i] }"
+        )
+        .is_ok());
+        assert!(check("check_bounds: slice", "fun f(a: [I64]) { a[10] }").is_ok());
+        assert_eq!(
+            check("check_bounds: out", "fun f() { let a = [1, 2, 3]; a[3] }"),
+            Err("index 3 is out of bounds of length 3".to_owned())
+        );
+        assert!(check("check_bounds: negative", "fun f() { [0; 2][-1] }").is_err());
+        assert!(check("check_bounds: annotated", "fun f(a: [I64; 2]) { a[2] }").is_err());
+        assert!(check(
+            "check_bounds: shadowed",
+            "fun f() { let a = [1, 2]; { let a = [0; 8]; a[5] } }"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn check_bounds_range() {
+        assert!(check(
+            "check_bounds_range: whole",
+            "fun f() { let a = [0; 4]; a[0..4] }"
+        )
+        .is_ok());
+        assert!(check(
+            "check_bounds_range: inclusive",
+            "fun f() { let a = [0; 4]; a[1..=3] }"
+        )
+        .is_ok());
+        assert_eq!(
+            check(
+                "check_bounds_range: out",
+                "fun f() { let a = [0; 4]; a[2..=4] }"
+            ),
+            Err("range 2..5 is out of bounds of length 4".to_owned())
+        );
+        assert!(check(
+            "check_bounds_range: reversed",
+            "fun f() { let a = [0; 4]; a[3..1] }"
+        )
+        .is_err());
+    }
+}
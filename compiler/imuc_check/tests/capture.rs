@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ast::*;
+    use imuc_check::capture::captures;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+
+    /// Returns the names captured by the closure
+    fn capture_names(name: &str, content: &str) -> Vec<String> {
+        let mut parser = Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+        let expr = rules::ExprRule { end: () }
+            .parse(&mut parser)
+            .expect("parsing should not fail")
+            .expect("expression rule should match");
+        let expr::Expr::Closure(closure) = expr else {
+            panic!("expected a closure");
+        };
+        captures(&closure)
+            .into_iter()
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn capture_free_names() {
+        assert_eq!(
+            capture_names("capture_free_names: simple", "fun(x) => x + y * z + y"),
+            ["y", "z"]
+        );
+        assert_eq!(
+            capture_names(
+                "capture_free_names: shadowed",
+                "fun() { let a = b; let b = a; b }"
+            ),
+            ["b"]
+        );
+        assert_eq!(
+            capture_names(
+                "capture_free_names: match",
+                "fun(x) { match x { (a, b) => a, _ => b } }"
+            ),
+            ["b"]
+        );
+        assert_eq!(
+            capture_names(
+                "capture_free_names: nested",
+                "fun(x) => fun(y) => x + y + z"
+            ),
+            ["z"]
+        );
+        assert!(
+            capture_names("capture_free_names: item", "fun() { fun g(a) { b }; g }").is_empty()
+        );
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use imuc_check::checks::FlowCheck;
+    use imuc_check::Check;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+
+    macro_rules! test_flow {
+        (parse $name: literal, $content: expr) => {{
+            let content = $content;
+            let mut parser = Parser::new(FileReader::new(
+                $name,
+                content,
+                Reader::new(content.chars()),
+            ));
+            rules::ModuleRules
+                .parse(&mut parser)
+                .expect("parsing should not fail")
+                .expect("module rule should always match")
+        }};
+        (ok $name: literal, $content: expr) => {
+            assert!(FlowCheck::new().check(&test_flow!(parse $name, $content)).is_ok());
+        };
+        (error $name: literal, $content: expr) => {
+            assert!(FlowCheck::new().check(&test_flow!(parse $name, $content)).is_err());
+        };
+    }
+
+    #[test]
+    fn check_break_outside() {
+        test_flow!(ok "check_break_outside: inside", "fun f() { loop { break } }");
+        test_flow!(ok "check_break_outside: continue", "fun f() { loop { continue; } }");
+        test_flow!(ok "check_break_outside: return", "fun f() { return 1 }");
+        test_flow!(error "check_break_outside: break", "fun f() { break }");
+        test_flow!(error "check_break_outside: continue", "fun f() { continue }");
+        test_flow!(
+            error "check_break_outside: nested function",
+            "fun f() { loop { fun g() { break } } }"
+        );
+    }
+
+    #[test]
+    fn check_label() {
+        test_flow!(
+            ok "check_label: outer",
+            "fun f() { loop 'outer { loop { break 'outer } } }"
+        );
+        test_flow!(
+            ok "check_label: continue",
+            "fun f() { loop 'outer { loop 'inner { continue 'outer } } }"
+        );
+        test_flow!(
+            error "check_label: unknown",
+            "fun f() { loop 'outer { break 'inner } }"
+        );
+        test_flow!(
+            error "check_label: out of scope",
+            "fun f() { loop 'outer { } loop { break 'outer } }"
+        );
+    }
+
+    #[test]
+    fn check_desugared_loop() {
+        test_flow!(
+            ok "check_desugared_loop: while",
+            "fun f() { while x < 3 { if y { break } continue } }"
+        );
+        test_flow!(
+            ok "check_desugared_loop: for",
+            "fun f() { for 'outer i in 0..=9 { for j in xs { break 'outer } } }"
+        );
+    }
+
+    #[test]
+    fn check_closure() {
+        test_flow!(error "check_closure: break", "fun f() { loop { let g = fun() { break }; break } }");
+        test_flow!(ok "check_closure: inner loop", "fun f() { loop { let g = fun() { loop { break } }; break } }");
+        test_flow!(ok "check_closure: return", "fun f() { let g = fun() { return 1 } }");
+    }
+}
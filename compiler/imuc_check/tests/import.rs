@@ -0,0 +1,333 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ast::*;
+    use imuc_check::checks::ImportCheck;
+    use imuc_check::Check;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+    use std::path::{Path, PathBuf};
+
+    fn parse(name: &str, content: &str) -> module::Module {
+        let mut parser = Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+        rules::ModuleRules
+            .parse(&mut parser)
+            .expect("parsing should not fail")
+            .expect("module rule should always match")
+    }
+
+    /// Creates an import from the file, where the items are listed like `name` or `name as alias`
+    fn import(public: module::Public, file: &str, kind: &str) -> module::Import {
+        import_in(public, file, &[], kind)
+    }
+
+    /// Creates an import from the inline module along the path in the file
+    fn import_in(public: module::Public, file: &str, path: &[&str], kind: &str) -> module::Import {
+        let kind = match kind {
+            "*" => module::ImportKind::Glob,
+            _ if kind.starts_with("as ") => module::ImportKind::Alias(kind[3..].into()),
+            _ => module::ImportKind::Items(
+                kind.split(',')
+                    .map(|item| {
+                        let (name, alias) = match item.trim().split_once(" as ") {
+                            Some((name, alias)) => (name, Some(alias.into())),
+                            None => (item.trim(), None),
+                        };
+                        module::ImportItem {
+                            kind: if name.starts_with(char::is_uppercase) {
+                                module::ImportItemKind::Type(name.into())
+                            } else {
+                                module::ImportItemKind::Value(name.into())
+                            },
+                            alias,
+                        }
+                    })
+                    .collect(),
+            ),
+        };
+        module::Import {
+            public,
+            file: imuc_path::File::new(PathBuf::from(file)),
+            path: path.iter().map(|name| (*name).into()).collect(),
+            kind,
+        }
+    }
+
+    /// The names in scope sorted, with the items they refer to like `file.name`
+    type Names = Vec<(String, String)>;
+
+    /// Parses the files with their imports, resolving the imports of the last one
+    fn check(
+        files: Vec<(&'static str, &str, Vec<module::Import>)>,
+    ) -> (Result<Names, String>, Vec<String>) {
+        let modules: Vec<_> = files
+            .into_iter()
+            .map(|(path, content, imports)| {
+                let mut module = parse(path, content);
+                module.import = imports;
+                (Path::new(path), module)
+            })
+            .collect();
+        let mut checker = ImportCheck::new();
+        for (path, module) in modules.iter() {
+            checker.insert(path, module);
+        }
+        let (path, _) = modules.last().expect("files should not be empty");
+        let result = checker
+            .check(path)
+            .map(|scope| {
+                let mut names: Vec<_> = scope
+                    .iter()
+                    .map(|(name, item)| {
+                        let item = format!("{}.{}", item.file.display(), item.name);
+                        (name.to_string(), item)
+                    })
+                    .collect();
+                names.sort();
+                names
+            })
+            .map_err(|err| err.root_cause().to_string());
+        let warnings = checker
+            .warnings
+            .iter()
+            .map(|warn| warn.root_cause().to_string())
+            .collect();
+        (result, warnings)
+    }
+
+    const SHAPE: &str = "pub cus Point(x: I64) pub fun origin() { 0 } fun secret() { 1 }";
+    const COLOR: &str = "pub cus Color(r: I8) pub fun origin() { 1 }";
+
+    #[test]
+    fn check_glob() {
+        use module::Public::*;
+        let (result, warnings) = check(vec![
+            ("lib/shape", SHAPE, vec![]),
+            ("main", "", vec![import(Priv, "lib/shape", "*")]),
+        ]);
+        assert_eq!(
+            result,
+            Ok(vec![
+                ("Point".to_owned(), "lib/shape.Point".to_owned()),
+                ("origin".to_owned(), "lib/shape.origin".to_owned()),
+            ])
+        );
+        assert!(warnings.is_empty());
+
+        // The explicit import shadows the glob import
+        let (result, warnings) = check(vec![
+            ("lib/shape", SHAPE, vec![]),
+            ("lib/color", COLOR, vec![]),
+            (
+                "main",
+                "fun start() {}",
+                vec![
+                    import(Priv, "lib/shape", "*"),
+                    import(Priv, "lib/color", "origin"),
+                ],
+            ),
+        ]);
+        assert!(result.is_ok_and(
+            |names| names.contains(&("origin".to_owned(), "lib/color.origin".to_owned()))
+        ));
+        assert_eq!(
+            warnings,
+            ["glob import of `origin` is shadowed by an explicit import"]
+        );
+
+        let (result, _) = check(vec![
+            ("lib/shape", SHAPE, vec![]),
+            ("lib/color", COLOR, vec![]),
+            (
+                "main",
+                "",
+                vec![
+                    import(Priv, "lib/shape", "*"),
+                    import(Priv, "lib/color", "*"),
+                ],
+            ),
+        ]);
+        assert_eq!(
+            result,
+            Err("`origin` is imported by more than one glob import".to_owned())
+        );
+    }
+
+    #[test]
+    fn check_explicit() {
+        use module::Public::*;
+        let (result, _) = check(vec![
+            ("lib/shape", SHAPE, vec![]),
+            ("lib/color", COLOR, vec![]),
+            (
+                "main",
+                "",
+                vec![
+                    import(Priv, "lib/shape", "origin"),
+                    import(Priv, "lib/color", "origin"),
+                ],
+            ),
+        ]);
+        assert_eq!(
+            result,
+            Err("`origin` is defined or imported more than once".to_owned())
+        );
+        let (result, _) = check(vec![
+            ("lib/shape", SHAPE, vec![]),
+            (
+                "main",
+                "fun origin() {}",
+                vec![import(Priv, "lib/shape", "origin")],
+            ),
+        ]);
+        assert!(result.is_err());
+        let (result, _) = check(vec![
+            ("lib/shape", SHAPE, vec![]),
+            (
+                "main",
+                "",
+                vec![import(Priv, "lib/shape", "origin as zero, Point")],
+            ),
+        ]);
+        assert_eq!(
+            result,
+            Ok(vec![
+                ("Point".to_owned(), "lib/shape.Point".to_owned()),
+                ("zero".to_owned(), "lib/shape.origin".to_owned()),
+            ])
+        );
+        let (result, _) = check(vec![
+            ("lib/shape", SHAPE, vec![]),
+            ("main", "", vec![import(Priv, "lib/shape", "dog")]),
+        ]);
+        assert_eq!(result, Err("no item `dog` in lib/shape".to_owned()));
+    }
+
+    #[test]
+    fn check_reexport() {
+        use module::Public::*;
+        let (result, _) = check(vec![
+            ("lib/shape", SHAPE, vec![]),
+            (
+                "lib/prelude",
+                "pub fun helper() {}",
+                vec![
+                    import(Pub, "lib/shape", "Point"),
+                    import(Priv, "lib/shape", "origin"),
+                ],
+            ),
+            ("main", "", vec![import(Priv, "lib/prelude", "*")]),
+        ]);
+        assert_eq!(
+            result,
+            Ok(vec![
+                ("Point".to_owned(), "lib/shape.Point".to_owned()),
+                ("helper".to_owned(), "lib/prelude.helper".to_owned()),
+            ])
+        );
+
+        // Re-exports in a loop end
+        let (result, _) = check(vec![
+            ("lib/a", "pub fun a() {}", vec![import(Pub, "lib/b", "*")]),
+            ("lib/b", "pub fun b() {}", vec![import(Pub, "lib/a", "*")]),
+            ("main", "", vec![import(Priv, "lib/a", "b")]),
+        ]);
+        assert_eq!(result, Ok(vec![("b".to_owned(), "lib/b.b".to_owned())]));
+    }
+
+    #[test]
+    fn check_alias() {
+        use module::Public::*;
+        let shape = parse("lib/shape", SHAPE);
+        let mut main = parse("main", "");
+        main.import = vec![import(Priv, "lib/shape", "as shape")];
+        let mut checker = ImportCheck::new();
+        checker.insert(Path::new("lib/shape"), &shape);
+        checker.insert(Path::new("main"), &main);
+        let scope = checker
+            .check(Path::new("main"))
+            .expect("imports should be resolved");
+        assert!(scope.is_alias("shape"));
+        assert!(scope.get("origin").is_none());
+        assert_eq!(
+            scope.get_in("shape", "origin").map(|item| &*item.name),
+            Some("origin")
+        );
+        assert!(scope.get_in("shape", "secret").is_none());
+
+        let (result, _) = check(vec![
+            ("lib/shape", SHAPE, vec![]),
+            (
+                "main",
+                "fun shape() {}",
+                vec![import(Priv, "lib/shape", "as shape")],
+            ),
+        ]);
+        assert!(result.is_err());
+    }
+    #[test]
+    fn check_inline() {
+        use module::Public::*;
+        const HELPER: &str = "
+            pub mod helpers { pub fun twice() {} fun hidden() {} mod inner { pub fun deep() {} } }
+            mod private { pub fun secret() {} }
+        ";
+        let (result, _) = check(vec![
+            ("lib/helper", HELPER, vec![]),
+            (
+                "main",
+                "",
+                vec![import_in(Priv, "lib/helper", &["helpers"], "*")],
+            ),
+        ]);
+        assert_eq!(
+            result,
+            Ok(vec![("twice".to_owned(), "lib/helper.twice".to_owned())])
+        );
+        let (result, _) = check(vec![
+            ("lib/helper", HELPER, vec![]),
+            (
+                "main",
+                "",
+                vec![import_in(Priv, "lib/helper", &["private"], "secret")],
+            ),
+        ]);
+        assert_eq!(
+            result,
+            Err("item `private` of lib/helper is private to where it is imported".to_owned())
+        );
+        let (result, _) = check(vec![
+            ("lib/helper", HELPER, vec![]),
+            (
+                "main",
+                "",
+                vec![import_in(Priv, "lib/helper", &["missing"], "*")],
+            ),
+        ]);
+        assert_eq!(result, Err("no item `missing` in lib/helper".to_owned()));
+
+        // Inline modules are a part of their file
+        let helper = parse("lib/helper", HELPER);
+        let mut checker = ImportCheck::new();
+        let mut main = parse("main", "");
+        main.import = vec![import(Priv, "lib/helper", "helpers")];
+        checker.insert(Path::new("lib/helper"), &helper);
+        checker.insert(Path::new("main"), &main);
+        let scope = checker
+            .check(Path::new("main"))
+            .expect("imports should be resolved");
+        assert!(scope
+            .get("helpers")
+            .is_some_and(|item| item.module.is_empty()));
+        let mut inner = parse("lib/helper", HELPER);
+        inner.import = vec![import_in(Priv, "lib/helper", &["helpers", "inner"], "deep")];
+        let mut checker = ImportCheck::new();
+        checker.insert(Path::new("lib/helper"), &inner);
+        let scope = checker
+            .scope(Path::new("lib/helper"), &[])
+            .expect("imports should be resolved");
+        assert!(scope
+            .get("deep")
+            .is_some_and(|item| item.module.len() == 2 && &*item.name == "deep"));
+    }
+}
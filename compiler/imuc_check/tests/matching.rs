@@ -0,0 +1,274 @@
+#[cfg(test)]
+mod tests {
+    use imuc_check::checks::MatchCheck;
+    use imuc_check::Check;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+
+    /// Checks the content, returning the result and the messages of warnings
+    fn check(name: &str, content: &str) -> (Result<(), String>, Vec<String>) {
+        let mut parser = Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+        let module = rules::ModuleRules
+            .parse(&mut parser)
+            .expect("parsing should not fail")
+            .expect("module rule should always match");
+        let mut checker = MatchCheck::new();
+        let result = checker
+            .check(&module)
+            .map_err(|err| err.root_cause().to_string());
+        let warnings = checker
+            .warnings
+            .iter()
+            .map(|warn| warn.root_cause().to_string())
+            .collect();
+        (result, warnings)
+    }
+
+    macro_rules! test_match {
+        (ok $name: literal, $content: expr) => {{
+            let (result, warnings) = check($name, $content);
+            assert_eq!(result, Ok(()));
+            assert!(warnings.is_empty(), "unexpected warnings: {:?}", warnings);
+        }};
+        (missing $name: literal, $content: expr, $missing: literal) => {{
+            let (result, _) = check($name, $content);
+            assert_eq!(
+                result,
+                Err(format!("non-exhaustive patterns: `{}` not covered", $missing))
+            );
+        }};
+        (unreachable $name: literal, $content: expr, $($arm: literal),*) => {{
+            let (result, warnings) = check($name, $content);
+            assert_eq!(result, Ok(()));
+            let arms: Vec<usize> = warnings
+                .iter()
+                .map(|warn| {
+                    warn.rsplit(' ')
+                        .next()
+                        .and_then(|arm| arm.parse().ok())
+                        .expect("warning should end with the arm number")
+                })
+                .collect();
+            assert_eq!(arms, [$($arm),*]);
+        }};
+    }
+
+    #[test]
+    fn check_exhaustive() {
+        test_match!(
+            ok "check_exhaustive: bool",
+            "fun f() { match x { true => 1, false => 2 } }"
+        );
+        test_match!(
+            ok "check_exhaustive: tuple",
+            "fun f() { match x { (true, _) => 1, (_, true) => 2, (false, false) => 3 } }"
+        );
+        test_match!(
+            ok "check_exhaustive: any",
+            "fun f() { match x { (true | false, 1) => 1, (_, _) => 2 } }"
+        );
+        test_match!(
+            ok "check_exhaustive: struct",
+            "fun f() { match p { Point(x: true) => 1, Point(y: 0) => 2, Point(x: false) => 3 } }"
+        );
+        test_match!(
+            ok "check_exhaustive: nested",
+            "fun f() { match x { y => match y { 1 => 1, _ => 2 } } }"
+        );
+    }
+
+    #[test]
+    fn check_missing() {
+        test_match!(
+            missing "check_missing: bool",
+            "fun f() { match x { true => 1 } }",
+            "false"
+        );
+        test_match!(
+            missing "check_missing: integer",
+            "fun f() { match x { 0 => 1, 1 => 2 } }",
+            "_"
+        );
+        test_match!(
+            missing "check_missing: tuple",
+            "fun f() { match x { (true, _) => 1, (_, true) => 2 } }",
+            "(false, false)"
+        );
+        test_match!(
+            missing "check_missing: struct",
+            "fun f() { match p { Point(x: true, y: 0) => 1, Point(x: false) => 2 } }",
+            "Point(x: true, y: _)"
+        );
+        test_match!(
+            missing "check_missing: guard",
+            "fun f() { match x { y if y > 0 => 1 } }",
+            "_"
+        );
+        test_match!(
+            missing "check_missing: empty",
+            "fun f() { match x { } }",
+            "_"
+        );
+    }
+
+    #[test]
+    fn check_unreachable() {
+        test_match!(
+            unreachable "check_unreachable: after catch-all",
+            "fun f() { match x { _ => 1, 2 => 2 } }",
+            2
+        );
+        test_match!(
+            unreachable "check_unreachable: covered",
+            "fun f() { match x { (true, _) => 1, (false, _) => 2, (_, 3) => 3, _ => 4 } }",
+            3, 4
+        );
+        test_match!(
+            unreachable "check_unreachable: guard does not cover",
+            "fun f() { match x { y if y => 1, true => 2, _ => 3 } }",
+        );
+        test_match!(
+            unreachable "check_unreachable: duplicate literal",
+            "fun f() { match x { \"dog\" => 1, \"dog\" => 2, _ => 3 } }",
+            2
+        );
+    }
+
+    #[test]
+    fn check_sum() {
+        const SHAPE: &str = "cus Shape { Circle(r: F64), Rect(w: F64, h: F64), Empty }\n";
+        test_match!(
+            ok "check_sum: variants",
+            &format!(
+                "{}fun f() {{ match s {{ Shape.Circle(r: _) => 1, Shape.Rect => 2, Shape.Empty => 3 }} }}",
+                SHAPE
+            )
+        );
+        test_match!(
+            ok "check_sum: defined later",
+            "fun f() { match s { Opt.Some(x: true) => 1, Opt.Some(x: false) | Opt.None => 2 } }
+            cus Opt { Some(x: Bool), None }"
+        );
+        test_match!(
+            ok "check_sum: built-in",
+            "fun f(o: Option[Bool]) { match o { Option.Some(value: true) => 1, Option.Some(value: false) | Option.None => 2 } }"
+        );
+        test_match!(
+            missing "check_sum: built-in variant",
+            "fun f(r: Result[I64, Str]) { match r { Result.Ok(value: _) => 1 } }",
+            "Result.Err"
+        );
+        test_match!(
+            missing "check_sum: variant",
+            &format!(
+                "{}fun f() {{ match s {{ Shape.Circle(r: _) => 1, Shape.Empty => 3 }} }}",
+                SHAPE
+            ),
+            "Shape.Rect"
+        );
+        test_match!(
+            missing "check_sum: payload",
+            "cus Opt { Some(x: Bool), None }
+            fun f() { match s { Opt.Some(x: true) => 1, Opt.None => 2 } }",
+            "Opt.Some(x: false)"
+        );
+        test_match!(
+            missing "check_sum: unknown sum type",
+            "fun f() { match s { Opt.Some(x: _) => 1, Opt.None => 2 } }",
+            "_"
+        );
+        test_match!(
+            unreachable "check_sum: covered",
+            &format!(
+                "{}fun f() {{ match s {{ Shape.Circle => 1, Shape.Circle(r: 1.0) => 2, _ => 3 }} }}",
+                SHAPE
+            ),
+            2
+        );
+        let (result, _) = check(
+            "check_sum: no such variant",
+            &format!(
+                "{}fun f() {{ match s {{ Shape.Square => 1, _ => 2 }} }}",
+                SHAPE
+            ),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn check_refutable() {
+        test_match!(
+            ok "check_refutable: destructure",
+            "cus Point(x: I64, y: I64) cus One { Only(x: I64) }
+            fun f((a, Point(x, y: _)), One.Only(x: b)) { let (c, (d, _)) = (a, (x, b)); c }"
+        );
+        test_match!(
+            ok "check_refutable: alternatives",
+            "fun f() { let (x, _) | (_, x) = (1, 2); x }"
+        );
+        for (name, content, context, witness) in [
+            (
+                "check_refutable: literal",
+                "fun f() { let (x, 0) = y; x }",
+                "`let` binding",
+                "(_, _)",
+            ),
+            (
+                "check_refutable: alternatives",
+                "fun f() { let (true, x) | (x, true) = y; x }",
+                "`let` binding",
+                "(false, false)",
+            ),
+            (
+                "check_refutable: variant",
+                "cus Opt { Some(x: I64), None } fun f(Opt.Some(x)) { x }",
+                "arguments",
+                "(Opt.None,)",
+            ),
+            (
+                "check_refutable: closure",
+                "fun f() { fun(true) => 1 }",
+                "closure arguments",
+                "(false,)",
+            ),
+        ] {
+            let (result, _) = check(name, content);
+            assert_eq!(
+                result,
+                Err(format!(
+                    "refutable pattern in {}: `{}` not covered",
+                    context, witness
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn check_mismatch() {
+        let (result, _) = check(
+            "check_mismatch: bool and integer",
+            "fun f() { match x { true => 1, 2 => 2, _ => 3 } }",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn check_context() {
+        let content = "fun f() { match x { _ => 1, _ => 2 } }";
+        let mut parser = Parser::new(FileReader::new(
+            "check_context",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let module = rules::ModuleRules.parse(&mut parser).unwrap().unwrap();
+        let mut checker = MatchCheck::new();
+        checker.check(&module).unwrap();
+        let warn = checker
+            .warnings
+            .iter()
+            .next()
+            .expect("a warning should be pushed");
+        assert_eq!(warn.to_string(), "in function `f`");
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use imuc_check::checks::MutCheck;
+    use imuc_check::Check;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+
+    /// Checks the content, returning the message of the root cause of the error
+    fn check(name: &str, content: &str) -> Result<(), String> {
+        let mut parser = Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+        let module = rules::ModuleRules
+            .parse(&mut parser)
+            .expect("parsing should not fail")
+            .expect("module rule should always match");
+        MutCheck::new()
+            .check(&module)
+            .map_err(|err| err.root_cause().to_string())
+    }
+
+    #[test]
+    fn check_mutable() {
+        assert!(check("check_mutable: let", "fun f() { let mut x = 1; x = 2 }").is_ok());
+        assert!(check(
+            "check_mutable: compound",
+            "fun f() { let mut x = (1, 2); x.a += 2; x[0] -= 1 }"
+        )
+        .is_ok());
+        assert!(check("check_mutable: argument", "fun f(mut x: I64) { x *= 2 }").is_ok());
+        assert!(check(
+            "check_mutable: match arm",
+            "fun f() { match y { mut x => { x = 1 } } }"
+        )
+        .is_ok());
+        assert!(check(
+            "check_mutable: desugared counter",
+            "fun f() { for i in 0..=10 { } }"
+        )
+        .is_ok());
+        assert!(check("check_mutable: unknown name", "fun f() { x = 1 }").is_ok());
+    }
+
+    #[test]
+    fn check_immutable() {
+        assert_eq!(
+            check(
+                "check_immutable: let",
+                "fun f() {\n    let x = 1;\n    x = 2\n}"
+            ),
+            Err("cannot assign to immutable binding `x` declared at line 2 column 9".to_owned())
+        );
+        assert!(check(
+            "check_immutable: field",
+            "fun f() { let x = (1, 2); x.a = 3 }"
+        )
+        .is_err());
+        assert!(check("check_immutable: argument", "fun f(x: I64) { x += 1 }").is_err());
+        assert!(check(
+            "check_immutable: shadowed",
+            "fun f() { let mut x = 1; let x = x; x = 2 }"
+        )
+        .is_err());
+        assert!(check(
+            "check_immutable: loop variable",
+            "fun f() { for i in 0..10 { i = 1 } }"
+        )
+        .is_err());
+        assert!(check(
+            "check_immutable: scope ends",
+            "fun f(x: I64) { { let mut x = 1; } x = 2 }"
+        )
+        .is_err());
+        assert!(check("check_immutable: not a place", "fun f() { 1 = 2 }").is_err());
+    }
+}
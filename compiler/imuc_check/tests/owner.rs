@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use imuc_check::checks::OwnCheck;
+    use imuc_check::Check;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+
+    /// Checks the content, returning the message of the root cause of the error
+    fn check(name: &str, content: &str) -> Result<(), String> {
+        let mut parser = Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+        let module = rules::ModuleRules
+            .parse(&mut parser)
+            .expect("parsing should not fail")
+            .expect("module rule should always match");
+        OwnCheck::new()
+            .check(&module)
+            .map_err(|err| err.root_cause().to_string())
+    }
+
+    #[test]
+    fn check_move() {
+        assert!(check(
+            "check_move: copy",
+            "fun f() { let x = 1; let y = x; let z = x }"
+        )
+        .is_ok());
+        assert!(check(
+            "check_move: read in place",
+            "fun f() { let x = P(a: 1); let y = x.a + x.a; let z = x }"
+        )
+        .is_ok());
+        assert!(check(
+            "check_move: assigned again",
+            "fun f() { let mut x = P(a: 1); let y = x; x = P(a: 2); let z = x }"
+        )
+        .is_ok());
+        assert_eq!(
+            check(
+                "check_move: use after move",
+                "fun f() {\n    let x = P(a: 1);\n    let y = x;\n    let z = x\n}"
+            ),
+            Err(
+                "use of moved value `x`: moved at line 3 column 13, used again at line 4 column 13"
+                    .to_owned()
+            )
+        );
+        assert!(check(
+            "check_move: annotated",
+            "fun f(x: P) { let y = x; let z = x }"
+        )
+        .is_err());
+        assert!(check(
+            "check_move: annotated shared",
+            "fun f(x: @P) { let y = x; let z = x }"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn check_move_flow() {
+        assert!(check(
+            "check_move_flow: branch",
+            "fun f() { let x = P(a: 1); if 1 { let y = x } else { }; let z = x }"
+        )
+        .is_err());
+        assert!(check(
+            "check_move_flow: both branches",
+            "fun f() { let x = P(a: 1); if 1 { let y = x } else { let z = x } }"
+        )
+        .is_ok());
+        assert!(check(
+            "check_move_flow: loop",
+            "fun f() { let x = P(a: 1); loop { let y = x } }"
+        )
+        .is_err());
+        assert!(check(
+            "check_move_flow: loop break",
+            "fun f() { let x = P(a: 1); loop { let y = x; break } }"
+        )
+        .is_ok());
+        assert!(check(
+            "check_move_flow: return",
+            "fun f() { let x = P(a: 1); if 1 { let y = x; return } else { }; let z = x }"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn check_closure() {
+        assert!(check(
+            "check_closure: copy",
+            "fun f() { let x = 1; let g = fun() => x; let y = x; g() }"
+        )
+        .is_ok());
+        assert_eq!(
+            check(
+                "check_closure: capture",
+                "fun f() {\n    let x = P(a: 1);\n    let g = fun() => x;\n    x\n}"
+            ),
+            Err(
+                "use of moved value `x`: moved at line 3 column 22, used again at line 4 column 5"
+                    .to_owned()
+            )
+        );
+        assert!(check(
+            "check_closure: argument",
+            "fun f() { let g = fun(x: P) { let y = x; x }; g }"
+        )
+        .is_err());
+        assert!(check(
+            "check_closure: call",
+            "fun f() { let g = fun(x) => x; g(1); g(2) }"
+        )
+        .is_ok());
+        assert!(check(
+            "check_closure: outlive",
+            "fun f() { let mut g = fun() => 0; { let x = 1; let r = @x; g = fun() => r } }"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn check_borrow() {
+        assert!(check(
+            "check_borrow: shared",
+            "fun f() { let x = P(a: 1); let y = @x; let z = @x }"
+        )
+        .is_ok());
+        assert_eq!(
+            check(
+                "check_borrow: outlive",
+                "fun f() {\n    let mut y = @0;\n    {\n        let x = 1;\n        y = @x\n    }\n}"
+            ),
+            Err("borrowed value `x` does not live long enough: borrowed at line 5 column 14"
+                .to_owned())
+        );
+        assert!(check(
+            "check_borrow: move borrowed",
+            "fun f() { let x = P(a: 1); let y = @x; let z = x }"
+        )
+        .is_err());
+        assert!(check("check_borrow: escape", "fun f() { let x = 1; @x }").is_err());
+        assert!(check(
+            "check_borrow: block result",
+            "fun f() { let y = { let x = 1; @x } }"
+        )
+        .is_err());
+    }
+}
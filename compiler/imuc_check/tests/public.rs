@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ast::*;
+    use imuc_check::checks::{PubCheck, TypeCheck};
+    use imuc_check::{hir, Check};
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+    use std::path::{Path, PathBuf};
+
+    const SHAPE: &str = "
+        pub cus Point(pub x: I64, pub(mod) y: I64, z: I64)
+        pub(mod) cus Hidden(pub a: I64)
+        pub cus Line(pub start: Point, pub end: Point)
+        pub fun origin() { 0 }
+        pub fun make() { Point(x: 1, y: 2, z: 3) }
+        fun secret() { 1 }
+        pub mod inner { pub cus Secret(pub a: I64, b: I64) }
+    ";
+
+    fn parse(name: &str, content: &str) -> module::Module {
+        let mut parser = Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+        rules::ModuleRules
+            .parse(&mut parser)
+            .expect("parsing should not fail")
+            .expect("module rule should always match")
+    }
+
+    /// Imports the items from the shape file, or from its inline modules on the path, into the
+    /// module
+    fn import(module: &mut module::Module, path: &[&str], items: &[&str]) {
+        let items = items
+            .iter()
+            .map(|name| module::ImportItem {
+                kind: if name.starts_with(char::is_uppercase) {
+                    module::ImportItemKind::Type((*name).into())
+                } else {
+                    module::ImportItemKind::Value((*name).into())
+                },
+                alias: None,
+            })
+            .collect();
+        module.import.push(module::Import {
+            public: module::Public::Priv,
+            file: imuc_path::File::new(PathBuf::from("lib/geo/shape")),
+            path: path.iter().map(|name| (*name).into()).collect(),
+            kind: module::ImportKind::Items(items),
+        });
+    }
+
+    /// Checks the content as the file at the path, which imports the items from the shape file
+    fn check(path: &str, items: &[&str], content: &str) -> Result<(), String> {
+        check_from(path, &[], items, content)
+    }
+
+    /// Checks the imports of the file at the path, then type checks it with the items imported
+    /// from the inline modules of the shape file on the module path
+    fn check_from(path: &str, from: &[&str], items: &[&str], content: &str) -> Result<(), String> {
+        let shape = parse("shape", SHAPE);
+        let mut module = parse(path, content);
+        import(&mut module, from, items);
+        let mut checker = PubCheck::new();
+        checker.insert(Path::new("lib/geo/shape"), &shape);
+        checker.insert(Path::new(path), &module);
+        checker
+            .check(Path::new(path))
+            .map_err(|err| err.root_cause().to_string())?;
+
+        let shape = TypeCheck::new()
+            .check(&shape)
+            .expect("type check of the shape file should not fail");
+        let mut owner = &shape;
+        for name in from {
+            owner = owner
+                .items
+                .iter()
+                .find_map(|item| match &item.kind {
+                    hir::ItemKind::Mod(inner) if &*item.name == *name => Some(inner),
+                    _ => None,
+                })
+                .expect("inline module should exist");
+        }
+        let hidden = checker.hidden();
+        let mut types = TypeCheck::new();
+        for name in items {
+            if let Some(item) = owner.items.iter().find(|item| &*item.name == *name) {
+                let hidden = hidden.get(*name).cloned().unwrap_or_default();
+                types.import((*name).into(), item, hidden);
+            }
+        }
+        types
+            .check(&module)
+            .map(|_| ())
+            .map_err(|err| err.root_cause().to_string())
+    }
+
+    #[test]
+    fn check_import() {
+        assert!(check("main", &["Point", "origin"], "").is_ok());
+        assert!(check("lib/geo/area", &["Hidden"], "").is_ok());
+        assert!(check("lib/geo/plane/area", &["Hidden"], "").is_ok());
+        assert!(check("main", &["Hidden"], "").is_err());
+        assert_eq!(
+            check("lib/geo/area", &["secret"], ""),
+            Err("item `secret` of lib/geo/shape is private to where it is imported".to_owned())
+        );
+    }
+
+    #[test]
+    fn check_field() {
+        assert!(check("main", &["Point"], "fun f(p: Point) { p.x }").is_ok());
+        assert!(check("main", &["Point"], "fun f(p: Point) { p.y }").is_err());
+        assert!(check("lib/geo/area", &["Point"], "fun f(p: Point) { p.y }").is_ok());
+        assert_eq!(
+            check("lib/geo/area", &["Point"], "fun f(p: Point) { p.z }"),
+            Err("field `z` of type `Point` is private to where it is used".to_owned())
+        );
+        assert!(check(
+            "main",
+            &["Point", "make"],
+            "fun f() { let p = make(); p.z }"
+        )
+        .is_err());
+        assert_eq!(
+            check("main", &["Point", "make"], "fun f() { make().z }"),
+            Err("field `z` of type `Point` is private to where it is used".to_owned())
+        );
+        assert!(check("main", &["Point", "Line"], "fun f(l: Line) { l.start.z }").is_err());
+        assert_eq!(
+            check("main", &["Point"], "fun f(p: Point) { Point(x: 1, ..p) }"),
+            Err("field `y` of type `Point` is private to where it is used".to_owned())
+        );
+        assert!(check(
+            "lib/geo/area",
+            &["Point"],
+            "fun f(p: Point) { Point(x: 1, y: 2, z: 3, ..p) }"
+        )
+        .is_err());
+        assert!(check("main", &["Point"], "fun f() { Point(x: 1, z: 2) }").is_err());
+        assert!(check(
+            "main",
+            &["Point"],
+            "fun f(p: Point) { match p { Point(z: 0) => 1, _ => 2 } }"
+        )
+        .is_err());
+        assert!(check(
+            "main",
+            &["Point", "Line"],
+            "fun f(p: Point, l: Line) { let p = l; p.start }"
+        )
+        .is_ok());
+
+        // Types of inline modules are checked like those of the file
+        assert!(check_from("main", &["inner"], &["Secret"], "fun f(s: Secret) { s.a }").is_ok());
+        assert_eq!(
+            check_from("main", &["inner"], &["Secret"], "fun f(s: Secret) { s.b }"),
+            Err("field `b` of type `Secret` is private to where it is used".to_owned())
+        );
+    }
+}
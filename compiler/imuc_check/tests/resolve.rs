@@ -0,0 +1,237 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ast::*;
+    use imuc_check::checks::{DefKind, ResolveCheck};
+    use imuc_check::Check;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+    use std::path::PathBuf;
+
+    fn parse(name: &str, content: &str) -> module::Module {
+        let mut parser = Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+        rules::ModuleRules
+            .parse(&mut parser)
+            .expect("parsing should not fail")
+            .expect("module rule should always match")
+    }
+
+    /// Creates an import of the values listed from the file, or of every item like `*`
+    fn import(file: &str, kind: &str) -> module::Import {
+        let kind = match kind {
+            "*" => module::ImportKind::Glob,
+            _ => module::ImportKind::Items(
+                kind.split(',')
+                    .map(|name| module::ImportItem {
+                        kind: module::ImportItemKind::Value(name.trim().into()),
+                        alias: None,
+                    })
+                    .collect(),
+            ),
+        };
+        module::Import {
+            public: module::Public::Priv,
+            file: imuc_path::File::new(PathBuf::from(file)),
+            path: Vec::new(),
+            kind,
+        }
+    }
+
+    /// Resolves the content with the imports, returning the message of the root cause of the
+    /// error and the warnings
+    fn check(
+        name: &str,
+        content: &str,
+        imports: Vec<module::Import>,
+    ) -> (Result<(), String>, Vec<String>) {
+        let mut module = parse(name, content);
+        module.import = imports;
+        let mut checker = ResolveCheck::new();
+        let result = checker
+            .check(&module)
+            .map(|_| ())
+            .map_err(|err| err.root_cause().to_string());
+        let warnings = checker
+            .warnings
+            .iter()
+            .map(|warn| warn.root_cause().to_string())
+            .collect();
+        (result, warnings)
+    }
+
+    #[test]
+    fn resolve_scopes() {
+        let (result, warnings) = check(
+            "resolve_scopes: args and lets",
+            "fun f(a: I64) { let b = a; let g = fun(x) { x + b }; g(1) }",
+            vec![],
+        );
+        assert_eq!(result, Ok(()));
+        assert!(warnings.is_empty());
+
+        // Items are visible before they are defined, and from inline modules
+        let (result, _) = check(
+            "resolve_scopes: items",
+            "fun f() { g(); helpers.twice() } fun g() { fun h() {} h() } mod helpers { fun twice() { g() } }",
+            vec![],
+        );
+        assert_eq!(result, Ok(()));
+
+        let (result, _) = check(
+            "resolve_scopes: match",
+            "cus Shape { Circle(r: F64), Empty } fun f(s: Shape) { match s { Shape.Circle(r: r) => r, other => 0.0 } }",
+            vec![],
+        );
+        assert_eq!(result, Ok(()));
+
+        // Template parameters are types within their functions, and receivers are never unused
+        let (result, warnings) = check(
+            "resolve_scopes: interfaces",
+            "interface Show { fun show(self) => Str } cus Point(x: I64) for Point: Show { fun show(self) { \"point\" } } fun describe[T: Show](v: T) { v.show() }",
+            vec![],
+        );
+        assert_eq!(result, Ok(()));
+        assert!(warnings.is_empty());
+
+        // The built-in sum types need no definition, and are shadowed by items
+        let (result, warnings) = check(
+            "resolve_scopes: built-in",
+            "fun f(o: Option[I64]) { Result.Ok(value: o?) } mod inner { cus Option(x: I64) fun g(o: Option) { o } }",
+            vec![],
+        );
+        assert_eq!(result, Ok(()));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn resolve_undefined() {
+        let (result, _) = check("resolve_undefined: value", "fun f() { x }", vec![]);
+        assert_eq!(result, Err("cannot find `x` in this scope".to_owned()));
+        let (result, _) = check("resolve_undefined: type", "fun f(a: Shape) { a }", vec![]);
+        assert_eq!(
+            result,
+            Err("cannot find type `Shape` in this scope".to_owned())
+        );
+        let (result, _) = check(
+            "resolve_undefined: default",
+            "cus Config(size: I64 = base(), name: Str = missing) fun base() { 8 }",
+            vec![],
+        );
+        assert_eq!(
+            result,
+            Err("cannot find `missing` in this scope".to_owned())
+        );
+        let (result, _) = check(
+            "resolve_undefined: out of scope",
+            "fun f() { { let a = 1; a }; a }",
+            vec![],
+        );
+        assert!(result.is_err());
+        let (result, _) = check(
+            "resolve_undefined: outer local",
+            "fun f() { let a = 1; fun g() { a } g() }",
+            vec![],
+        );
+        assert_eq!(result, Err("cannot find `a` in this scope".to_owned()));
+        let (result, _) = check(
+            "resolve_undefined: bound",
+            "fun f[T: Show](v: T) { v }",
+            vec![],
+        );
+        assert_eq!(
+            result,
+            Err("cannot find type `Show` in this scope".to_owned())
+        );
+        let (result, _) = check("resolve_undefined: self", "fun f() { self }", vec![]);
+        assert_eq!(result, Err("cannot find `self` in this scope".to_owned()));
+        let (result, _) = check(
+            "resolve_undefined: module item",
+            "fun f() { helpers.thrice() } mod helpers { fun twice() {} }",
+            vec![],
+        );
+        assert_eq!(
+            result,
+            Err("cannot find `helpers.thrice` in this scope".to_owned())
+        );
+        let (result, _) = check(
+            "resolve_undefined: or-pattern",
+            "fun f(x: I64) { match x { a | b => b } }",
+            vec![],
+        );
+        assert_eq!(
+            result,
+            Err("variable `a` is not bound in every alternative of the pattern".to_owned())
+        );
+
+        // Names may come from the glob import, unknown without its scope
+        let (result, _) = check(
+            "resolve_undefined: glob",
+            "fun f() { origin() }",
+            vec![import("lib/shape", "*")],
+        );
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn resolve_warnings() {
+        let (result, warnings) = check(
+            "resolve_warnings: unused",
+            "fun f(a: I64, _b: I64) { let c = 1; origin() }",
+            vec![import("lib/shape", "origin, secret")],
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            warnings,
+            [
+                "variable `c` is never used",
+                "variable `a` is never used",
+                "import `secret` is never used",
+            ]
+        );
+
+        let (_, warnings) = check(
+            "resolve_warnings: shadowed",
+            "fun f(a: I64) { let a = a + 1; a }",
+            vec![],
+        );
+        assert_eq!(warnings, ["`a` shadows a name already in scope"]);
+
+        // Names created by desugaring are never reported
+        let (result, warnings) = check(
+            "resolve_warnings: desugared",
+            "fun f(a: [I64]) { for x in a { for y in a { x + y; } } }",
+            vec![],
+        );
+        assert_eq!(result, Ok(()));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn resolve_uses() {
+        let module = parse("resolve_uses", "fun f(a: I64) { let b = a; b + f(b) }");
+        let resolution = ResolveCheck::new()
+            .check(&module)
+            .expect("resolving should not fail");
+        let mut defs: Vec<_> = resolution
+            .uses
+            .keys()
+            .map(|loc| {
+                let def = resolution.get(*loc).expect("use should be resolved");
+                match def.kind {
+                    DefKind::Local(bound) => format!("{} at {loc} to {bound}", def.name),
+                    _ => format!("{} at {loc} to item", def.name),
+                }
+            })
+            .collect();
+        defs.sort();
+        assert_eq!(
+            defs,
+            [
+                "a at line 1 column 25 to line 1 column 7",
+                "b at line 1 column 28 to line 1 column 21",
+                "b at line 1 column 34 to line 1 column 21",
+                "f at line 1 column 32 to item",
+            ]
+        );
+    }
+}
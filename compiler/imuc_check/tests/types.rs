@@ -0,0 +1,888 @@
+#[cfg(test)]
+mod tests {
+    use imuc_check::checks::TypeCheck;
+    use imuc_check::{hir, Check};
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+
+    /// Checks the content, returning the message of the root cause of the error
+    fn check(name: &str, content: &str) -> Result<hir::Module, String> {
+        let mut parser = Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+        let module = rules::ModuleRules
+            .parse(&mut parser)
+            .expect("parsing should not fail")
+            .expect("module rule should always match");
+        TypeCheck::new()
+            .check(&module)
+            .map_err(|err| err.root_cause().to_string())
+    }
+
+    /// Returns the types of the functions of the module in order
+    fn fun_types(module: &hir::Module) -> Vec<String> {
+        module
+            .items
+            .iter()
+            .filter_map(|item| match &item.kind {
+                hir::ItemKind::Fun(fun) => Some(fun.ty.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn check_types() {
+        let module = check(
+            "check_types: functions",
+            "fun f(a: I64) { g(a) } fun g(b: I64) { let c: I64 = b; (c, 1.0); c * 2 } fun h() { f(1) < 2 }",
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec![
+                "fun(I64) => I64".to_owned(),
+                "fun(I64) => I64".to_owned(),
+                "fun() => Bool".to_owned(),
+            ])
+        );
+
+        let module = check(
+            "check_types: custom",
+            "cus Point(x: I64, y: F32) fun f(p: Point) { let q = Point(x: p.x, y: 1.0); [q.y, p.y] }",
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec!["fun(Point) => [F32; 2]".to_owned()])
+        );
+
+        let module = check(
+            "check_types: flows",
+            "fun f(a: Bool) { if a { return 1 } loop { break 2 } }",
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec!["fun(Bool) => I64".to_owned()])
+        );
+
+        let module = check(
+            "check_types: modules",
+            "mod inner { pub fun g() { 1 } pub mod deep { pub fun h(a: Str) { a } } } \
+             fun f() { (inner.g(), inner.deep.h(\"a\")) }",
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec!["fun() => (I64, Str)".to_owned()])
+        );
+        assert_eq!(
+            check(
+                "check_types: module mismatch",
+                "mod inner { pub fun g() { 1 } } fun f() { let x: Bool = inner.g(); x }"
+            )
+            .err(),
+            Some("mismatched types: expected `Bool`, found `I64`".to_owned())
+        );
+    }
+
+    #[test]
+    fn check_mismatch() {
+        assert_eq!(
+            check("check_mismatch: let", "fun f() { let a: I64 = 1.0; a }").err(),
+            Some("mismatched types: expected `I64`, found `{float}`".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_mismatch: branches",
+                "fun f(a: Bool) { if a { 1 } else { \"one\" } }"
+            )
+            .err(),
+            Some("mismatched types: expected `{integer}`, found `Str`".to_owned())
+        );
+        assert!(check("check_mismatch: cond", "fun f() { if 1 { 2 } else { 3 } }").is_err());
+        assert!(check("check_mismatch: operands", "fun f(a: I32) { a + 1.0 }").is_err());
+        assert_eq!(
+            check(
+                "check_mismatch: argument",
+                "fun f(a: I64) { a } fun g() { f(true) }"
+            )
+            .err(),
+            Some("mismatched types: expected `I64`, found `Bool`".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_mismatch: count",
+                "fun f(a: I64) { a } fun g() { f(1, 2) }"
+            )
+            .err(),
+            Some("expected 1 arguments, found 2".to_owned())
+        );
+        assert!(check(
+            "check_mismatch: field",
+            "cus Point(x: I64) fun f() { Point(x: true) }"
+        )
+        .is_err());
+        assert_eq!(
+            check(
+                "check_mismatch: no field",
+                "cus Point(x: I64) fun f(p: Point) { p.z }"
+            )
+            .err(),
+            Some("no field `z` on type `Point`".to_owned())
+        );
+        assert_eq!(
+            check("check_mismatch: call", "fun f() { let a = 1; a() }").err(),
+            Some("`{integer}` is not a function".to_owned())
+        );
+        assert!(check(
+            "check_mismatch: return",
+            "fun f(a: Bool) { if a { return 1.0 }; 2 }"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn check_closure() {
+        let module = check(
+            "check_closure",
+            "fun f(a: I64, b: F32) { let c = fun(x: I64) => x + a; c(1) + a }",
+        )
+        .expect("type check should not fail");
+        let hir::ItemKind::Fun(fun) = &module.items[0].kind else {
+            panic!("expected a function");
+        };
+        let Some(hir::Bind::Let(pat, val)) = fun.body.bind.first() else {
+            panic!("expected a binding");
+        };
+        assert_eq!(pat.ty.to_string(), "fun(I64) => I64");
+        let hir::ExprKind::Closure(closure) = &val.kind else {
+            panic!("expected a closure");
+        };
+        let captures: Vec<_> = closure
+            .captures
+            .iter()
+            .map(|(name, ty)| format!("{}: {}", name, ty))
+            .collect();
+        assert_eq!(captures, ["a: I64"]);
+    }
+
+    #[test]
+    fn check_wide() {
+        let module = check(
+            "check_wide: literals",
+            "fun f(h: U128) { let m = 0xffffffffffffffffffffffffffffffff; let n: I128 = -9223372036854775809; (h * m, n) }",
+        )
+        .expect("type check should not fail");
+        assert_eq!(fun_types(&module), ["fun(U128) => (U128, I128)"]);
+        assert_eq!(
+            check(
+                "check_wide: range",
+                "fun f() { let a: I128 = 0xffffffffffffffffffffffffffffffff; a }"
+            )
+            .err(),
+            Some(
+                "literal `340282366920938463463374607431768211455` is out of range for `I128`"
+                    .to_owned()
+            )
+        );
+        let module = check(
+            "check_wide: default",
+            "fun f() { 9223372036854775808 } fun g() { let a = 1; a + 0xffffffffffffffffffffffffffffffff } \
+            fun h() { (-9223372036854775809, 1) }",
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec![
+                "fun() => I128".to_owned(),
+                "fun() => U128".to_owned(),
+                "fun() => (I128, I64)".to_owned(),
+            ])
+        );
+    }
+
+    #[test]
+    fn check_try() {
+        let module = check(
+            "check_try: propagate",
+            "fun f(x: Option[I64]) { Option.Some(value: x? + 1) } \
+            fun g(r: Result[Bool, Str]) { let b = r?; Result.Ok(value: (b, 1.0)) }",
+        )
+        .expect("type check should not fail");
+        assert_eq!(
+            fun_types(&module),
+            [
+                "fun(Option[I64]) => Option[I64]",
+                "fun(Result[Bool, Str]) => Result[(Bool, F32), Str]"
+            ]
+        );
+        assert_eq!(
+            check("check_try: operand", "fun f(x: I64) { x? }").err(),
+            Some("the `?` operator takes an `Option` or a `Result`, found `I64`".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_try: return",
+                "fun f(x: Option[I64], r: Result[I64, Str]) { let y = r?; x? + y }"
+            )
+            .err(),
+            Some(
+                "the `?` operator returns `Option[_]` from a function returning `Result[_, Str]`"
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn check_try_call() {
+        let module = check(
+            "check_try_call: option",
+            "fun g(x: I64) { if x < 0 { Option.None } else { Option.Some(value: x) } } \
+            fun f(x: I64) { let y = g(x)?; Option.Some(value: y) }",
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec![
+                "fun(I64) => Option[I64]".to_owned(),
+                "fun(I64) => Option[I64]".to_owned(),
+            ])
+        );
+        let module = check(
+            "check_try_call: result",
+            "fun f(x: I64) { let y = g(x)?; Result.Ok(value: y < 2) } \
+            fun g(x: I64) { if x < 0 { Result.Err(error: \"dog\") } else { Result.Ok(value: x) } }",
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec![
+                "fun(I64) => Result[Bool, Str]".to_owned(),
+                "fun(I64) => Result[I64, Str]".to_owned(),
+            ])
+        );
+        let module = check(
+            "check_try_call: match",
+            "fun f() { match g() { Option.Some(value: v) => v, Option.None => 0 } } \
+            fun g() { Option.Some(value: 1) }",
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec![
+                "fun() => I64".to_owned(),
+                "fun() => Option[I64]".to_owned(),
+            ])
+        );
+        assert_eq!(
+            check("check_try_call: operand", "fun f() { g()? } fun g() { 1 }").err(),
+            Some("the `?` operator takes an `Option` or a `Result`, found `I64`".to_owned())
+        );
+    }
+
+    #[test]
+    fn check_struct_update() {
+        const CONFIG: &str =
+            "cus Config(size: I32 = 4 * 2, name: Str, on = ready()) fun ready() { true } ";
+        let module = check(
+            "check_struct_update: defaults and base",
+            &format!(
+                "{CONFIG}fun f() {{ Config(name: \"a\") }} \
+                fun g(c: Config) {{ let d = Config(size: 1, ..c); (d.size, d.on) }}"
+            ),
+        )
+        .expect("type check should not fail");
+        assert_eq!(
+            fun_types(&module),
+            [
+                "fun() => Bool",
+                "fun() => Config",
+                "fun(Config) => (I32, Bool)"
+            ]
+        );
+        let Some(hir::ItemKind::Cus(hir::Cus::Struct(elems))) =
+            module.items.first().map(|item| &item.kind)
+        else {
+            panic!("expected a struct");
+        };
+        let elems: Vec<_> = elems.iter().map(|(_, ty)| ty.to_string()).collect();
+        assert_eq!(elems, ["I32", "Str", "Bool"]);
+
+        // The base is bound after the elements given, and takes the elements left out
+        let Some(hir::ItemKind::Fun(fun)) = module.items.last().map(|item| &item.kind) else {
+            panic!("expected a function");
+        };
+        let Some(hir::Bind::Let(_, val)) = fun.body.bind.first() else {
+            panic!("expected a binding");
+        };
+        let hir::ExprKind::Body(body) = &val.kind else {
+            panic!("expected the elements to be bound");
+        };
+        let names: Vec<_> = body
+            .bind
+            .iter()
+            .filter_map(|bind| match bind {
+                hir::Bind::Let(
+                    hir::Pat {
+                        kind: hir::PatKind::Ident(name, _),
+                        ..
+                    },
+                    _,
+                ) => name.as_deref().map(str::to_owned),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(names, ["#size", "#base"]);
+        let Some(hir::ExprKind::Struct(None, elem)) = body.body.first().map(|val| &val.kind) else {
+            panic!("expected a struct");
+        };
+        assert!(matches!(elem["name"].kind, hir::ExprKind::Field(..)));
+
+        for (name, content, err) in [
+            (
+                "check_struct_update: missing",
+                "fun f() { Config(size: 1) }",
+                "missing field `name` in `Config`, which has no default value",
+            ),
+            (
+                "check_struct_update: unknown",
+                "fun f() { Config(name: \"a\", colour: 1) }",
+                "no field `colour` on type `Config`",
+            ),
+            (
+                "check_struct_update: variant",
+                "cus Shape { Circle(r: F64), Empty } fun f(s: Shape) { Shape.Circle(..s) }",
+                "the variant `Shape.Circle` cannot take the elements left out from a base",
+            ),
+            (
+                "check_struct_update: base",
+                "fun f() { Config(name: \"a\", ..1.0) }",
+                "mismatched types: expected `Config`, found `{float}`",
+            ),
+            (
+                "check_struct_update: default",
+                "cus Sized(size: I32 = \"big\")",
+                "mismatched types: expected `I32`, found `Str`",
+            ),
+        ] {
+            assert_eq!(
+                check(name, &format!("{CONFIG}{content}")).err(),
+                Some(err.to_owned())
+            );
+        }
+    }
+
+    #[test]
+    fn check_destructure() {
+        const POINT: &str = "cus Point(x: F32, y: F32) ";
+        let module = check(
+            "check_destructure: nested",
+            &format!(
+                "{POINT}fun f((a: Bool, Point(x, y: b)), c: I64) {{ \
+                let (d, (e, _)) = (x, (c, a)); (d, b, e) }} fun g(p: Point) {{ f((true, p), 1) }}"
+            ),
+        )
+        .expect("type check should not fail");
+        assert_eq!(
+            fun_types(&module),
+            [
+                "fun((Bool, Point), I64) => (F32, F32, I64)",
+                "fun(Point) => (F32, F32, I64)"
+            ]
+        );
+
+        assert_eq!(
+            check(
+                "check_destructure: no field",
+                &format!("{POINT}fun f(Point(z)) {{ z }}")
+            )
+            .err(),
+            Some("no field `z` on type `Point`".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_destructure: tuple",
+                &format!("{POINT}fun f(p: Point) {{ let (x, y) = p; x }}")
+            )
+            .err(),
+            Some("mismatched types: expected `Point`, found `(_, _)`".to_owned())
+        );
+        let module = check(
+            "check_destructure: or-pattern",
+            "fun f(x: I64) { let (a, 1) | (a, _) = (x, 2); a }",
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec!["fun(I64) => I64".to_owned()])
+        );
+        assert_eq!(
+            check(
+                "check_destructure: or-names",
+                "fun f() { let a | b = 1; b }"
+            )
+            .err(),
+            Some("variable `a` is not bound in every alternative of the pattern".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_destructure: or-types",
+                "fun f(x: I64, y: Bool) { let (a, _) | (_, a) = (x, y); a }"
+            )
+            .err(),
+            Some("mismatched types: expected `I64`, found `Bool`".to_owned())
+        );
+    }
+
+    #[test]
+    fn check_infer() {
+        let module = check(
+            "check_infer: bindings",
+            "fun f(z: I32) { let x = 1; let y: _ = x + z; let w = 2; (y, w, 0.5) }",
+        )
+        .expect("type check should not fail");
+        assert_eq!(fun_types(&module), ["fun(I32) => (I32, I64, F32)"]);
+        let hir::ItemKind::Fun(fun) = &module.items[0].kind else {
+            panic!("expected a function");
+        };
+        let types: Vec<_> = fun
+            .body
+            .bind
+            .iter()
+            .map(|bind| match bind {
+                hir::Bind::Let(pat, val) => format!("{}: {}", pat.ty, val.ty),
+                hir::Bind::Item(_) => panic!("expected a binding"),
+            })
+            .collect();
+        assert_eq!(types, ["I32: I32", "I32: I32", "I64: I64"]);
+
+        // Arguments and literals take the types of their uses
+        let module = check(
+            "check_infer: uses",
+            "fun f(a, b) { let c: [F64; 2] = [a, 1.5]; c[0] + b } fun g(x: I8) { f(2.0, 1.0); let d = [x, 100]; d }",
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec![
+                "fun(F64, F64) => F64".to_owned(),
+                "fun(I8) => [I8; 2]".to_owned(),
+            ])
+        );
+        let module = check(
+            "check_infer: closure",
+            "fun f() { let add = fun(x, y) => x + y; add(1, 2) }",
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec!["fun() => I64".to_owned()])
+        );
+    }
+
+    #[test]
+    fn check_ambiguous() {
+        assert_eq!(
+            check("check_ambiguous: array", "fun f() { let a = []; a }").err(),
+            Some("type annotations needed for the array element".to_owned())
+        );
+        assert_eq!(
+            check("check_ambiguous: argument", "fun f(a, b: I64) { b }").err(),
+            Some("type annotations needed for `a`".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_ambiguous: annotation",
+                "fun f() { let a: [_; 1] = [fun(x) => x]; 1 }"
+            )
+            .err(),
+            Some("type annotations needed for `x`".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_ambiguous: range",
+                "fun f(a: I8) { let b = a + 300; b }"
+            )
+            .err(),
+            Some("literal `300` is out of range for `I8`".to_owned())
+        );
+        assert!(check(
+            "check_ambiguous: kinds",
+            "fun f(a) { let b = a + 1; let c = a + 1.0; (b, c) }"
+        )
+        .is_err());
+        assert_eq!(
+            check("check_ambiguous: recursion", "fun f() { f() }").err(),
+            Some("type annotations needed for the return type".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_ambiguous: partly recursive",
+                "fun f(a: I64) { (a, f(a)) }"
+            )
+            .err(),
+            Some("type annotations needed for the return type".to_owned())
+        );
+        assert_eq!(
+            check("check_ambiguous: unbound", "fun f() { x }").err(),
+            Some("cannot find `x` in this scope".to_owned())
+        );
+        assert_eq!(
+            check("check_ambiguous: field", "fun f(a: I64) { a.z }").err(),
+            Some("no field `z` on type `I64`".to_owned())
+        );
+    }
+
+    #[test]
+    fn check_cond() {
+        assert_eq!(
+            check(
+                "check_cond: compare",
+                "fun f(x: I64) { if x < 3 { 1 } else { 2 } }"
+            )
+            .as_ref()
+            .map(fun_types),
+            Ok(vec!["fun(I64) => I64".to_owned()])
+        );
+        assert_eq!(
+            check(
+                "check_cond: guard",
+                "fun f(x: I64) { match x { y if y > 0 => true, _ => (1 < 2) & true } }"
+            )
+            .as_ref()
+            .map(fun_types),
+            Ok(vec!["fun(I64) => Bool".to_owned()])
+        );
+        assert_eq!(
+            check("check_cond: integer", "fun f() { if 1 { 2 } else { 3 } }").err(),
+            Some("mismatched types: expected `Bool`, found `{integer}`".to_owned())
+        );
+        assert_eq!(
+            check("check_cond: while", "fun f() { while \"dog\" { } }").err(),
+            Some("mismatched types: expected `Bool`, found `Str`".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_cond: guard",
+                "fun f(x: I64) { match x { y if 1 => 1, _ => 2 } }"
+            )
+            .err(),
+            Some("mismatched types: expected `Bool`, found `{integer}`".to_owned())
+        );
+    }
+
+    #[test]
+    fn check_operand() {
+        assert_eq!(
+            check(
+                "check_operand: built-in",
+                "fun f(a: I32, b: F64) { (a % 3 & 5, -b * 2.0, \"dog\" + \"s\", true ^ false) }"
+            )
+            .as_ref()
+            .map(fun_types),
+            Ok(vec!["fun(I32, F64) => (I32, F64, Str, Bool)".to_owned()])
+        );
+        assert_eq!(
+            check(
+                "check_operand: str",
+                "fun f(a: Str) { (a < \"dog\", a >= \"cat\", a == \"s\") }"
+            )
+            .as_ref()
+            .map(fun_types),
+            Ok(vec!["fun(Str) => (Bool, Bool, Bool)".to_owned()])
+        );
+        assert_eq!(
+            check("check_operand: bool", "fun f() { true + false }").err(),
+            Some("cannot apply `+` to type `Bool`".to_owned())
+        );
+        assert_eq!(
+            check("check_operand: tuple", "fun f() { (1, 2) * (3, 4) }").err(),
+            Some("cannot apply `*` to type `(I64, I64)`".to_owned())
+        );
+        assert_eq!(
+            check("check_operand: neg", "fun f() { -\"dog\" }").err(),
+            Some("cannot apply `-` to type `Str`".to_owned())
+        );
+        assert_eq!(
+            check("check_operand: bitwise", "fun f(a: F32) { a | 1.0 }").err(),
+            Some("cannot apply `|` to type `F32`".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_operand: inferred",
+                "fun f() { let g = fun(a, b) => a - b; g(true, false) }"
+            )
+            .err(),
+            Some("cannot apply `-` to type `Bool`".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_operand: assign",
+                "fun f() { let s = \"dog\"; s *= \"s\"; s }"
+            )
+            .err(),
+            Some("cannot apply `*` to type `Str`".to_owned())
+        );
+    }
+
+    #[test]
+    fn check_cast() {
+        assert_eq!(
+            check(
+                "check_cast: numeric",
+                "fun f(p: Ptr) { (1 as I128, 1.5 as I8 as F64, p as I32, true as I64, 2 as F32) }"
+            )
+            .as_ref()
+            .map(fun_types),
+            Ok(vec!["fun(Ptr) => (I128, F64, I32, I64, F32)".to_owned()])
+        );
+        assert_eq!(
+            check("check_cast: str", "fun f() { \"a\" as I64 }").err(),
+            Some("cannot cast Str as I64: only numeric types can be cast".to_owned())
+        );
+        assert_eq!(
+            check("check_cast: to bool", "fun f() { 1.5 as Bool }").err(),
+            Some("cannot cast F32 as Bool: only numeric types can be cast".to_owned())
+        );
+        assert_eq!(
+            check("check_cast: ptr float", "fun f() { 1 as Ptr as F32 }").err(),
+            Some("cannot cast Ptr as F32: pointers only convert to and from integers".to_owned())
+        );
+        assert_eq!(
+            check("check_cast: tuple", "fun f() { (1, 2) as I64 }").err(),
+            Some("cannot cast (I64, I64) as I64: only numeric types can be cast".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_cast: inferred",
+                "fun f() { let g = fun(a) => a as I8; g(\"dog\") }"
+            )
+            .err(),
+            Some("cannot cast Str as I8: only numeric types can be cast".to_owned())
+        );
+    }
+
+    #[test]
+    fn check_loop_ty() {
+        let module = check(
+            "check_loop_ty: loops",
+            "fun f() { loop { } } fun g() { loop { break; } } \
+            fun h(x: Bool) { loop { if x { break 1 } break 2 } } \
+            fun i() { loop 'outer { loop { break 'outer \"dog\" } } } \
+            fun j(x: Bool) { while x { } }",
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec![
+                "fun() => !".to_owned(),
+                "fun() => ()".to_owned(),
+                "fun(Bool) => I64".to_owned(),
+                "fun() => Str".to_owned(),
+                "fun(Bool) => ()".to_owned(),
+            ])
+        );
+        assert_eq!(
+            check(
+                "check_loop_ty: mismatch",
+                "fun f(x: Bool) { loop { if x { break 1 } break \"dog\" } }"
+            )
+            .err(),
+            Some("mismatched types: expected `{integer}`, found `Str`".to_owned())
+        );
+        assert!(check(
+            "check_loop_ty: mismatch unit",
+            "fun f(x: Bool) { loop { if x { break } break 1 } }"
+        )
+        .is_err());
+        assert!(check(
+            "check_loop_ty: while value",
+            "fun f(x: Bool) { while x { break 1 } }"
+        )
+        .is_err());
+        assert!(check(
+            "check_loop_ty: for value",
+            "fun f() { for i in 0..9 { break \"dog\" } }"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn check_index() {
+        let module = check(
+            "check_index: built-in",
+            "fun f(a: [F32; 3], s: Str, i: I8) { (a[i], a[0..2], s[1], s[0..2], s.len) }",
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec![
+                "fun([F32; 3], Str, I8) => (F32, [F32], I8, Str, I64)".to_owned()
+            ])
+        );
+        assert_eq!(
+            check("check_index: str", "fun f(a: [I64; 2]) { a[\"x\"] }").err(),
+            Some("the index must be an integer or a range, found `Str`".to_owned())
+        );
+        assert_eq!(
+            check("check_index: float", "fun f(a: [I64; 2]) { a[1.0] }").err(),
+            Some("the index must be an integer or a range, found `F32`".to_owned())
+        );
+        assert_eq!(
+            check("check_index: range", "fun f(a: [I64; 2]) { a[0.0..1.0] }").err(),
+            Some("cannot apply `..` to type `F32`".to_owned())
+        );
+        assert_eq!(
+            check("check_index: value", "fun f() { let a = (1, 2); a[0] }").err(),
+            Some("cannot index into a value of type `(I64, I64)`".to_owned())
+        );
+        assert_eq!(
+            check("check_index: range value", "fun f() { let r = 0..5; r }").err(),
+            Some("ranges can only be used in indexes and `for` loops".to_owned())
+        );
+        assert!(check(
+            "check_index: range loop",
+            "fun f(a: [I64; 4]) { for i in 0..=3 { a[i] } }"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn check_interface() {
+        const DEFS: &str = "interface Show { fun show(self) => Str } cus Point(x: I64, y: I64)";
+        let module = check(
+            "check_interface: methods",
+            &format!(
+                "{DEFS} for Point: Show {{ fun show(self) {{ \"point\" }} }} \
+                 for Point {{ fun sum(self, z: I64) {{ self.x + self.y + z }} }} \
+                 fun describe[T: Show](v: T) {{ v.show() }} \
+                 fun report(p: Point) {{ (describe(p), p.sum(1)) }}"
+            ),
+        );
+        assert_eq!(
+            module.as_ref().map(fun_types),
+            Ok(vec![
+                "fun(T) => Str".to_owned(),
+                "fun(Point) => (Str, I64)".to_owned()
+            ])
+        );
+
+        assert_eq!(
+            check(
+                "check_interface: unsatisfied",
+                &format!(
+                    "{DEFS} fun describe[T: Show](v: T) {{ v.show() }} fun f() {{ describe(1) }}"
+                ),
+            )
+            .err(),
+            Some("the type `I64` does not implement `Show`".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_interface: missing",
+                &format!("{DEFS} for Point: Show {{ }}")
+            )
+            .err(),
+            Some("missing `show` in the implementation of `Show`".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_interface: signature",
+                &format!("{DEFS} for Point: Show {{ fun show(self) {{ 1 }} }}"),
+            )
+            .err(),
+            Some(
+                "mismatched types: expected `fun(Point) => Str`, found `fun(Point) => I64`"
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            check(
+                "check_interface: no method",
+                &format!("{DEFS} fun f[T: Show](v: T) {{ v.size() }}"),
+            )
+            .err(),
+            Some("no method `size` on type `T`".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_interface: not interface",
+                &format!("{DEFS} fun f[T: Point](v: T) {{ v }}"),
+            )
+            .err(),
+            Some("`Point` is not an interface".to_owned())
+        );
+    }
+
+    #[test]
+    fn check_operators() {
+        const DEFS: &str = "cus Vec(x: F32, y: F32) for Vec { \
+            fun add(self, other: Vec) { Vec(x: self.x + other.x, y: self.y + other.y) } \
+            fun neg(self) { Vec(x: -self.x, y: -self.y) } \
+            fun eq(self, other: Vec) { self.x == other.x } \
+            fun index(self, i: I64) { self.x } }";
+        let module = check(
+            "check_operators: lowered",
+            &format!("{DEFS} fun f(a: Vec, b: Vec) {{ let c = a + -b; c += a; (c == b, c[0]) }}"),
+        )
+        .expect("type check should not fail");
+        assert_eq!(fun_types(&module), ["fun(Vec, Vec) => (Bool, F32)"]);
+        let Some(hir::ItemKind::Fun(fun)) = module.items.last().map(|item| &item.kind) else {
+            panic!("expected a function");
+        };
+        let Some(hir::Bind::Let(_, val)) = fun.body.bind.first() else {
+            panic!("expected a binding");
+        };
+        let hir::ExprKind::Call(method, args) = &val.kind else {
+            panic!("expected a call");
+        };
+        assert!(matches!(&method.kind, hir::ExprKind::Method(_, name) if &**name == "add"));
+        assert!(matches!(
+            &args[1].kind,
+            hir::ExprKind::Call(method, _) if matches!(&method.kind, hir::ExprKind::Method(_, name) if &**name == "neg")
+        ));
+
+        // The index of the place is evaluated once, then read and assigned
+        let module = check(
+            "check_operators: compound index",
+            &format!("{DEFS} fun g() {{ 0 }} fun f(mut a: [Vec; 2], b: Vec) {{ a[g()] += b }}"),
+        )
+        .expect("type check should not fail");
+        let Some(hir::ItemKind::Fun(fun)) = module.items.last().map(|item| &item.kind) else {
+            panic!("expected a function");
+        };
+        let Some(hir::Expr {
+            kind: hir::ExprKind::Body(body),
+            ..
+        }) = fun.body.body.first()
+        else {
+            panic!("expected the index to be bound");
+        };
+        let calls = body
+            .bind
+            .iter()
+            .filter(|bind| matches!(bind, hir::Bind::Let(_, val) if matches!(val.kind, hir::ExprKind::Call(..))))
+            .count();
+        assert_eq!(calls, 1);
+        let [hir::Expr {
+            kind: hir::ExprKind::Assign(None, place, val),
+            ..
+        }] = body.body.as_slice()
+        else {
+            panic!("expected an assignment");
+        };
+        let index = |expr: &hir::Expr| match &expr.kind {
+            hir::ExprKind::Index(_, at) => {
+                matches!(&at.kind, hir::ExprKind::Name(name) if &**name == "#index0")
+            }
+            _ => false,
+        };
+        assert!(index(place));
+        assert!(matches!(&val.kind, hir::ExprKind::Call(_, args) if index(&args[0])));
+
+        assert_eq!(
+            check(
+                "check_operators: missing",
+                &format!("{DEFS} fun f(a: Vec) {{ a * a }}")
+            )
+            .err(),
+            Some("no operator `*` on type `Vec`, which needs a method `mul`".to_owned())
+        );
+        assert_eq!(
+            check(
+                "check_operators: compare",
+                "cus P(x: I64) for P { fun lt(self, other: P) { 1 } } fun f(a: P) { a < a }"
+            )
+            .err(),
+            Some("mismatched types: expected `Bool`, found `I64`".to_owned())
+        );
+    }
+}
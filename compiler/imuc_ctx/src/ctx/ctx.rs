@@ -5,6 +5,8 @@ use nonempty::NonEmpty;
 #[derive(Default)]
 pub struct Ctx {
     pub ty: super::Types,
+    /// String literals of the module, placed into its data section
+    pub data: sym::Data,
     locals: NonEmpty<super::Locals>,
     /// The pointer to the top of current stack
     stack: cmd::Ptr,
@@ -72,7 +72,13 @@ impl Types {
                 index
             };
             match &ty.kind {
-                ty::TyKind::Res(_) | ty::TyKind::Ptr(_) => {}
+                ty::TyKind::Res(_)
+                | ty::TyKind::Ptr(_)
+                | ty::TyKind::Fun(_)
+                | ty::TyKind::Slice(_) => {}
+                ty::TyKind::Array(array) => {
+                    add_edge(&mut graph, &mut map, node, &array.item);
+                }
                 ty::TyKind::Ref(item) => {
                     add_edge(&mut graph, &mut map, node, item);
                 }
@@ -86,6 +92,13 @@ impl Types {
                         add_edge(&mut graph, &mut map, node, item);
                     }
                 }
+                ty::TyKind::Union(union) => {
+                    for (_, item) in union.variants.iter() {
+                        if let Some(item) = item {
+                            add_edge(&mut graph, &mut map, node, item);
+                        }
+                    }
+                }
             }
         }
 
@@ -99,6 +112,12 @@ impl Types {
             let kind = match &ty.kind {
                 // pointers does not resolve recursively
                 ty::TyKind::Ptr(item) => ty::TyKind::Ptr(item.clone()),
+                ty::TyKind::Fun(fun) => ty::TyKind::Fun(fun.clone()),
+                ty::TyKind::Slice(item) => ty::TyKind::Slice(item.clone()),
+                ty::TyKind::Array(array) => ty::TyKind::Array(ty::Array {
+                    item: modify_item(&self.map, &array.item)?,
+                    len: array.len,
+                }),
                 ty::TyKind::Ref(item) => ty::TyKind::Ref(modify_item(&self.map, item)?),
                 ty::TyKind::Res(res) => ty::TyKind::Res(*res),
                 ty::TyKind::Tuple(tuple) => {
@@ -115,6 +134,20 @@ impl Types {
                     }
                     ty::TyKind::Struct(ty::Struct(value))
                 }
+                ty::TyKind::Union(union) => {
+                    let mut variants = Vec::new();
+                    for (name, item) in union.variants.iter() {
+                        let item = match item {
+                            Some(item) => Some(modify_item(&self.map, item)?),
+                            None => None,
+                        };
+                        variants.push((name.clone(), item));
+                    }
+                    ty::TyKind::Union(ty::Union {
+                        tag: union.tag,
+                        variants,
+                    })
+                }
             };
             let ty = ty::Ty::new(ty::TyInner {
                 name: ty.name.clone(),
@@ -0,0 +1,11 @@
+use crate::*;
+
+#[derive(Debug, Error)]
+pub enum AttrError {
+    #[error("unknown attribute `{0}`")]
+    Unknown(String),
+    #[error("attribute `{name}` expects {expect}")]
+    Args { name: String, expect: String },
+    #[error("attribute `{name}` cannot be used on {target}")]
+    Target { name: String, target: String },
+}
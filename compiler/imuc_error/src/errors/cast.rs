@@ -0,0 +1,9 @@
+use crate::*;
+
+#[derive(Debug, Error)]
+pub enum CastError {
+    #[error("cannot cast {from} as {to}: only numeric types can be cast")]
+    NonNumeric { from: String, to: String },
+    #[error("cannot cast {from} as {to}: pointers only convert to and from integers")]
+    PtrFloat { from: String, to: String },
+}
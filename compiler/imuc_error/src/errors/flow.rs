@@ -0,0 +1,11 @@
+use crate::*;
+
+#[derive(Debug, Error)]
+pub enum FlowError {
+    #[error("`break` outside of a loop")]
+    BreakOutside,
+    #[error("`continue` outside of a loop")]
+    ContinueOutside,
+    #[error("use of undeclared label '{0}")]
+    UnknownLabel(String),
+}
@@ -0,0 +1,13 @@
+use crate::*;
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("no item `{name}` in {file}")]
+    NoSuchItem { name: String, file: String },
+    #[error("`{0}` is defined or imported more than once")]
+    Conflict(String),
+    #[error("`{0}` is imported by more than one glob import")]
+    Ambiguous(String),
+    #[error("glob import of `{0}` is shadowed by an explicit import")]
+    Shadowed(String),
+}
@@ -0,0 +1,11 @@
+use crate::*;
+
+#[derive(Debug, Error)]
+pub enum IndexError {
+    #[error("index {index} is out of bounds of length {len}")]
+    OutOfBounds { index: i64, len: u64 },
+    #[error("range {start}..{end} is out of bounds of length {len}")]
+    RangeOutOfBounds { start: i64, end: i64, len: u64 },
+    #[error("range starts at {start} but ends before it at {end}")]
+    Reversed { start: i64, end: i64 },
+}
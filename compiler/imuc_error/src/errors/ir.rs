@@ -22,6 +22,10 @@ pub enum IrError {
     MissingSignature(String),
     #[error("unimplemented function signature: {0}")]
     UnimplementedSignature(String),
+    #[error("no such ABI: {0}")]
+    NoSuchAbi(String),
+    #[error("type {0} has no C layout, as its elements are not ordered")]
+    NoCLayout(String),
     #[error("unexpected eof hit")]
     Eof,
     #[error("internal type required for IR generation")]
@@ -30,4 +34,8 @@ pub enum IrError {
     CharRequired(char),
     #[error("looped reference of types")]
     LoopedReference,
+    #[error("refutable pattern cannot be lowered to stack slots")]
+    RefutablePattern,
+    #[error("only value expressions can be lowered to a stack slot")]
+    NotValue,
 }
@@ -0,0 +1,15 @@
+use crate::*;
+
+#[derive(Debug, Error)]
+pub enum MatchError {
+    #[error("non-exhaustive patterns: `{0}` not covered")]
+    NonExhaustive(String),
+    #[error("unreachable pattern `{1}` in match arm {0}")]
+    Unreachable(usize, String),
+    #[error("mismatched patterns: expected `{expect}`, found `{found}`")]
+    Mismatch { expect: String, found: String },
+    #[error("no variant `{variant}` in sum type `{ty}`")]
+    NoSuchVariant { ty: String, variant: String },
+    #[error("refutable pattern in {context}: `{witness}` not covered")]
+    Refutable { context: String, witness: String },
+}
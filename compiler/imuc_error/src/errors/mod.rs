@@ -1,13 +1,35 @@
+mod attr;
+mod cast;
+mod flow;
+mod import;
+mod index;
 mod ir;
 mod lexer;
+mod matching;
 mod memory;
+mod mutable;
+mod owner;
 mod parser;
 mod path;
+mod public;
+mod resolve;
 mod syntax;
+mod types;
 
+pub use attr::AttrError;
+pub use cast::CastError;
+pub use flow::FlowError;
+pub use import::ImportError;
+pub use index::IndexError;
 pub use ir::IrError;
 pub use lexer::LexerError;
+pub use matching::MatchError;
 pub use memory::MemoryError;
+pub use mutable::MutError;
+pub use owner::OwnError;
 pub use parser::ParserError;
 pub use path::PathError;
+pub use public::PubError;
+pub use resolve::ResolveError;
 pub use syntax::SyntaxError;
+pub use types::TypeError;
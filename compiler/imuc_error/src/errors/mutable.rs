@@ -0,0 +1,9 @@
+use crate::*;
+
+#[derive(Debug, Error)]
+pub enum MutError {
+    #[error("cannot assign to immutable binding `{name}` declared at {site}")]
+    Immutable { name: String, site: String },
+    #[error("invalid left-hand side of assignment")]
+    NotPlace,
+}
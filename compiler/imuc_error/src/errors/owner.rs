@@ -0,0 +1,21 @@
+use crate::*;
+
+#[derive(Debug, Error)]
+pub enum OwnError {
+    #[error("use of moved value `{name}`: moved at {moved}, used again at {used}")]
+    UseAfterMove {
+        name: String,
+        moved: String,
+        used: String,
+    },
+    #[error("cannot move out of `{name}` at {moved}, as it is borrowed at {borrowed}")]
+    MoveBorrowed {
+        name: String,
+        moved: String,
+        borrowed: String,
+    },
+    #[error("borrowed value `{name}` does not live long enough: borrowed at {borrowed}")]
+    Outlive { name: String, borrowed: String },
+    #[error("cannot return a reference to local `{name}` borrowed at {borrowed}")]
+    Escape { name: String, borrowed: String },
+}
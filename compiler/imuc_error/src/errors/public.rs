@@ -0,0 +1,9 @@
+use crate::*;
+
+#[derive(Debug, Error)]
+pub enum PubError {
+    #[error("item `{name}` of {file} is private to where it is imported")]
+    PrivateItem { name: String, file: String },
+    #[error("field `{field}` of type `{ty}` is private to where it is used")]
+    PrivateField { field: String, ty: String },
+}
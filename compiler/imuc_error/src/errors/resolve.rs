@@ -0,0 +1,17 @@
+use crate::*;
+
+#[derive(Debug, Error)]
+pub enum ResolveError {
+    #[error("cannot find `{0}` in this scope")]
+    Undefined(String),
+    #[error("cannot find type `{0}` in this scope")]
+    UndefinedType(String),
+    #[error("variable `{0}` is never used")]
+    UnusedVariable(String),
+    #[error("import `{0}` is never used")]
+    UnusedImport(String),
+    #[error("`{0}` shadows a name already in scope")]
+    Shadowed(String),
+    #[error("variable `{0}` is not bound in every alternative of the pattern")]
+    OrPattern(String),
+}
@@ -21,4 +21,6 @@ pub enum SyntaxError {
     TooManyOp,
     #[error("too few operators in an expression")]
     TooFewOp,
+    #[error("duplicate {kind} `{name}`")]
+    Duplicate { kind: String, name: String },
 }
@@ -0,0 +1,47 @@
+use crate::*;
+
+#[derive(Debug, Error)]
+pub enum TypeError {
+    #[error("mismatched types: expected `{expect}`, found `{found}`")]
+    Mismatch { expect: String, found: String },
+    #[error("`{0}` is not a function")]
+    NotCallable(String),
+    #[error("expected {expect} arguments, found {found}")]
+    ArgCount { expect: usize, found: usize },
+    #[error("no field `{field}` on type `{ty}`")]
+    NoField { field: String, ty: String },
+    #[error("missing field `{field}` in `{ty}`, which has no default value")]
+    MissingField { field: String, ty: String },
+    #[error("the variant `{0}` cannot take the elements left out from a base")]
+    VariantBase(String),
+    #[error("type annotations needed for {0}")]
+    Ambiguous(String),
+    #[error("literal `{value}` is out of range for `{ty}`")]
+    LiteralRange { value: String, ty: String },
+    #[error("`{0}` is not an interface")]
+    NotInterface(String),
+    #[error("the type `{ty}` does not implement `{interface}`")]
+    NotImplemented { ty: String, interface: String },
+    #[error("missing `{method}` in the implementation of `{interface}`")]
+    MissingMethod { method: String, interface: String },
+    #[error("no operator `{op}` on type `{ty}`, which needs a method `{method}`")]
+    NoOperator {
+        op: String,
+        ty: String,
+        method: String,
+    },
+    #[error("cannot apply `{op}` to type `{ty}`")]
+    Operand { op: String, ty: String },
+    #[error("ranges can only be used in indexes and `for` loops")]
+    RangeValue,
+    #[error("cannot index into a value of type `{0}`")]
+    NotIndexable(String),
+    #[error("the index must be an integer or a range, found `{0}`")]
+    IndexType(String),
+    #[error("no method `{method}` on type `{ty}`")]
+    NoMethod { method: String, ty: String },
+    #[error("the `?` operator takes an `Option` or a `Result`, found `{0}`")]
+    NotTry(String),
+    #[error("the `?` operator returns `{found}` from a function returning `{expect}`")]
+    TryReturn { expect: String, found: String },
+}
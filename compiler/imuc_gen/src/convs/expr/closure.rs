@@ -0,0 +1,62 @@
+use crate::prelude::*;
+use ir::sym::ty::{Struct, TyInner, TyItem, TyKind};
+use std::collections::BTreeMap;
+
+/// Lowers a closure into an environment struct and a code pointer
+///
+/// The body is lifted to a function named [`Self::name`], whose parameter is a tuple of an opaque
+/// pointer to the environment followed by the arguments. The value of the closure is a struct
+/// holding the pointer to the environment as `env` and the pointer to the lifted function as
+/// `code`, which is the type of every function value of the same signature, so that callers
+/// invoke any of them indirectly whatever they capture
+pub struct ClosureConv {
+    pub name: ast::StrRef,
+}
+
+/// The types generated for a closure, where the environment is also added to the current locals
+pub struct Closure {
+    /// The captured locals, in the order of first use in the body
+    pub captures: Vec<ast::StrRef>,
+    pub env: ir::sym::Ty,
+    /// The signature of the lifted function
+    pub sig: ir::sym::FunSig,
+    pub ty: ir::sym::Ty,
+}
+
+impl Converter for ClosureConv {
+    type Input = hir::Closure;
+}
+
+impl Convert<Closure> for ClosureConv {
+    fn convert(self, ctx: &mut Ctx, input: Self::Input) -> Result<Closure> {
+        let mut captures = Vec::new();
+        let mut env = BTreeMap::new();
+        for (name, ty) in input.captures {
+            env.insert(
+                name.clone(),
+                TyItem::Solid(convs::TypeConv.convert(ctx, ty)?),
+            );
+            captures.push(name);
+        }
+        let env = ir::sym::Ty::new(TyInner {
+            name: format!("{}#env", self.name).into(),
+            kind: TyKind::Struct(Struct(env)),
+            external: false,
+        });
+
+        let args = match input.args.kind {
+            hir::PatKind::Tuple(list) => list.into_iter().map(|arg| arg.ty).collect(),
+            _ => vec![input.args.ty],
+        };
+        let sig = convs::code_sig(ctx, args.clone(), input.body.ty.clone())?;
+        let ty = convs::TypeConv.convert(ctx, Ty::Fun(args, Box::new(input.body.ty)))?;
+
+        ctx.locals_mut().ty.insert(env.name.clone(), env.clone());
+        Ok(Closure {
+            captures,
+            env,
+            sig,
+            ty,
+        })
+    }
+}
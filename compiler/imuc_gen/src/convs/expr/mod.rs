@@ -1,3 +1,5 @@
+mod closure;
 mod value;
 
+pub use closure::*;
 pub use value::*;
@@ -1,13 +1,49 @@
 use crate::prelude::*;
+use imuc_lexer::token::ResVal;
+use ir::sym::Prim;
 
+/// Lowers a value expression, pushing the value onto the stack
+///
+/// Literals are stored, where strings point into the data section of the module, and names,
+/// including `self`, are duplicated from the slots of their locals. The value takes a stack slot
+/// of the size of its type. Other expressions are not values and cannot be lowered here
 pub struct ValueConv;
 
+/// A value pushed by [`ValueConv`]
+pub struct Pushed {
+    /// The stack slot holding the value
+    pub ptr: Ptr,
+    pub cmds: Vec<Cmd>,
+}
+
 impl Converter for ValueConv {
-    type Input = ast::expr::Value;
+    type Input = hir::Expr;
 }
 
-impl Convert<Ptr> for ValueConv {
-    fn convert(self, ctx: &mut Ctx, input: Self::Input) -> Result<Ptr> {
-        todo!()
+impl Convert<Pushed> for ValueConv {
+    fn convert(self, ctx: &mut Ctx, input: Self::Input) -> Result<Pushed> {
+        let ty = convs::TypeConv.convert(ctx, input.ty)?;
+        let cmd = match input.kind {
+            hir::ExprKind::Prim(Prim::String(value)) => Cmd::Data(ctx.data.insert(&value)),
+            hir::ExprKind::Prim(prim) => Cmd::Store(prim),
+            hir::ExprKind::Res(ResVal::True) => Cmd::Store(Prim::Bool(true)),
+            hir::ExprKind::Res(ResVal::False) => Cmd::Store(Prim::Bool(false)),
+            hir::ExprKind::Res(ResVal::SelfValue) => local(ctx, "self")?,
+            hir::ExprKind::Name(name) => local(ctx, &name)?,
+            _ => return Err(errors::IrError::NotValue.into()),
+        };
+        let ptr = ctx.push_stack(ty.size()?);
+        Ok(Pushed {
+            ptr,
+            cmds: vec![cmd],
+        })
     }
 }
+
+/// Duplicates the value of the local
+fn local(ctx: &Ctx, name: &str) -> Result<Cmd> {
+    let value = ctx
+        .get_value(name)
+        .ok_or_else(|| errors::IrError::NoSuchValue(name.to_owned()))?;
+    Ok(Cmd::Dupli(value.ty.size()?, value.ptr))
+}
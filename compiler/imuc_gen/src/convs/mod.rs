@@ -1,3 +1,5 @@
 mod expr;
+mod pat;
 
 pub use expr::*;
+pub use pat::*;
@@ -0,0 +1,162 @@
+use crate::prelude::*;
+use imuc_ctx::ctx::Value;
+use ir::sym::ty::{TyInner, TyItem, TyKind};
+
+/// Lowers the pattern of a binding or of arguments, destructuring the value at [`Self::ptr`]
+///
+/// Every name bound by the pattern takes a stack slot of its own in the current locals, and the
+/// part of the value it binds is duplicated into that slot. Parts are found by the sizes of the
+/// parts before them, where the fields of a struct are ordered by their names and the payload of
+/// a variant follows the tag. Unused names take no slots
+///
+/// The pattern must match every value, as nothing is tested. Literals and variants of sum types
+/// with several variants may only appear in alternatives of an or-pattern ending with one that
+/// matches everything, where every alternative up to it binds its names from the same parts,
+/// like `(a, 1) | (a, _)`
+pub struct BindConv {
+    pub ptr: Ptr,
+}
+
+/// A part of the value bound to a name
+struct Part {
+    name: ast::StrRef,
+    ty: ir::sym::Ty,
+    ptr: Ptr,
+}
+
+impl Converter for BindConv {
+    type Input = hir::Pat;
+}
+
+impl Convert<Vec<Cmd>> for BindConv {
+    fn convert(self, ctx: &mut Ctx, input: Self::Input) -> Result<Vec<Cmd>> {
+        let ty = convs::TypeConv.convert(ctx, input.ty.clone())?;
+        let mut parts = Vec::new();
+        if !parts_of(input, &ty, self.ptr, &mut parts)? {
+            return Err(errors::IrError::RefutablePattern.into());
+        }
+        let mut cmds = Vec::new();
+        for part in parts {
+            let size = part.ty.size()?;
+            let slot = ctx.push_stack(size);
+            ctx.locals_mut()
+                .value
+                .insert(part.name, Value::new(part.ty, slot));
+            cmds.push(Cmd::Dupli(size, part.ptr));
+        }
+        Ok(cmds)
+    }
+}
+
+fn solid(item: &TyItem) -> Result<&ir::sym::Ty> {
+    match item {
+        TyItem::Solid(ty) => Ok(ty),
+        TyItem::Pending(name) => Err(errors::MemoryError::UnknownHandle(name.to_string()).into()),
+    }
+}
+
+/// Finds the parts of the value at the pointer bound by the pattern, returning whether the
+/// pattern matches every value
+fn parts_of(input: hir::Pat, ty: &ir::sym::Ty, ptr: Ptr, parts: &mut Vec<Part>) -> Result<bool> {
+    match (input.kind, &ty.kind) {
+        (hir::PatKind::Ident(None, _), _) => Ok(true),
+        (hir::PatKind::Ident(Some(name), _), _) => {
+            parts.push(Part {
+                name,
+                ty: ty.clone(),
+                ptr,
+            });
+            Ok(true)
+        }
+        (hir::PatKind::Lit(_), _) => Ok(false),
+        (hir::PatKind::Tuple(list), TyKind::Tuple(tuple)) if list.len() == tuple.0.len() => {
+            let mut ptr = ptr;
+            let mut all = true;
+            for (pat, item) in list.into_iter().zip(tuple.0.iter()) {
+                let item = solid(item)?;
+                all &= parts_of(pat, item, ptr, parts)?;
+                ptr += item.size()?;
+            }
+            Ok(all)
+        }
+        (hir::PatKind::Struct(None, pats), TyKind::Struct(cus)) => fields(pats, cus, ptr, parts),
+        // Only the variant of a sum type with a single variant matches every value
+        (hir::PatKind::Struct(Some(variant), pats), TyKind::Union(union)) => {
+            let tag = TyInner {
+                name: ty.name.clone(),
+                kind: TyKind::Res(union.tag),
+                external: false,
+            };
+            let all = union.variants.len() == 1;
+            let payload = union
+                .variants
+                .iter()
+                .find(|(name, _)| *name == variant)
+                .ok_or_else(|| errors::IrError::NoSuchValue(variant.to_string()))?
+                .1
+                .as_ref()
+                .map(solid)
+                .transpose()?;
+            match payload.map(|payload| &payload.kind) {
+                Some(TyKind::Struct(cus)) => {
+                    Ok(fields(pats, cus, ptr + tag.size()?, parts)? && all)
+                }
+                _ if pats.is_empty() => Ok(all),
+                _ => Err(errors::IrError::RefutablePattern.into()),
+            }
+        }
+        // The first alternative matching binds the names, which is known without testing the
+        // value if every alternative up to one matching everything binds the same parts
+        (hir::PatKind::Any(list), _) => {
+            let mut first: Option<Vec<Part>> = None;
+            for pat in list {
+                let mut found = Vec::new();
+                let all = parts_of(pat, ty, ptr, &mut found)?;
+                match &first {
+                    None => first = Some(found),
+                    Some(first) if same(first, &found) => {}
+                    Some(_) => return Err(errors::IrError::RefutablePattern.into()),
+                }
+                if all {
+                    parts.extend(first.into_iter().flatten());
+                    return Ok(true);
+                }
+            }
+            parts.extend(first.into_iter().flatten());
+            Ok(false)
+        }
+        _ => Err(errors::IrError::RefutablePattern.into()),
+    }
+}
+
+/// Returns whether the alternatives bind every name to the same part of the value
+fn same(lhs: &[Part], rhs: &[Part]) -> bool {
+    lhs.len() == rhs.len()
+        && lhs.iter().all(|lhs| {
+            rhs.iter()
+                .any(|rhs| rhs.name == lhs.name && rhs.ptr == lhs.ptr && rhs.ty.name == lhs.ty.name)
+        })
+}
+
+/// Finds the parts bound by the fields of the struct at the pointer, where fields not in the
+/// patterns are skipped
+fn fields(
+    mut pats: std::collections::BTreeMap<ast::StrRef, hir::Pat>,
+    cus: &ir::sym::ty::Struct,
+    ptr: Ptr,
+    parts: &mut Vec<Part>,
+) -> Result<bool> {
+    let mut ptr = ptr;
+    let mut all = true;
+    for (name, item) in cus.0.iter() {
+        let item = solid(item)?;
+        if let Some(pat) = pats.remove(name) {
+            all &= parts_of(pat, item, ptr, parts)?;
+        }
+        ptr += item.size()?;
+    }
+    match pats.into_keys().next() {
+        Some(name) => Err(errors::IrError::NoSuchValue(name.to_string()).into()),
+        None => Ok(all),
+    }
+}
@@ -0,0 +1,5 @@
+mod bind;
+mod types;
+
+pub use bind::*;
+pub use types::*;
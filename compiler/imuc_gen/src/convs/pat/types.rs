@@ -0,0 +1,146 @@
+use crate::prelude::*;
+use ast::StrRef;
+use imuc_check::builtin;
+use ir::sym::ty::{Array, FunTy, Struct, Tuple, TyInner, TyItem, TyKind, Union};
+use std::collections::BTreeMap;
+
+/// Converts a type told by the type check to the IR type, looking up custom types in the context
+pub struct TypeConv;
+
+impl Converter for TypeConv {
+    type Input = Ty;
+}
+
+/// Creates an anonymous type of the kind, named after how it is written
+fn anonymous(name: String, kind: TyKind) -> ir::sym::Ty {
+    ir::sym::Ty::new(TyInner {
+        name: name.into(),
+        kind,
+        external: false,
+    })
+}
+
+/// Creates an anonymous tuple of the types, named like `(a,b)`
+fn tuple(items: Vec<TyItem>) -> ir::sym::Ty {
+    let name = items
+        .iter()
+        .map(|item| match item {
+            TyItem::Solid(ty) => ty.name.to_string(),
+            TyItem::Pending(name) => name.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    anonymous(format!("({})", name), TyKind::Tuple(Tuple(items)))
+}
+
+/// Returns the signature of the code of a function value taking the arguments, which takes
+/// the environment as an opaque pointer before them
+pub(crate) fn code_sig(ctx: &mut Ctx, args: Vec<Ty>, ret: Ty) -> Result<ir::sym::FunSig> {
+    let mut param = vec![TyItem::Solid(anonymous(
+        "Ptr".to_owned(),
+        TyKind::Res(ResTy::Ptr),
+    ))];
+    for arg in args {
+        param.push(TyItem::Solid(TypeConv.convert(ctx, arg)?));
+    }
+    let ret = TypeConv.convert(ctx, ret)?;
+    Ok(ir::sym::FunSig {
+        param: tuple(param),
+        ret,
+    })
+}
+
+impl Convert<ir::sym::Ty> for TypeConv {
+    fn convert(self, ctx: &mut Ctx, input: Self::Input) -> Result<ir::sym::Ty> {
+        let ty = match input {
+            Ty::Never | Ty::Unknown | Ty::Var(..) => {
+                return Err(errors::IrError::TypeNotAllowed(input.to_string()).into())
+            }
+            Ty::Res(ResTy::SelfType) => return Err(errors::MemoryError::UnexpectedSelf.into()),
+            Ty::Res(res) => anonymous(format!("{:?}", res), TyKind::Res(res)),
+            Ty::Unit => tuple(Vec::new()),
+            Ty::Named(ref name, ref args) if args.is_empty() => ctx
+                .get_type(name)
+                .cloned()
+                .ok_or_else(|| errors::IrError::NoSuchType(name.to_string()))?,
+            // Built-in sum types are laid out for each list of template arguments they take
+            Ty::Named(ref name, ref args) if ctx.get_type(name).is_none() => {
+                let sum = builtin::find(name)
+                    .ok_or_else(|| errors::IrError::NoSuchType(name.to_string()))?;
+                let mut variants = Vec::new();
+                for (variant, _) in sum.variants {
+                    let variant = StrRef::from(*variant);
+                    let payload = match sum.elems(Some(&variant), args) {
+                        Some(elems) => {
+                            let mut fields = BTreeMap::new();
+                            for (field, ty) in elems {
+                                fields.insert(field, TyItem::Solid(TypeConv.convert(ctx, ty)?));
+                            }
+                            let payload = format!("{}.{}", input, variant);
+                            Some(TyItem::Solid(anonymous(
+                                payload,
+                                TyKind::Struct(Struct(fields)),
+                            )))
+                        }
+                        None => None,
+                    };
+                    variants.push((variant, payload));
+                }
+                anonymous(input.to_string(), TyKind::Union(Union::new(variants)))
+            }
+            Ty::Named(..) => return Err(errors::IrError::TypeNotAllowed(input.to_string()).into()),
+            Ty::Tuple(elem) => {
+                let mut items = Vec::new();
+                for ty in elem {
+                    items.push(TyItem::Solid(TypeConv.convert(ctx, ty)?));
+                }
+                tuple(items)
+            }
+            Ty::Shared(ty) => {
+                let ty = TypeConv.convert(ctx, *ty)?;
+                anonymous(format!("@{}", ty.name), TyKind::Ref(TyItem::Solid(ty)))
+            }
+            Ty::Array(item, len) => {
+                let item = TypeConv.convert(ctx, *item)?;
+                anonymous(
+                    format!("[{};{}]", item.name, len),
+                    TyKind::Array(Array {
+                        item: TyItem::Solid(item),
+                        len,
+                    }),
+                )
+            }
+            Ty::Slice(item) => {
+                let item = TypeConv.convert(ctx, *item)?;
+                anonymous(
+                    format!("[{}]", item.name),
+                    TyKind::Slice(TyItem::Solid(item)),
+                )
+            }
+            // Functions and closures alike are values of an environment and a code pointer,
+            // see [`convs::ClosureConv`]
+            Ty::Fun(args, ret) => {
+                let ir::sym::FunSig { param, ret } = code_sig(ctx, args, *ret)?;
+                let name = format!("^{}>{}", param.name, ret.name);
+                let code = anonymous(
+                    format!("{}#code", name),
+                    TyKind::Fun(FunTy {
+                        param: TyItem::Solid(param),
+                        ret: TyItem::Solid(ret),
+                    }),
+                );
+                anonymous(
+                    name,
+                    TyKind::Struct(Struct(BTreeMap::from([
+                        (
+                            "env".into(),
+                            TyItem::Solid(anonymous("Ptr".to_owned(), TyKind::Res(ResTy::Ptr))),
+                        ),
+                        ("code".into(), TyItem::Solid(code)),
+                    ]))),
+                )
+            }
+        };
+        Ok(ty)
+    }
+}
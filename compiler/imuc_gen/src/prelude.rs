@@ -3,7 +3,9 @@ pub(crate) use crate::{
     convs,
 };
 pub(crate) use imuc_ast as ast;
+pub(crate) use imuc_check::{hir, ty::Ty};
 pub(crate) use imuc_ctx::ctx::Ctx;
 pub(crate) use imuc_error::*;
 pub(crate) use imuc_ir as ir;
+pub(crate) use imuc_lexer::token::ResTy;
 pub(crate) use ir::cmd::{Alloc, Bytes, Cmd, NumBytes, Ptr};
@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+    use imuc_check::checks::TypeCheck;
+    use imuc_check::{hir, Check};
+    use imuc_ctx::ctx::Ctx;
+    use imuc_gen::convs::BindConv;
+    use imuc_gen::Convert;
+    use imuc_ir::cmd::{Bytes, Cmd};
+    use imuc_ir::sym::ty::*;
+    use imuc_lexer::token::ResTy;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+    use std::collections::BTreeMap;
+
+    fn res(res: ResTy) -> TyItem {
+        TyItem::Solid(Ty::new(TyInner {
+            name: format!("{:?}", res).into(),
+            kind: TyKind::Res(res),
+            external: false,
+        }))
+    }
+
+    #[test]
+    fn convert_bind() {
+        let content = "cus Point(x: F32, y: I64) \
+            fun f(a: I8, p: Point) { let (b, Point(y, x: _)) = (a, p); b + 1 }";
+        let mut parser = Parser::new(FileReader::new(
+            "convert_bind",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let module = rules::ModuleRules
+            .parse(&mut parser)
+            .expect("parsing should not fail")
+            .expect("module rule should always match");
+        let module = TypeCheck::new()
+            .check(&module)
+            .expect("type check should not fail");
+        let Some(hir::ItemKind::Fun(fun)) = module.items.into_iter().nth(1).map(|item| item.kind)
+        else {
+            panic!("expected a function");
+        };
+        let Some(hir::Bind::Let(pat, _)) = fun.body.bind.into_iter().next() else {
+            panic!("expected a binding");
+        };
+
+        let mut ctx = Ctx::new();
+        let point = Ty::new(TyInner {
+            name: "Point".into(),
+            kind: TyKind::Struct(Struct(BTreeMap::from([
+                ("x".into(), res(ResTy::F32)),
+                ("y".into(), res(ResTy::I64)),
+            ]))),
+            external: false,
+        });
+        ctx.ty.insert("Point".into(), point);
+        ctx.push_stack(Bytes::from(16));
+        ctx.push();
+        let cmds = BindConv {
+            ptr: Bytes::from(3),
+        }
+        .convert(&mut ctx, pat)
+        .expect("pattern should be lowered");
+
+        // `b` is the first byte of the tuple, and `y` follows `x` inside the point
+        assert!(matches!(
+            cmds.as_slice(),
+            [Cmd::Dupli(b, b_ptr), Cmd::Dupli(y, y_ptr)]
+                if u32::from(*b) == 1
+                    && u32::from(*b_ptr) == 3
+                    && u32::from(*y) == 8
+                    && u32::from(*y_ptr) == 8
+        ));
+        let slot = |ctx: &Ctx, name: &str| ctx.get_value(name).map(|value| u32::from(value.ptr));
+        assert_eq!(slot(&ctx, "b"), Some(16));
+        assert_eq!(slot(&ctx, "y"), Some(17));
+        assert_eq!(slot(&ctx, "x"), None);
+        ctx.pop();
+        assert_eq!(slot(&ctx, "b"), None);
+    }
+
+    /// Lowers the pattern of the first binding of the function, whose value is at the pointer
+    fn bind_first(content: &str, ptr: u32) -> Result<(Vec<Cmd>, Ctx), String> {
+        let mut parser = Parser::new(FileReader::new(
+            "convert_bind_any",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let module = rules::ModuleRules
+            .parse(&mut parser)
+            .expect("parsing should not fail")
+            .expect("module rule should always match");
+        let module = TypeCheck::new()
+            .check(&module)
+            .expect("type check should not fail");
+        let Some(hir::ItemKind::Fun(fun)) = module.items.into_iter().next().map(|item| item.kind)
+        else {
+            panic!("expected a function");
+        };
+        let Some(hir::Bind::Let(pat, _)) = fun.body.bind.into_iter().next() else {
+            panic!("expected a binding");
+        };
+        let mut ctx = Ctx::new();
+        ctx.push_stack(Bytes::from(16));
+        ctx.push();
+        let cmds = BindConv {
+            ptr: Bytes::from(ptr),
+        }
+        .convert(&mut ctx, pat)
+        .map_err(|err| err.root_cause().to_string())?;
+        Ok((cmds, ctx))
+    }
+
+    #[test]
+    fn convert_bind_any() {
+        // Both alternatives take `a` from the first element
+        let (cmds, ctx) = bind_first("fun f(x: I64) { let (a, 1) | (a, _) = (x, 2); a }", 0)
+            .expect("pattern should be lowered");
+        assert!(matches!(
+            cmds.as_slice(),
+            [Cmd::Dupli(size, ptr)] if u32::from(*size) == 8 && u32::from(*ptr) == 0
+        ));
+        assert!(ctx.get_value("a").is_some());
+
+        // The first alternative matches every value
+        let (cmds, _) = bind_first(
+            "fun f(x: I64, y: I64) { let (_, a) | (a, _) = (x, y); a }",
+            0,
+        )
+        .expect("pattern should be lowered");
+        assert!(matches!(
+            cmds.as_slice(),
+            [Cmd::Dupli(_, ptr)] if u32::from(*ptr) == 8
+        ));
+
+        // Which alternative binds `a` is only known by testing the value
+        assert_eq!(
+            bind_first(
+                "fun f(x: I64, y: I64) { let (a, 1) | (_, a) = (x, y); a }",
+                0
+            )
+            .err(),
+            Some("refutable pattern cannot be lowered to stack slots".to_owned())
+        );
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use imuc_check::checks::TypeCheck;
+    use imuc_check::ty::Ty;
+    use imuc_check::{hir, Check};
+    use imuc_ctx::ctx::Ctx;
+    use imuc_gen::convs::{ClosureConv, TypeConv};
+    use imuc_gen::Convert;
+    use imuc_ir::sym::ty::*;
+    use imuc_lexer::token::ResTy;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+
+    #[test]
+    fn convert_closure() {
+        let content =
+            "fun g(v: F64) { 1 } fun f(y: I64, z: F64) { let c = fun(x: I64) => x + y + g(z); c }";
+        let mut parser = Parser::new(FileReader::new(
+            "convert_closure",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let module = rules::ModuleRules
+            .parse(&mut parser)
+            .expect("parsing should not fail")
+            .expect("module rule should always match");
+        let module = TypeCheck::new()
+            .check(&module)
+            .expect("type check should not fail");
+        let Some(hir::ItemKind::Fun(fun)) = module.items.into_iter().nth(1).map(|item| item.kind)
+        else {
+            panic!("expected a function");
+        };
+        let Some(hir::Bind::Let(
+            _,
+            hir::Expr {
+                kind: hir::ExprKind::Closure(closure),
+                ..
+            },
+        )) = fun.body.bind.into_iter().next()
+        else {
+            panic!("expected a closure");
+        };
+
+        let mut ctx = Ctx::new();
+        ctx.push();
+        let closure = ClosureConv { name: "f#0".into() }
+            .convert(&mut ctx, closure)
+            .expect("closure should be converted");
+
+        // `g` is not a local, thus not captured
+        assert_eq!(
+            closure
+                .captures
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>(),
+            ["y", "z"]
+        );
+        let TyKind::Struct(Struct(env)) = &closure.env.kind else {
+            panic!("expected the environment to be a struct");
+        };
+        assert_eq!(env.len(), 2);
+        let TyKind::Tuple(Tuple(param)) = &closure.sig.param.kind else {
+            panic!("expected the parameter to be a tuple");
+        };
+        assert!(matches!(
+            param.as_slice(),
+            [TyItem::Solid(env), TyItem::Solid(arg)]
+                if matches!(env.kind, TyKind::Res(ResTy::Ptr))
+                    && matches!(arg.kind, TyKind::Res(ResTy::I64))
+        ));
+        let TyKind::Struct(Struct(value)) = &closure.ty.kind else {
+            panic!("expected the closure to be a struct");
+        };
+        assert!(matches!(
+            value.get("env"),
+            Some(TyItem::Solid(env)) if matches!(env.kind, TyKind::Res(ResTy::Ptr))
+        ));
+        assert!(matches!(
+            value.get("code"),
+            Some(TyItem::Solid(code)) if matches!(code.kind, TyKind::Fun(_))
+        ));
+        assert!(ctx.get_type("f#0#env").is_some());
+
+        // The closure is a value of the function type it is passed as
+        let fun = TypeConv
+            .convert(
+                &mut ctx,
+                Ty::Fun(vec![Ty::Res(ResTy::I64)], Box::new(Ty::Res(ResTy::I64))),
+            )
+            .expect("function type should be converted");
+        assert_eq!(fun.name, closure.ty.name);
+        assert_eq!(fun.size().ok(), closure.ty.size().ok());
+    }
+}
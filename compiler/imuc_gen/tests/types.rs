@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use imuc_check::ty;
+    use imuc_ctx::ctx::Ctx;
+    use imuc_gen::convs::TypeConv;
+    use imuc_gen::Convert;
+    use imuc_ir::cmd::Bytes;
+    use imuc_ir::sym::ty::*;
+    use imuc_lexer::token::ResTy;
+
+    #[test]
+    fn convert_builtin() {
+        let mut ctx = Ctx::new();
+        let option = ty::Ty::Named("Option".into(), vec![ty::Ty::Res(ResTy::I64)]);
+        let option = TypeConv
+            .convert(&mut ctx, option)
+            .expect("conversion should not fail");
+        assert_eq!(&*option.name, "Option[I64]");
+        let TyKind::Union(union) = &option.kind else {
+            panic!("expected a union");
+        };
+        let variants: Vec<_> = union
+            .variants
+            .iter()
+            .map(|(name, payload)| (name.to_string(), payload.is_some()))
+            .collect();
+        assert_eq!(
+            variants,
+            [("Some".to_owned(), true), ("None".to_owned(), false)]
+        );
+        assert_eq!(
+            option.size().expect("union should be sized"),
+            Bytes::from(9)
+        );
+
+        // Only the built-in types take template arguments
+        let unknown = ty::Ty::Named("Maybe".into(), vec![ty::Ty::Res(ResTy::I64)]);
+        assert!(TypeConv.convert(&mut ctx, unknown).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ast::prim::{Integer, Prim};
+    use imuc_check::hir;
+    use imuc_check::ty::Ty;
+    use imuc_ctx::ctx::{Ctx, Value};
+    use imuc_gen::convs::ValueConv;
+    use imuc_gen::Convert;
+    use imuc_ir::cmd::{Bytes, Cmd};
+    use imuc_ir::sym::ty::{TyInner, TyKind};
+    use imuc_lexer::token::{ResTy, ResVal};
+
+    fn expr(kind: hir::ExprKind, ty: ResTy) -> hir::Expr {
+        hir::Expr {
+            kind,
+            ty: Ty::Res(ty),
+        }
+    }
+
+    #[test]
+    fn convert_value() {
+        let mut ctx = Ctx::new();
+        ctx.push();
+        let i32_ty = imuc_ir::sym::Ty::new(TyInner {
+            name: "I32".into(),
+            kind: TyKind::Res(ResTy::I32),
+            external: false,
+        });
+        let slot = ctx.push_stack(Bytes::from(4));
+        ctx.locals_mut()
+            .value
+            .insert("a".into(), Value::new(i32_ty, slot));
+
+        let mut convert = |input| ValueConv.convert(&mut ctx, input);
+        let int = convert(expr(
+            hir::ExprKind::Prim(Prim::Integer(Integer::I64(2))),
+            ResTy::I64,
+        ))
+        .expect("literal should be lowered");
+        assert_eq!(u32::from(int.ptr), 4);
+        assert!(matches!(
+            int.cmds.as_slice(),
+            [Cmd::Store(Prim::Integer(Integer::I64(2)))]
+        ));
+
+        let name = convert(expr(hir::ExprKind::Name("a".into()), ResTy::I32))
+            .expect("name should be lowered");
+        assert_eq!(u32::from(name.ptr), 12);
+        assert!(matches!(
+            name.cmds.as_slice(),
+            [Cmd::Dupli(size, ptr)] if u32::from(*size) == 4 && u32::from(*ptr) == 0
+        ));
+
+        let res = convert(expr(hir::ExprKind::Res(ResVal::True), ResTy::Bool))
+            .expect("`true` should be lowered");
+        assert_eq!(u32::from(res.ptr), 16);
+        assert!(matches!(
+            res.cmds.as_slice(),
+            [Cmd::Store(Prim::Bool(true))]
+        ));
+
+        let str = convert(expr(
+            hir::ExprKind::Prim(Prim::String("dog".to_owned())),
+            ResTy::Str,
+        ))
+        .expect("string should be lowered");
+        assert!(matches!(str.cmds.as_slice(), [Cmd::Data(0)]));
+
+        assert!(convert(expr(hir::ExprKind::Name("b".into()), ResTy::I32)).is_err());
+        assert!(convert(expr(hir::ExprKind::Tuple(Vec::new()), ResTy::I32)).is_err());
+        assert_eq!(ctx.data.get(0), Some("dog"));
+    }
+}
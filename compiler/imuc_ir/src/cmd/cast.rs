@@ -0,0 +1,72 @@
+use super::{Cmd, FloatBytes, NumBytes, Ptr};
+use crate::prelude::*;
+use imuc_lexer::token::ResTy;
+
+/// Reserved types as seen by the conversion commands
+enum Repr {
+    /// Integers are signed except `U128`, while pointers and booleans are not
+    Int(NumBytes, bool),
+    Float(FloatBytes),
+}
+
+impl Repr {
+    fn of(res: ResTy) -> Result<Self> {
+        let repr = match res {
+            ResTy::I8 => Repr::Int(NumBytes::I8, true),
+            ResTy::I16 => Repr::Int(NumBytes::I16, true),
+            ResTy::I32 => Repr::Int(NumBytes::I32, true),
+            ResTy::I64 => Repr::Int(NumBytes::I64, true),
+            ResTy::I128 => Repr::Int(NumBytes::I128, true),
+            ResTy::U128 => Repr::Int(NumBytes::I128, false),
+            ResTy::Ptr => Repr::Int(NumBytes::I64, false),
+            ResTy::Bool => Repr::Int(NumBytes::I8, false),
+            ResTy::F32 => Repr::Float(FloatBytes::F32),
+            ResTy::F64 => Repr::Float(FloatBytes::F64),
+            _ => return Err(errors::IrError::TypeNotAllowed(format!("{:?}", res)).into()),
+        };
+        Ok(repr)
+    }
+}
+
+fn width(bytes: NumBytes) -> u8 {
+    match bytes {
+        NumBytes::I8 => 1,
+        NumBytes::I16 => 2,
+        NumBytes::I32 => 4,
+        NumBytes::I64 => 8,
+        NumBytes::I128 => 16,
+    }
+}
+
+impl Cmd {
+    /// Selects the command converting the value at the pointer from a reserved type to another,
+    /// following the rules of [`imuc_ast::expr::Cast`]
+    ///
+    /// Returns [`None`] if the bits are kept unchanged, so that no command is needed
+    pub fn cast(from: ResTy, to: ResTy, ptr: Ptr) -> Result<Option<Cmd>> {
+        let float = |res| matches!(res, ResTy::F32 | ResTy::F64);
+        if to == ResTy::Bool
+            || (from == ResTy::Ptr && float(to))
+            || (float(from) && to == ResTy::Ptr)
+        {
+            let cast = format!("{:?} as {:?}", from, to);
+            return Err(errors::IrError::TypeNotAllowed(cast).into());
+        }
+        let cmd = match (Repr::of(from)?, Repr::of(to)?) {
+            (Repr::Int(from, signed), Repr::Int(to, _)) => match width(from).cmp(&width(to)) {
+                std::cmp::Ordering::Less if signed => Some(Cmd::SignExt(from, to, ptr)),
+                std::cmp::Ordering::Less => Some(Cmd::ZeroExt(from, to, ptr)),
+                std::cmp::Ordering::Equal => None,
+                std::cmp::Ordering::Greater => Some(Cmd::Trunc(from, to, ptr)),
+            },
+            (Repr::Int(from, true), Repr::Float(to)) => Some(Cmd::IntToFloat(from, to, ptr)),
+            (Repr::Int(from, false), Repr::Float(to)) => Some(Cmd::UIntToFloat(from, to, ptr)),
+            (Repr::Float(from), Repr::Int(to, true)) => Some(Cmd::FloatToInt(from, to, ptr)),
+            (Repr::Float(from), Repr::Int(to, false)) => Some(Cmd::FloatToUInt(from, to, ptr)),
+            (Repr::Float(FloatBytes::F32), Repr::Float(FloatBytes::F32))
+            | (Repr::Float(FloatBytes::F64), Repr::Float(FloatBytes::F64)) => None,
+            (Repr::Float(from), Repr::Float(to)) => Some(Cmd::FloatConv(from, to, ptr)),
+        };
+        Ok(cmd)
+    }
+}
@@ -20,12 +20,14 @@ impl Deref for CmdBody {
     }
 }
 
+/// Widths of integers, written as `b`, `d`, `q`, `o` and `x` for 1 to 16 bytes
 #[derive(Clone, Copy, Debug)]
 pub enum NumBytes {
     I8,
     I16,
     I32,
     I64,
+    I128,
 }
 
 impl TryFrom<char> for NumBytes {
@@ -37,6 +39,7 @@ impl TryFrom<char> for NumBytes {
             'd' => I16,
             'q' => I32,
             'o' => I64,
+            'x' => I128,
             _ => return Err(errors::IrError::NoSuchCommandMod(value.to_string()).into()),
         };
         Ok(value)
@@ -63,15 +66,99 @@ impl From<NumBytes> for char {
             I16 => 'd',
             I32 => 'q',
             I64 => 'o',
+            I128 => 'x',
         }
     }
 }
 
+/// Widths of floats, written like the float primitives
+#[derive(Clone, Copy, Debug)]
+pub enum FloatBytes {
+    F32,
+    F64,
+}
+
+impl TryFrom<char> for FloatBytes {
+    type Error = Error;
+    fn try_from(value: char) -> std::result::Result<Self, Self::Error> {
+        match value {
+            'f' => Ok(FloatBytes::F32),
+            'l' => Ok(FloatBytes::F64),
+            _ => Err(errors::IrError::NoSuchCommandMod(value.to_string()).into()),
+        }
+    }
+}
+
+impl From<FloatBytes> for char {
+    fn from(value: FloatBytes) -> Self {
+        match value {
+            FloatBytes::F32 => 'f',
+            FloatBytes::F64 => 'l',
+        }
+    }
+}
+
+/// Reads the pair of widths a conversion command converts from and to
+fn read_conv<A, B>(value: &str) -> Result<(A, B)>
+where
+    A: TryFrom<char, Error = Error>,
+    B: TryFrom<char, Error = Error>,
+{
+    let mut chars = value.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(from), Some(to), None) => Ok((from.try_into()?, to.try_into()?)),
+        _ => Err(errors::IrError::NoSuchCommandMod(value.to_owned()).into()),
+    }
+}
+
 #[derive(Clone)]
 pub enum Cmd {
     Dupli(Bytes, Ptr),
     Add(NumBytes, Ptr, Ptr),
     Store(crate::sym::Prim),
+    /// Calls the closure at the pointer indirectly through its code pointer, passing its environment
+    /// followed by the arguments at the second pointer
+    Call(Ptr, Ptr),
+    /// Stops the program unless the [`NumBytes::I64`] index at the first pointer is less than the
+    /// length at the second pointer, guarding indexes into arrays and slices
+    Bound(Ptr, Ptr),
+    /// Pushes the [`ResTy::Str`] pointing at the literal of the index in the module's [`Data`]
+    ///
+    /// [`ResTy::Str`]: imuc_lexer::token::ResTy::Str
+    /// [`Data`]: crate::sym::Data
+    Data(usize),
+    /// Pushes the [`NumBytes::I64`] length in bytes of the string at the pointer
+    StrLen(Ptr),
+    /// Pushes the [`NumBytes::I8`] byte of the string at the first pointer, at the index at the
+    /// second pointer, stopping the program if the index is out of bounds like [`Cmd::Bound`]
+    StrByte(Ptr, Ptr),
+    /// Pushes the string borrowing the bytes of the string at the first pointer, from the start
+    /// index at the second pointer to the end index at the third, stopping the program if the range
+    /// is reversed, out of bounds or does not lie on UTF-8 boundaries
+    StrSlice(Ptr, Ptr, Ptr),
+    /// Compares the bytes of the strings at the pointers lexicographically, pushing a
+    /// [`NumBytes::I8`] of -1, 0 or 1 for less, equal or greater
+    StrCmp(Ptr, Ptr),
+    /// Pushes a new owned string holding the bytes of the first string followed by the second
+    StrCat(Ptr, Ptr),
+    /// Pushes the integer at the pointer sign extended from the first width to the second
+    SignExt(NumBytes, NumBytes, Ptr),
+    /// Pushes the integer at the pointer zero extended from the first width to the second
+    ZeroExt(NumBytes, NumBytes, Ptr),
+    /// Pushes the low bytes of the integer at the pointer, truncated from the first width to the second
+    Trunc(NumBytes, NumBytes, Ptr),
+    /// Pushes the float nearest to the signed integer at the pointer, with ties to even
+    IntToFloat(NumBytes, FloatBytes, Ptr),
+    /// Pushes the float nearest to the unsigned integer at the pointer, with ties to even
+    UIntToFloat(NumBytes, FloatBytes, Ptr),
+    /// Pushes the signed integer of the float at the pointer rounded toward zero, saturating at the
+    /// bounds of the integer, with NaN giving 0
+    FloatToInt(FloatBytes, NumBytes, Ptr),
+    /// Pushes the unsigned integer of the float at the pointer rounded toward zero, saturating at
+    /// the bounds of the integer, with NaN giving 0
+    FloatToUInt(FloatBytes, NumBytes, Ptr),
+    /// Pushes the float at the pointer converted to the other width, rounding to the nearest
+    FloatConv(FloatBytes, FloatBytes, Ptr),
     /// Note that this command should not appear in [`CmdBody`]. It is only used to mark function ends in files
     End,
 }
@@ -98,6 +185,78 @@ impl Rw for Cmd {
                 let prim = crate::sym::Prim::read(&mut input)?;
                 Ok(Self::Store(prim))
             }
+            "cal" => {
+                let fun = Ptr::read(&mut input)?;
+                let args = Ptr::read(&mut input)?;
+                Ok(Self::Call(fun, args))
+            }
+            "bnd" => {
+                let index = Ptr::read(&mut input)?;
+                let len = Ptr::read(&mut input)?;
+                Ok(Self::Bound(index, len))
+            }
+            "dat" => {
+                let index = input.read_until(' ')?;
+                let index = index.parse::<usize>()?;
+                Ok(Self::Data(index))
+            }
+            "sln" => {
+                let str = Ptr::read(&mut input)?;
+                Ok(Self::StrLen(str))
+            }
+            "sbt" => {
+                let str = Ptr::read(&mut input)?;
+                let index = Ptr::read(&mut input)?;
+                Ok(Self::StrByte(str, index))
+            }
+            "ssl" => {
+                let str = Ptr::read(&mut input)?;
+                let start = Ptr::read(&mut input)?;
+                let end = Ptr::read(&mut input)?;
+                Ok(Self::StrSlice(str, start, end))
+            }
+            "scm" => {
+                let lhs = Ptr::read(&mut input)?;
+                let rhs = Ptr::read(&mut input)?;
+                Ok(Self::StrCmp(lhs, rhs))
+            }
+            "sct" => {
+                let lhs = Ptr::read(&mut input)?;
+                let rhs = Ptr::read(&mut input)?;
+                Ok(Self::StrCat(lhs, rhs))
+            }
+            "sxt" => {
+                let (from, to) = read_conv(bytes)?;
+                Ok(Self::SignExt(from, to, Ptr::read(&mut input)?))
+            }
+            "zxt" => {
+                let (from, to) = read_conv(bytes)?;
+                Ok(Self::ZeroExt(from, to, Ptr::read(&mut input)?))
+            }
+            "trn" => {
+                let (from, to) = read_conv(bytes)?;
+                Ok(Self::Trunc(from, to, Ptr::read(&mut input)?))
+            }
+            "itf" => {
+                let (from, to) = read_conv(bytes)?;
+                Ok(Self::IntToFloat(from, to, Ptr::read(&mut input)?))
+            }
+            "utf" => {
+                let (from, to) = read_conv(bytes)?;
+                Ok(Self::UIntToFloat(from, to, Ptr::read(&mut input)?))
+            }
+            "fti" => {
+                let (from, to) = read_conv(bytes)?;
+                Ok(Self::FloatToInt(from, to, Ptr::read(&mut input)?))
+            }
+            "ftu" => {
+                let (from, to) = read_conv(bytes)?;
+                Ok(Self::FloatToUInt(from, to, Ptr::read(&mut input)?))
+            }
+            "fcv" => {
+                let (from, to) = read_conv(bytes)?;
+                Ok(Self::FloatConv(from, to, Ptr::read(&mut input)?))
+            }
             "end" => Ok(Self::End),
             _ => Err(errors::IrError::NoSuchCommand(cmd.to_owned()).into()),
         }
@@ -112,7 +271,6 @@ impl Rw for Cmd {
             }
             Self::Add(bytes, lhs, rhs) => {
                 write!(output, "add{} ", char::from(*bytes))?;
-                write!(output, " ")?;
                 lhs.write(&mut output)?;
                 write!(output, " ")?;
                 rhs.write(&mut output)?;
@@ -121,6 +279,83 @@ impl Rw for Cmd {
                 write!(output, "str ")?;
                 prim.write(&mut output)?;
             }
+            Self::Call(fun, args) => {
+                write!(output, "cal ")?;
+                fun.write(&mut output)?;
+                write!(output, " ")?;
+                args.write(&mut output)?;
+            }
+            Self::Bound(index, len) => {
+                write!(output, "bnd ")?;
+                index.write(&mut output)?;
+                write!(output, " ")?;
+                len.write(&mut output)?;
+            }
+            Self::Data(index) => {
+                write!(output, "dat {}", index)?;
+            }
+            Self::StrLen(str) => {
+                write!(output, "sln ")?;
+                str.write(&mut output)?;
+            }
+            Self::StrByte(str, index) => {
+                write!(output, "sbt ")?;
+                str.write(&mut output)?;
+                write!(output, " ")?;
+                index.write(&mut output)?;
+            }
+            Self::StrSlice(str, start, end) => {
+                write!(output, "ssl ")?;
+                str.write(&mut output)?;
+                write!(output, " ")?;
+                start.write(&mut output)?;
+                write!(output, " ")?;
+                end.write(&mut output)?;
+            }
+            Self::StrCmp(lhs, rhs) => {
+                write!(output, "scm ")?;
+                lhs.write(&mut output)?;
+                write!(output, " ")?;
+                rhs.write(&mut output)?;
+            }
+            Self::StrCat(lhs, rhs) => {
+                write!(output, "sct ")?;
+                lhs.write(&mut output)?;
+                write!(output, " ")?;
+                rhs.write(&mut output)?;
+            }
+            Self::SignExt(from, to, ptr) => {
+                write!(output, "sxt{}{} ", char::from(*from), char::from(*to))?;
+                ptr.write(&mut output)?;
+            }
+            Self::ZeroExt(from, to, ptr) => {
+                write!(output, "zxt{}{} ", char::from(*from), char::from(*to))?;
+                ptr.write(&mut output)?;
+            }
+            Self::Trunc(from, to, ptr) => {
+                write!(output, "trn{}{} ", char::from(*from), char::from(*to))?;
+                ptr.write(&mut output)?;
+            }
+            Self::IntToFloat(from, to, ptr) => {
+                write!(output, "itf{}{} ", char::from(*from), char::from(*to))?;
+                ptr.write(&mut output)?;
+            }
+            Self::UIntToFloat(from, to, ptr) => {
+                write!(output, "utf{}{} ", char::from(*from), char::from(*to))?;
+                ptr.write(&mut output)?;
+            }
+            Self::FloatToInt(from, to, ptr) => {
+                write!(output, "fti{}{} ", char::from(*from), char::from(*to))?;
+                ptr.write(&mut output)?;
+            }
+            Self::FloatToUInt(from, to, ptr) => {
+                write!(output, "ftu{}{} ", char::from(*from), char::from(*to))?;
+                ptr.write(&mut output)?;
+            }
+            Self::FloatConv(from, to, ptr) => {
+                write!(output, "fcv{}{} ", char::from(*from), char::from(*to))?;
+                ptr.write(&mut output)?;
+            }
             Self::End => {
                 write!(output, "end")?;
             }
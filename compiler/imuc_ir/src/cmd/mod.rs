@@ -1,3 +1,4 @@
+mod cast;
 mod cmd;
 mod prim;
 mod ptr;
@@ -6,6 +6,9 @@ impl Rw for Prim {
         let begin = input.read_char()?;
         let prim = match begin {
             'u' => Self::Unit,
+            // Booleans are stored as a single byte of 1 or 0
+            'T' => Self::Bool(true),
+            'F' => Self::Bool(false),
             'b' => {
                 let value = input.read_until(' ')?.parse()?;
                 Self::Integer(Integer::I8(value))
@@ -22,6 +25,14 @@ impl Rw for Prim {
                 let value = input.read_until(' ')?.parse()?;
                 Self::Integer(Integer::I64(value))
             }
+            'x' => {
+                let value = input.read_until(' ')?.parse()?;
+                Self::Integer(Integer::I128(value))
+            }
+            'X' => {
+                let value = input.read_until(' ')?.parse()?;
+                Self::Integer(Integer::U128(value))
+            }
             'f' => {
                 let value = input.read_until(' ')?.parse()?;
                 Self::Float(Float::F32(value))
@@ -65,6 +76,9 @@ impl Rw for Prim {
             Prim::Unit => {
                 write!(output, "u")?;
             }
+            Prim::Bool(value) => {
+                write!(output, "{}", if *value { 'T' } else { 'F' })?;
+            }
             Prim::Integer(integer) => match integer {
                 Integer::I8(value) => {
                     write!(output, "b{}", value)?;
@@ -78,6 +92,12 @@ impl Rw for Prim {
                 Integer::I64(value) => {
                     write!(output, "o{}", value)?;
                 }
+                Integer::I128(value) => {
+                    write!(output, "x{}", value)?;
+                }
+                Integer::U128(value) => {
+                    write!(output, "X{}", value)?;
+                }
             },
             Prim::Float(float) => match float {
                 Float::F32(value) => {
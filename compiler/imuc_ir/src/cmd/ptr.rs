@@ -36,6 +36,12 @@ impl SubAssign for Bytes {
     }
 }
 
+impl From<u32> for Bytes {
+    fn from(value: u32) -> Self {
+        Bytes(value)
+    }
+}
+
 impl From<Bytes> for usize {
     fn from(value: Bytes) -> Self {
         value.0 as usize
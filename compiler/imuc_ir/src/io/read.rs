@@ -6,6 +6,7 @@ pub trait IrRead {
     /// Reads the next char, regarding EOF as an error
     fn read_char(&mut self) -> Result<char>;
     /// Reads until the reader hits [`ch`] or reaches the end of a line, consuming the "until" character
+    /// without returning it
     fn read_until(&mut self, ch: char) -> Result<&str>;
     /// Reads until the reader reaches the end of a line, consuming the rest of the line
     fn read_line(&mut self) -> Result<&str>;
@@ -78,11 +79,14 @@ where
         {
             let mut buf = String::new();
             let value = self.inner.read_line(&mut buf)?;
-            buf.pop().expect("a line contains at least one character");
-            self.line = Some(buf);
             if value == 0 {
                 return Ok(false);
             }
+            if buf.ends_with('\n') {
+                buf.pop();
+            }
+            self.line = Some(buf);
+            self.cursor = 0;
         }
         Ok(true)
     }
@@ -113,6 +117,7 @@ where
         }
         let begin = self.cursor;
         if let Some(line) = &mut self.line {
+            let mut end = line.len();
             while self.cursor < line.len() {
                 let ch = line[self.cursor..]
                     .chars()
@@ -120,10 +125,11 @@ where
                     .expect("chars should not be empty");
                 self.cursor += ch.len_utf8();
                 if ch == until {
+                    end = self.cursor - ch.len_utf8();
                     break;
                 }
             }
-            Ok(&line[begin..self.cursor])
+            Ok(&line[begin..end])
         } else {
             unreachable!()
         }
@@ -188,6 +194,7 @@ impl IrRead for LineReader<'_> {
 
     fn read_until(&mut self, until: char) -> Result<&str> {
         let begin = self.cursor;
+        let mut end = self.value.len();
         while self.cursor < self.value.len() {
             let ch = self.value[self.cursor..]
                 .chars()
@@ -195,13 +202,14 @@ impl IrRead for LineReader<'_> {
                 .expect("chars should not be empty");
             self.cursor += ch.len_utf8();
             if ch == until {
+                end = self.cursor - ch.len_utf8();
                 break;
             }
         }
         if begin == self.cursor {
             Err(errors::IrError::Eof.into())
         } else {
-            Ok(&self.value[begin..self.cursor])
+            Ok(&self.value[begin..end])
         }
     }
 
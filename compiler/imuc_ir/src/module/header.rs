@@ -5,9 +5,15 @@ use std::collections::BTreeMap;
 /// Header for a module, defining interfaces of types and functions
 ///
 /// This is used when referencing to a external module. For compiling new modules, [`Module`](`crate::module::Module`) is used
+///
+/// Besides the functions of the module, the header records the functions it imports from code
+/// written in other languages in [`Self::ext`], and the ABI its functions are exported with in
+/// [`Self::export`]. Each part ends with a line of `%`
 pub struct Header {
     pub ty: BTreeMap<StrRef, Ty>,
     pub fun: BTreeMap<StrRef, FunSig>,
+    pub ext: BTreeMap<StrRef, ExternFun>,
+    pub export: BTreeMap<StrRef, Abi>,
 }
 
 impl Rw for Header {
@@ -15,6 +21,7 @@ impl Rw for Header {
         let mut ty = Vec::new();
         loop {
             if input.peek_line_or_else()? == "%" {
+                input.read_line()?;
                 break;
             }
             ty.push(Ty::read(&mut input)?);
@@ -24,25 +31,65 @@ impl Rw for Header {
         let mut fun = Vec::new();
         loop {
             if input.peek_line_or_else()? == "%" {
+                input.read_line()?;
                 break;
             }
             let name = StrRef::from(input.read_until(' ')?);
             fun.push((name, FunSig::read(&mut input)?));
         }
-        let fun = BTreeMap::from_iter(fun.into_iter());
+        let fun = BTreeMap::from_iter(fun);
 
-        Ok(Header { ty, fun })
+        let mut ext = BTreeMap::new();
+        loop {
+            if input.peek_line_or_else()? == "%" {
+                input.read_line()?;
+                break;
+            }
+            let name = StrRef::from(input.read_until(' ')?);
+            ext.insert(name, ExternFun::read(&mut input)?);
+        }
+
+        let mut export = BTreeMap::new();
+        loop {
+            if input.peek_line_or_else()? == "%" {
+                input.read_line()?;
+                break;
+            }
+            let name = StrRef::from(input.read_until(' ')?);
+            export.insert(name, Abi::read(&mut input)?);
+        }
+
+        Ok(Header {
+            ty,
+            fun,
+            ext,
+            export,
+        })
     }
     fn write(&self, mut output: impl std::io::Write) -> Result<()> {
         for ty in self.ty.values() {
             ty.write(&mut output)?;
+            writeln!(output)?;
         }
-        write!(output, "%\n")?;
+        writeln!(output, "%")?;
         for (name, fun) in self.fun.iter() {
-            write!(output, "{}\n", &**name)?;
+            writeln!(output, "{}", &**name)?;
             fun.write(&mut output)?;
+            writeln!(output)?;
+        }
+        writeln!(output, "%")?;
+        for (name, ext) in self.ext.iter() {
+            writeln!(output, "{}", &**name)?;
+            ext.write(&mut output)?;
+            writeln!(output)?;
+        }
+        writeln!(output, "%")?;
+        for (name, abi) in self.export.iter() {
+            write!(output, "{} ", &**name)?;
+            abi.write(&mut output)?;
+            writeln!(output)?;
         }
-        write!(output, "%\n")?;
+        writeln!(output, "%")?;
         Ok(())
     }
 }
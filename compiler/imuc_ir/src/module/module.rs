@@ -9,11 +9,23 @@ pub struct Module {
     // header's function signatures are reused to in the new field, thus becoming useless
     pub ty: BTreeMap<StrRef, crate::sym::Ty>,
     pub fun: BTreeMap<StrRef, crate::sym::Fun>,
+    pub data: crate::sym::Data,
+    /// Functions implemented outside of the module, which have no bodies
+    pub ext: BTreeMap<StrRef, crate::sym::ExternFun>,
+    /// Functions of the module exported with the ABI, which must be in [`Self::fun`]
+    pub export: BTreeMap<StrRef, crate::sym::Abi>,
 }
 
 impl Module {
-    pub fn split(self) -> (super::Header, Vec<crate::sym::SiglessFun>) {
-        let Module { ty, fun } = self;
+    /// Splits the module into the header, and the data section with the functions of the source
+    pub fn split(self) -> (super::Header, crate::sym::Data, Vec<crate::sym::SiglessFun>) {
+        let Module {
+            ty,
+            fun,
+            data,
+            ext,
+            export,
+        } = self;
         let mut fun_arr = Vec::new();
         let mut sig_arr = Vec::new();
         for (name, sig_fun) in fun.into_iter() {
@@ -24,13 +36,15 @@ impl Module {
         let header = super::Header {
             ty,
             fun: BTreeMap::from_iter(sig_arr),
+            ext,
+            export,
         };
-        (header, fun_arr)
+        (header, data, fun_arr)
     }
 
     /// Writes the module to the path, regardless of the file extension
     pub fn split_to_path(self, base_path: &Path) -> Result<()> {
-        let (header, fun) = self.split();
+        let (header, data, fun) = self.split();
         let header_path = base_path.with_extension(".iuh");
         let src_path = base_path.with_extension(".iuc");
 
@@ -41,14 +55,14 @@ impl Module {
                 .truncate(true)
                 .open(header_path)?,
         )?;
+        let mut src = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(src_path)?;
+        data.write(&mut src)?;
         for fun in fun.into_iter() {
-            fun.write(
-                OpenOptions::new()
-                    .write(true)
-                    .create(true)
-                    .truncate(true)
-                    .open(&src_path)?,
-            )?;
+            fun.write(&mut src)?;
         }
         Ok(())
     }
@@ -88,6 +102,7 @@ where
             mut header,
             mut src,
         } = value;
+        let data = crate::sym::Data::read(&mut src)?;
         let mut fun_arr = Vec::<(StrRef, crate::sym::Fun)>::new();
         while src.peek_line().is_some() {
             let fun = crate::sym::SiglessFun::read(&mut src)?;
@@ -97,12 +112,27 @@ where
                 .ok_or_else(|| errors::IrError::MissingSignature(fun.name.to_string()))?;
             fun_arr.push((fun.name.clone(), (sig, fun).into()));
         }
+        // Functions implemented outside are in their own part of the header, thus only
+        // the functions of the module are required to have bodies
         if let Some((name, _)) = header.fun.pop_first() {
-            Err(errors::IrError::UnimplementedSignature(name.to_string()).into())
-        } else {
-            let fun = BTreeMap::from_iter(fun_arr.into_iter());
-            let super::Header { ty, fun: _fun } = header;
-            Ok(Self { ty, fun })
+            return Err(errors::IrError::UnimplementedSignature(name.to_string()).into());
+        }
+        let fun = BTreeMap::from_iter(fun_arr);
+        let super::Header {
+            ty,
+            fun: _fun,
+            ext,
+            export,
+        } = header;
+        if let Some(name) = export.keys().find(|name| !fun.contains_key(*name)) {
+            return Err(errors::IrError::UnimplementedSignature(name.to_string()).into());
         }
+        Ok(Self {
+            ty,
+            fun,
+            data,
+            ext,
+            export,
+        })
     }
 }
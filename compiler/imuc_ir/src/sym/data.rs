@@ -0,0 +1,72 @@
+use crate::prelude::*;
+use std::collections::HashMap;
+
+/// The module-level data section, holding the contents of string literals
+///
+/// A literal is stored once no matter how many times it appears, and a [`ResTy::Str`] value of
+/// it points into the section. In files, the section is a line of each escaped literal,
+/// ending with a line of `%`
+///
+/// [`ResTy::Str`]: imuc_lexer::token::ResTy::Str
+#[derive(Default)]
+pub struct Data {
+    list: Vec<String>,
+    index: HashMap<String, usize>,
+}
+
+impl Data {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the literal to the section if absent, returning its index
+    pub fn insert(&mut self, value: &str) -> usize {
+        if let Some(index) = self.index.get(value) {
+            return *index;
+        }
+        let index = self.list.len();
+        self.list.push(value.to_owned());
+        self.index.insert(value.to_owned(), index);
+        index
+    }
+
+    /// Gets the literal at the index
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.list.get(index).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+}
+
+impl Rw for Data {
+    fn read(mut input: impl IrRead) -> Result<Self> {
+        let mut data = Self::new();
+        loop {
+            let line = input.read_line()?;
+            if line == "%" {
+                break;
+            }
+            let value = line
+                .strip_prefix('"')
+                .and_then(|line| line.strip_suffix('"'))
+                .ok_or(errors::IrError::Unmatched('"', '"'))?;
+            let value = unescape::unescape(value)
+                .ok_or_else(|| errors::IrError::UnknownEscape(value.to_owned()))?;
+            data.insert(&value);
+        }
+        Ok(data)
+    }
+    fn write(&self, mut output: impl std::io::Write) -> Result<()> {
+        for value in self.list.iter() {
+            writeln!(output, "{:?}", value)?;
+        }
+        writeln!(output, "%")?;
+        Ok(())
+    }
+}
@@ -32,6 +32,34 @@ pub struct FunSig {
     pub ret: super::Ty,
 }
 
+/// The calling convention of a function shared with code written in other languages
+///
+/// With [`Abi::C`], the elements of the parameter tuple are passed as the arguments of a C
+/// function in order, and types are laid out as described in [`TyInner::c_layout`]
+///
+/// [`TyInner::c_layout`]: super::ty::TyInner::c_layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Abi {
+    C,
+}
+
+impl std::str::FromStr for Abi {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "C" => Ok(Abi::C),
+            _ => Err(errors::IrError::NoSuchAbi(s.to_owned()).into()),
+        }
+    }
+}
+
+/// A function implemented outside of the module, which is linked by its name
+pub struct ExternFun {
+    pub abi: Abi,
+    pub sig: FunSig,
+}
+
 impl Rw for Fun {
     fn read(mut input: impl IrRead) -> Result<Self> {
         let name = StrRef::from(input.read_line()?);
@@ -73,3 +101,29 @@ impl Rw for FunSig {
         Ok(())
     }
 }
+
+impl Rw for Abi {
+    fn read(mut input: impl IrRead) -> Result<Self> {
+        input.read_until(' ')?.parse()
+    }
+    fn write(&self, mut output: impl std::io::Write) -> Result<()> {
+        match self {
+            Abi::C => write!(output, "C")?,
+        }
+        Ok(())
+    }
+}
+
+impl Rw for ExternFun {
+    fn read(mut input: impl IrRead) -> Result<Self> {
+        let abi = Abi::read(&mut input)?;
+        let sig = FunSig::read(&mut input)?;
+        Ok(Self { abi, sig })
+    }
+    fn write(&self, mut output: impl std::io::Write) -> Result<()> {
+        self.abi.write(&mut output)?;
+        writeln!(output)?;
+        self.sig.write(&mut output)?;
+        Ok(())
+    }
+}
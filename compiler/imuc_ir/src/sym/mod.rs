@@ -1,6 +1,8 @@
+pub mod data;
 pub mod fun;
 pub mod ty;
 
-pub use fun::{Fun, FunSig, SiglessFun};
+pub use data::Data;
+pub use fun::{Abi, ExternFun, Fun, FunSig, SiglessFun};
 pub use imuc_ast::prim::Prim;
 pub use ty::Ty;
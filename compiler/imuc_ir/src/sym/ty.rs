@@ -1,3 +1,4 @@
+use crate::cmd::Bytes;
 use crate::io::LineReader;
 use crate::prelude::*;
 use imuc_lexer::token::ResTy;
@@ -38,8 +39,13 @@ pub enum TyKind {
     Res(ResTy),
     Tuple(Tuple),
     Struct(Struct),
+    Union(Union),
     Ref(TyItem),
     Ptr(TyItem),
+    Fun(FunTy),
+    Array(Array),
+    /// A pointer to the first element and the number of elements as [`ResTy::I64`]
+    Slice(TyItem),
 }
 
 /// A type item included in the definition of another type
@@ -57,6 +63,233 @@ pub struct Tuple(pub Vec<TyItem>);
 #[derive(Clone)]
 pub struct Struct(pub BTreeMap<StrRef, TyItem>);
 
+/// A tagged union type, holding one of the variants at a time
+///
+/// The discriminant of a variant is its index in the list, stored as an integer of type
+/// [`Self::tag`] before the payload. Variants without a payload only store the discriminant
+#[derive(Clone)]
+pub struct Union {
+    pub tag: ResTy,
+    pub variants: Vec<(StrRef, Option<TyItem>)>,
+}
+
+impl Union {
+    /// Creates a union with the smallest discriminant type that fits all variants
+    pub fn new(variants: Vec<(StrRef, Option<TyItem>)>) -> Self {
+        let tag = match variants.len() {
+            len if len <= 1 << 8 => ResTy::I8,
+            len if len <= 1 << 16 => ResTy::I16,
+            len if len as u64 <= 1 << 32 => ResTy::I32,
+            _ => ResTy::I64,
+        };
+        Self { tag, variants }
+    }
+
+    /// Returns the discriminant of the variant with the name
+    pub fn discriminant(&self, name: &str) -> Option<usize> {
+        self.variants
+            .iter()
+            .position(|(variant, _)| &**variant == name)
+    }
+}
+
+/// A pointer to the code of a function taking the parameter and returning the result
+///
+/// A closure is a struct of its environment and such a pointer, whose parameter is a tuple of
+/// a reference to the environment followed by the arguments, so that callers invoke the code
+/// indirectly without knowing what is captured
+#[derive(Clone)]
+pub struct FunTy {
+    pub param: TyItem,
+    pub ret: TyItem,
+}
+
+/// An array type, holding a fixed number of elements next to each other
+#[derive(Clone)]
+pub struct Array {
+    pub item: TyItem,
+    pub len: u64,
+}
+
+/// The size of pointers, references and code pointers
+pub const PTR_SIZE: u32 = 8;
+
+impl TyItem {
+    /// Returns the size of the type, which must have been resolved
+    pub fn size(&self) -> Result<Bytes> {
+        match self {
+            Self::Solid(ty) => ty.size(),
+            Self::Pending(name) => Err(errors::MemoryError::UnknownHandle(name.to_string()).into()),
+        }
+    }
+
+    /// Returns the C layout of the type, which must have been resolved
+    pub fn c_layout(&self) -> Result<CLayout> {
+        match self {
+            Self::Solid(ty) => ty.c_layout(),
+            Self::Pending(name) => Err(errors::MemoryError::UnknownHandle(name.to_string()).into()),
+        }
+    }
+}
+
+/// The layout of a type shared with C, with the offset of every element in bytes
+///
+/// Only compounds have offsets, see [`TyInner::c_layout`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CLayout {
+    pub size: u32,
+    pub align: u32,
+    pub offsets: Vec<u32>,
+}
+
+impl CLayout {
+    fn scalar(size: u32) -> Self {
+        Self {
+            size,
+            align: size.max(1),
+            offsets: Vec::new(),
+        }
+    }
+
+    /// Lays out the elements next to each other like the members of a C struct, padding each
+    /// to its alignment and the whole to the largest alignment
+    fn compound(items: impl IntoIterator<Item = CLayout>) -> Result<Self> {
+        let overflow = || errors::MemoryError::OverflowError(u32::MAX as usize);
+        let mut size = 0u32;
+        let mut align = 1;
+        let mut offsets = Vec::new();
+        for item in items {
+            size = size
+                .checked_next_multiple_of(item.align)
+                .ok_or_else(overflow)?;
+            offsets.push(size);
+            size = size.checked_add(item.size).ok_or_else(overflow)?;
+            align = align.max(item.align);
+        }
+        let size = size.checked_next_multiple_of(align).ok_or_else(overflow)?;
+        Ok(Self {
+            size,
+            align,
+            offsets,
+        })
+    }
+}
+
+/// Returns the size of a reserved type
+fn res_size(res: ResTy) -> Result<u32> {
+    let size = match res {
+        ResTy::I8 | ResTy::Bool => 1,
+        ResTy::I16 => 2,
+        ResTy::I32 | ResTy::F32 => 4,
+        ResTy::I64 | ResTy::F64 => 8,
+        ResTy::I128 | ResTy::U128 => 16,
+        ResTy::Ptr => PTR_SIZE,
+        ResTy::SelfType => return Err(errors::MemoryError::UnexpectedSelf.into()),
+        // A pointer to the UTF-8 bytes followed by the length as an `I64`
+        ResTy::Str => PTR_SIZE + 8,
+    };
+    Ok(size)
+}
+
+/// Sums up the sizes of the types laid out next to each other
+fn sum_sizes<'a>(items: impl IntoIterator<Item = &'a TyItem>) -> Result<u32> {
+    let mut sum = 0u32;
+    for item in items {
+        sum = sum
+            .checked_add(item.size()?.into())
+            .ok_or(errors::MemoryError::OverflowError(u32::MAX as usize))?;
+    }
+    Ok(sum)
+}
+
+impl TyInner {
+    /// Computes the number of bytes a value of the type takes
+    ///
+    /// Elements are laid out next to each other without padding, and a union takes the size of its
+    /// tag followed by its largest payload
+    pub fn size(&self) -> Result<Bytes> {
+        let overflow = || errors::MemoryError::OverflowError(u32::MAX as usize);
+        let size = match &self.kind {
+            TyKind::Res(res) => res_size(*res)?,
+            TyKind::Ref(_) | TyKind::Ptr(_) | TyKind::Fun(_) => PTR_SIZE,
+            TyKind::Slice(_) => PTR_SIZE + 8,
+            TyKind::Tuple(tuple) => sum_sizes(tuple.0.iter())?,
+            TyKind::Struct(cus) => sum_sizes(cus.0.values())?,
+            TyKind::Union(union) => {
+                let mut payload = 0;
+                for item in union.variants.iter().filter_map(|(_, item)| item.as_ref()) {
+                    payload = payload.max(u32::from(item.size()?));
+                }
+                res_size(union.tag)?
+                    .checked_add(payload)
+                    .ok_or_else(overflow)?
+            }
+            TyKind::Array(array) => {
+                let item = u32::from(array.item.size()?);
+                u32::try_from(array.len)
+                    .ok()
+                    .and_then(|len| len.checked_mul(item))
+                    .ok_or_else(overflow)?
+            }
+        };
+        Ok(Bytes::from(size))
+    }
+}
+
+impl TyInner {
+    /// Computes the layout of the type when passed to or shared with C code
+    ///
+    /// Unlike [`Self::size`], elements are padded to their natural alignment, which is the size of
+    /// scalars. Tuples are laid out as C structs with the elements as members in order, and so
+    /// are [`ResTy::Str`] and slices as a pointer followed by an `I64`. Arrays are C arrays, and a
+    /// union is a C struct of its tag followed by a C union of the payloads. Structs are keyed
+    /// by names and have no order of elements, thus no C layout, and C structs are written as
+    /// tuples instead
+    pub fn c_layout(&self) -> Result<CLayout> {
+        let overflow = || errors::MemoryError::OverflowError(u32::MAX as usize);
+        let pair = || CLayout::compound([CLayout::scalar(PTR_SIZE), CLayout::scalar(8)]);
+        let layout = match &self.kind {
+            TyKind::Res(ResTy::Str) => pair()?,
+            TyKind::Res(res) => CLayout::scalar(res_size(*res)?),
+            TyKind::Ref(_) | TyKind::Ptr(_) | TyKind::Fun(_) => CLayout::scalar(PTR_SIZE),
+            TyKind::Slice(_) => pair()?,
+            TyKind::Tuple(tuple) => {
+                let items = tuple.0.iter().map(TyItem::c_layout);
+                CLayout::compound(items.collect::<Result<Vec<_>>>()?)?
+            }
+            TyKind::Array(array) => {
+                let item = array.item.c_layout()?;
+                let size = u32::try_from(array.len)
+                    .ok()
+                    .and_then(|len| len.checked_mul(item.size))
+                    .ok_or_else(overflow)?;
+                CLayout {
+                    size,
+                    align: item.align,
+                    offsets: Vec::new(),
+                }
+            }
+            TyKind::Union(union) => {
+                let mut payload = CLayout::scalar(0);
+                for item in union.variants.iter().filter_map(|(_, item)| item.as_ref()) {
+                    let item = item.c_layout()?;
+                    payload.size = payload.size.max(item.size);
+                    payload.align = payload.align.max(item.align);
+                }
+                payload.size = payload
+                    .size
+                    .checked_next_multiple_of(payload.align)
+                    .ok_or_else(overflow)?;
+                CLayout::compound([CLayout::scalar(res_size(union.tag)?), payload])?
+            }
+            TyKind::Struct(_) => {
+                return Err(errors::IrError::NoCLayout(self.name.to_string()).into())
+            }
+        };
+        Ok(layout)
+    }
+}
+
 impl Rw for ResTy {
     fn read(mut input: impl IrRead) -> Result<Self> {
         let name = input.read_until(' ')?;
@@ -65,10 +298,13 @@ impl Rw for ResTy {
             "I16" => ResTy::I16,
             "I32" => ResTy::I32,
             "I64" => ResTy::I64,
+            "I128" => ResTy::I128,
+            "U128" => ResTy::U128,
             "F32" => ResTy::F32,
             "F64" => ResTy::F64,
             "Str" => ResTy::Str,
             "Ptr" => ResTy::Ptr,
+            "Bool" => ResTy::Bool,
             _ => return Err(errors::IrError::NoSuchType(name.to_owned()).into()),
         };
         Ok(res)
@@ -79,10 +315,13 @@ impl Rw for ResTy {
             ResTy::I16 => "I16",
             ResTy::I32 => "I32",
             ResTy::I64 => "I64",
+            ResTy::I128 => "I128",
+            ResTy::U128 => "U128",
             ResTy::F32 => "F32",
             ResTy::F64 => "F64",
             ResTy::Str => "Str",
             ResTy::Ptr => "Ptr",
+            ResTy::Bool => "Bool",
             _ => return Err(errors::IrError::TypeNotAllowed(format!("{:?}", self)).into()),
         };
         write!(output, "{}", str)?;
@@ -130,6 +369,36 @@ impl Rw for Ty {
                     external: input.external(),
                 }))
             }
+            '^' => {
+                let (param, ret) = content[1..]
+                    .split_once('>')
+                    .ok_or(errors::IrError::CharRequired('>'))?;
+                let param = TyItem::read(LineReader::new(param, external))?;
+                let ret = TyItem::read(LineReader::new(ret, external))?;
+                Ok(Ty::new(TyInner {
+                    name,
+                    kind: TyKind::Fun(FunTy { param, ret }),
+                    external: input.external(),
+                }))
+            }
+            '[' => {
+                if content.chars().next_back().is_none_or(|ch| ch != ']') {
+                    return Err(errors::IrError::Unmatched('[', ']').into());
+                }
+                let content = &content[1..content.len() - 1];
+                let kind = match content.split_once(';') {
+                    Some((item, len)) => TyKind::Array(Array {
+                        item: TyItem::read(LineReader::new(item, external))?,
+                        len: len.parse()?,
+                    }),
+                    None => TyKind::Slice(TyItem::read(LineReader::new(content, external))?),
+                };
+                Ok(Ty::new(TyInner {
+                    name,
+                    kind,
+                    external: input.external(),
+                }))
+            }
             '(' => {
                 if content.chars().next_back().is_none_or(|ch| ch != ')') {
                     return Err(errors::IrError::Unmatched('(', ')').into());
@@ -176,6 +445,33 @@ impl Rw for Ty {
                     external: input.external(),
                 }))
             }
+            '<' => {
+                if content.chars().next_back().is_none_or(|ch| ch != '>') {
+                    return Err(errors::IrError::Unmatched('<', '>').into());
+                }
+                let content = &content[1..content.len() - 1];
+                let (tag, values) = content
+                    .split_once('|')
+                    .ok_or(errors::IrError::CharRequired('|'))?;
+                let tag = ResTy::read(LineReader::new(tag, external))?;
+                let mut variants = Vec::new();
+                for value in values.split(',') {
+                    let variant = match value.split_once(':') {
+                        Some((_, "")) => return Err(errors::IrError::CharRequired(':').into()),
+                        Some((name, item)) => (
+                            StrRef::from(name),
+                            Some(TyItem::read(LineReader::new(item, external))?),
+                        ),
+                        None => (StrRef::from(value), None),
+                    };
+                    variants.push(variant);
+                }
+                Ok(Ty::new(TyInner {
+                    name,
+                    kind: TyKind::Union(Union { tag, variants }),
+                    external: input.external(),
+                }))
+            }
             _ => {
                 let res = ResTy::read(LineReader::new(content, external))?;
                 // TODO: Reuse reserved type definitions to save space
@@ -202,6 +498,22 @@ impl Rw for Ty {
                 write!(output, "@")?;
                 ty.write(output)?;
             }
+            TyKind::Fun(fun) => {
+                write!(output, "^")?;
+                fun.param.write(&mut output)?;
+                write!(output, ">")?;
+                fun.ret.write(&mut output)?;
+            }
+            TyKind::Array(array) => {
+                write!(output, "[")?;
+                array.item.write(&mut output)?;
+                write!(output, ";{}]", array.len)?;
+            }
+            TyKind::Slice(item) => {
+                write!(output, "[")?;
+                item.write(&mut output)?;
+                write!(output, "]")?;
+            }
             TyKind::Tuple(tuple) => {
                 write!(output, "(")?;
                 for (is_last, ty) in tuple
@@ -225,7 +537,7 @@ impl Rw for Ty {
                     .enumerate()
                     .map(|(i, value)| (i + 1 == cus.0.len(), value))
                 {
-                    write!(output, "{}=", &**name)?;
+                    write!(output, "{}:", &**name)?;
                     ty.write(&mut output)?;
                     if !is_last {
                         write!(output, ",")?;
@@ -233,6 +545,22 @@ impl Rw for Ty {
                 }
                 write!(output, "}}")?;
             }
+            TyKind::Union(union) => {
+                write!(output, "<")?;
+                union.tag.write(&mut output)?;
+                write!(output, "|")?;
+                for (i, (name, item)) in union.variants.iter().enumerate() {
+                    if i != 0 {
+                        write!(output, ",")?;
+                    }
+                    write!(output, "{}", &**name)?;
+                    if let Some(item) = item {
+                        write!(output, ":")?;
+                        item.write(&mut output)?;
+                    }
+                }
+                write!(output, ">")?;
+            }
         }
         Ok(())
     }
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ir::cmd::*;
+    use imuc_ir::io::{LineReader, Rw};
+    use imuc_lexer::token::ResTy;
+
+    #[test]
+    fn rw_conv() {
+        for line in [
+            "sxtbo 8",
+            "zxtbd 0",
+            "trnoq 16",
+            "itfql 4",
+            "ftifd 0",
+            "utfxl 0",
+            "ftulx 8",
+            "fcvlf 8",
+            "sxtox 0",
+            "addx 0 16",
+            "str x-170141183460469231731687303715884105728",
+            "str X340282366920938463463374607431768211455",
+        ] {
+            let cmd = Cmd::read(LineReader::new(line, false)).expect("conversion should be read");
+            let mut output = Vec::new();
+            cmd.write(&mut output)
+                .expect("conversion should be written");
+            assert_eq!(String::from_utf8(output).unwrap(), line);
+        }
+        assert!(Cmd::read(LineReader::new("sxtb 8", false)).is_err());
+        assert!(Cmd::read(LineReader::new("itfqo 8", false)).is_err());
+    }
+
+    #[test]
+    fn cast() {
+        let cast = |from, to| {
+            let cmd = Cmd::cast(from, to, Ptr::default()).expect("cast should be allowed");
+            cmd.map(|cmd| {
+                let mut output = Vec::new();
+                cmd.write(&mut output)
+                    .expect("conversion should be written");
+                String::from_utf8(output).unwrap()
+            })
+        };
+        assert_eq!(cast(ResTy::I8, ResTy::I64).as_deref(), Some("sxtbo 0"));
+        assert_eq!(cast(ResTy::Ptr, ResTy::I32).as_deref(), Some("trnoq 0"));
+        assert_eq!(cast(ResTy::Bool, ResTy::I16).as_deref(), Some("zxtbd 0"));
+        assert_eq!(cast(ResTy::I64, ResTy::Ptr), None);
+        assert_eq!(cast(ResTy::I32, ResTy::F64).as_deref(), Some("itfql 0"));
+        assert_eq!(cast(ResTy::F32, ResTy::I8).as_deref(), Some("ftifb 0"));
+        assert_eq!(cast(ResTy::F64, ResTy::F32).as_deref(), Some("fcvlf 0"));
+        assert_eq!(cast(ResTy::F64, ResTy::F64), None);
+        assert_eq!(cast(ResTy::I64, ResTy::I128).as_deref(), Some("sxtox 0"));
+        assert_eq!(cast(ResTy::Ptr, ResTy::U128).as_deref(), Some("zxtox 0"));
+        assert_eq!(cast(ResTy::U128, ResTy::I32).as_deref(), Some("trnxq 0"));
+        assert_eq!(cast(ResTy::I128, ResTy::U128), None);
+        // `U128::MAX as F64` is near 3.4e38 rather than the -1.0 of its bits read as signed
+        assert_eq!(cast(ResTy::U128, ResTy::F64).as_deref(), Some("utfxl 0"));
+        assert_eq!(cast(ResTy::F64, ResTy::U128).as_deref(), Some("ftulx 0"));
+        assert_eq!(cast(ResTy::I128, ResTy::F64).as_deref(), Some("itfxl 0"));
+
+        assert!(Cmd::cast(ResTy::I8, ResTy::Bool, Ptr::default()).is_err());
+        assert!(Cmd::cast(ResTy::Ptr, ResTy::F64, Ptr::default()).is_err());
+        assert!(Cmd::cast(ResTy::Str, ResTy::I64, Ptr::default()).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ir::cmd::Cmd;
+    use imuc_ir::io::{IrReader, LineReader, Rw};
+    use imuc_ir::sym::Data;
+
+    #[test]
+    fn rw_data() {
+        let mut data = Data::new();
+        assert_eq!(data.insert("hello"), 0);
+        assert_eq!(data.insert("line\n\"quoted\""), 1);
+        assert_eq!(data.insert("hello"), 0);
+        assert_eq!(data.len(), 2);
+
+        let mut output = Vec::new();
+        data.write(&mut output).expect("data should be written");
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, "\"hello\"\n\"line\\n\\\"quoted\\\"\"\n%\n");
+
+        let data =
+            Data::read(IrReader::new(output.as_bytes(), false)).expect("data should be read");
+        assert_eq!(data.get(0), Some("hello"));
+        assert_eq!(data.get(1), Some("line\n\"quoted\""));
+        assert_eq!(data.get(2), None);
+        assert!(Data::read(IrReader::new("hello\n%\n".as_bytes(), false)).is_err());
+        assert!(Data::read(IrReader::new("\"hello\"\n".as_bytes(), false)).is_err());
+    }
+
+    #[test]
+    fn rw_str_cmd() {
+        for line in [
+            "addo 8 0",
+            "dat 3",
+            "sln 8",
+            "sbt 16 0",
+            "ssl 24 8 0",
+            "scm 16 0",
+            "sct 16 0",
+        ] {
+            let cmd = Cmd::read(LineReader::new(line, false)).expect("command should be read");
+            let mut output = Vec::new();
+            cmd.write(&mut output).expect("command should be written");
+            assert_eq!(String::from_utf8(output).unwrap(), line);
+        }
+        assert!(Cmd::read(LineReader::new("dat x", false)).is_err());
+    }
+
+    #[test]
+    fn rw_bool() {
+        for line in ["str T", "str F"] {
+            let cmd = Cmd::read(LineReader::new(line, false)).expect("boolean should be read");
+            let mut output = Vec::new();
+            cmd.write(&mut output).expect("boolean should be written");
+            assert_eq!(String::from_utf8(output).unwrap(), line);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ir::io::{IrReader, Rw};
+    use imuc_ir::module::{Header, Module, ModuleBuilder};
+    use imuc_ir::sym::Abi;
+
+    const HEADER: &str = "\
+Int I64
+%
+main
+Arg (I64)
+Ret I64
+%
+puts
+C
+Param (Ptr)
+Int I32
+%
+main C
+%
+";
+
+    #[test]
+    fn rw_header() {
+        let header =
+            Header::read(IrReader::new(HEADER.as_bytes(), false)).expect("header should be read");
+        assert_eq!(header.ty.len(), 1);
+        assert!(header.fun.contains_key("main"));
+        let puts = header
+            .ext
+            .get("puts")
+            .expect("extern function should be read");
+        assert_eq!(puts.abi, Abi::C);
+        assert_eq!(&*puts.sig.ret.name, "Int");
+        assert_eq!(header.export.get("main"), Some(&Abi::C));
+
+        let mut output = Vec::new();
+        header.write(&mut output).expect("header should be written");
+        let output = String::from_utf8(output).unwrap();
+        let header =
+            Header::read(IrReader::new(output.as_bytes(), false)).expect("header should be reread");
+        assert_eq!(header.ext.len(), 1);
+        assert_eq!(header.export.len(), 1);
+
+        assert!("Rust".parse::<Abi>().is_err());
+    }
+
+    #[test]
+    fn build_extern() {
+        let build = |header: &str, src: &str| {
+            let header = Header::read(IrReader::new(header.as_bytes(), false))
+                .expect("header should be read");
+            Module::try_from(ModuleBuilder {
+                header,
+                src: IrReader::new(src.as_bytes(), false),
+            })
+        };
+        // Extern functions need no bodies
+        let module =
+            build("%\n%\nputs\nC\nP (Ptr)\nR I32\n%\n%\n", "%\n").expect("module should be built");
+        assert!(module.fun.is_empty());
+        assert!(module.ext.contains_key("puts"));
+        // Exported functions need bodies
+        assert!(build("%\n%\n%\nmain C\n%\n", "%\n").is_err());
+        assert!(build("%\nmain\nU (I64)\nU I64\n%\n%\n%\n", "%\n").is_err());
+    }
+}
@@ -0,0 +1,20 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ir::io::{IrRead, IrReader, LineReader};
+
+    #[test]
+    fn read_until() {
+        let mut line = LineReader::new("dup 8 0", false);
+        assert_eq!(line.read_until(' ').expect("command should be read"), "dup");
+        assert_eq!(line.read_until(' ').expect("size should be read"), "8");
+        assert_eq!(line.read_until(' ').expect("pointer should be read"), "0");
+        assert!(line.read_until(' ').is_err());
+
+        // The last line may miss its line break
+        let mut reader = IrReader::new("ab cd\nef".as_bytes(), false);
+        assert_eq!(reader.read_until(' ').expect("word should be read"), "ab");
+        assert_eq!(reader.read_until(' ').expect("word should be read"), "cd");
+        assert_eq!(reader.read_until(' ').expect("word should be read"), "ef");
+        assert!(reader.read_char().is_err());
+    }
+}
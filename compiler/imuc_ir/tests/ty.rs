@@ -0,0 +1,191 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ir::io::{LineReader, Rw};
+    use imuc_ir::sym::ty::*;
+    use imuc_lexer::token::ResTy;
+
+    #[test]
+    fn read_union() {
+        let ty = Ty::read(LineReader::new("Shape <I8|Circle:Round,Empty>", false))
+            .expect("union should be read");
+        let TyKind::Union(union) = &ty.kind else {
+            panic!("expected a union");
+        };
+        assert_eq!(union.tag, ResTy::I8);
+        assert_eq!(union.discriminant("Circle"), Some(0));
+        assert_eq!(union.discriminant("Empty"), Some(1));
+        assert!(matches!(
+            union.variants.as_slice(),
+            [(_, Some(TyItem::Pending(item))), (_, None)] if &**item == "Round"
+        ));
+
+        assert!(Ty::read(LineReader::new("Shape <I8|Circle:Round", false)).is_err());
+        assert!(Ty::read(LineReader::new("Shape <Circle:Round>", false)).is_err());
+    }
+
+    #[test]
+    fn write_union() {
+        let ty = Ty::new(TyInner {
+            name: "Shape".into(),
+            kind: TyKind::Union(Union::new(vec![
+                ("Circle".into(), Some(TyItem::Pending("Round".into()))),
+                ("Empty".into(), None),
+            ])),
+            external: false,
+        });
+        let mut output = Vec::new();
+        ty.write(&mut output).expect("union should be written");
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Shape <I8|Circle:Round,Empty>"
+        );
+    }
+
+    #[test]
+    fn rw_fun() {
+        let ty =
+            Ty::read(LineReader::new("Code ^Param>I64", false)).expect("code type should be read");
+        let TyKind::Fun(fun) = &ty.kind else {
+            panic!("expected a code pointer");
+        };
+        assert!(matches!(&fun.param, TyItem::Pending(param) if &**param == "Param"));
+        assert!(Ty::read(LineReader::new("Code ^Param", false)).is_err());
+
+        let ty = Ty::new(TyInner {
+            name: "Code".into(),
+            kind: TyKind::Fun(FunTy {
+                param: TyItem::Pending("Param".into()),
+                ret: TyItem::Pending("I64".into()),
+            }),
+            external: false,
+        });
+        let mut output = Vec::new();
+        ty.write(&mut output).expect("code type should be written");
+        assert_eq!(String::from_utf8(output).unwrap(), "Code ^Param>I64");
+    }
+
+    #[test]
+    fn rw_array() {
+        let ty = Ty::read(LineReader::new("Arr [I64;4]", false)).expect("array should be read");
+        assert!(matches!(&ty.kind, TyKind::Array(array) if array.len == 4));
+        let ty = Ty::read(LineReader::new("View [I64]", false)).expect("slice should be read");
+        assert!(matches!(&ty.kind, TyKind::Slice(TyItem::Pending(item)) if &**item == "I64"));
+        assert!(Ty::read(LineReader::new("Arr [I64;4", false)).is_err());
+        assert!(Ty::read(LineReader::new("Arr [I64;n]", false)).is_err());
+
+        let ty = Ty::new(TyInner {
+            name: "Arr".into(),
+            kind: TyKind::Array(Array {
+                item: TyItem::Pending("I64".into()),
+                len: 4,
+            }),
+            external: false,
+        });
+        let mut output = Vec::new();
+        ty.write(&mut output).expect("array should be written");
+        assert_eq!(String::from_utf8(output).unwrap(), "Arr [I64;4]");
+    }
+
+    #[test]
+    fn size() {
+        let res = |res| {
+            TyItem::Solid(Ty::new(TyInner {
+                name: format!("{:?}", res).into(),
+                kind: TyKind::Res(res),
+                external: false,
+            }))
+        };
+        let new = |kind| {
+            Ty::new(TyInner {
+                name: "T".into(),
+                kind,
+                external: false,
+            })
+        };
+        let size = |ty: &Ty| usize::from(ty.size().expect("size should be computed"));
+
+        let pair = new(TyKind::Tuple(Tuple(vec![res(ResTy::I8), res(ResTy::F64)])));
+        assert_eq!(size(&pair), 9);
+        let array = new(TyKind::Array(Array {
+            item: TyItem::Solid(pair.clone()),
+            len: 3,
+        }));
+        assert_eq!(size(&array), 27);
+        assert_eq!(size(&new(TyKind::Slice(TyItem::Solid(pair)))), 16);
+        let union = new(TyKind::Union(Union::new(vec![
+            ("A".into(), Some(TyItem::Solid(array))),
+            ("B".into(), None),
+        ])));
+        assert_eq!(size(&union), 28);
+        assert_eq!(size(&new(TyKind::Res(ResTy::Str))), 16);
+        assert_eq!(size(&new(TyKind::Res(ResTy::U128))), 16);
+
+        assert!(new(TyKind::Array(Array {
+            item: TyItem::Pending("I64".into()),
+            len: 2,
+        }))
+        .size()
+        .is_err());
+        assert!(new(TyKind::Array(Array {
+            item: res(ResTy::I64),
+            len: u64::MAX,
+        }))
+        .size()
+        .is_err());
+    }
+    #[test]
+    fn c_layout() {
+        let res = |res| {
+            TyItem::Solid(Ty::new(TyInner {
+                name: format!("{:?}", res).into(),
+                kind: TyKind::Res(res),
+                external: false,
+            }))
+        };
+        let new = |kind| {
+            Ty::new(TyInner {
+                name: "T".into(),
+                kind,
+                external: false,
+            })
+        };
+        let layout = |ty: &Ty| ty.c_layout().expect("layout should be computed");
+
+        // struct { int8_t a; double b; int16_t c; }
+        let tuple = new(TyKind::Tuple(Tuple(vec![
+            res(ResTy::I8),
+            res(ResTy::F64),
+            res(ResTy::I16),
+        ])));
+        assert_eq!(
+            layout(&tuple),
+            CLayout {
+                size: 24,
+                align: 8,
+                offsets: vec![0, 8, 16]
+            }
+        );
+        let array = new(TyKind::Array(Array {
+            item: TyItem::Solid(tuple.clone()),
+            len: 2,
+        }));
+        assert_eq!((layout(&array).size, layout(&array).align), (48, 8));
+        let union = new(TyKind::Union(Union::new(vec![
+            ("A".into(), Some(res(ResTy::I32))),
+            ("B".into(), Some(res(ResTy::I16))),
+        ])));
+        assert_eq!(
+            layout(&union),
+            CLayout {
+                size: 8,
+                align: 4,
+                offsets: vec![0, 4]
+            }
+        );
+        assert_eq!(layout(&new(TyKind::Res(ResTy::Str))).offsets, [0, 8]);
+        assert_eq!(layout(&new(TyKind::Tuple(Tuple(Vec::new())))).size, 0);
+
+        let cus = new(TyKind::Struct(Struct(Default::default())));
+        assert!(cus.c_layout().is_err());
+    }
+}
@@ -17,6 +17,15 @@ lazy_static::lazy_static! {
         ac.insert("if", TokenKind::Keyword(Keyword::If));
         ac.insert("else", TokenKind::Keyword(Keyword::Else));
         ac.insert("loop", TokenKind::Keyword(Keyword::Loop));
+        ac.insert("break", TokenKind::Keyword(Keyword::Break));
+        ac.insert("continue", TokenKind::Keyword(Keyword::Continue));
+        ac.insert("return", TokenKind::Keyword(Keyword::Return));
+        ac.insert("while", TokenKind::Keyword(Keyword::While));
+        ac.insert("in", TokenKind::Keyword(Keyword::In));
+        ac.insert("match", TokenKind::Keyword(Keyword::Match));
+        ac.insert("mod", TokenKind::Keyword(Keyword::Mod));
+        ac.insert("extern", TokenKind::Keyword(Keyword::Extern));
+        ac.insert("interface", TokenKind::Keyword(Keyword::Interface));
         ac.insert("true", TokenKind::ResVal(ResVal::True));
         ac.insert("false", TokenKind::ResVal(ResVal::False));
         ac.insert("self", TokenKind::ResVal(ResVal::SelfValue));
@@ -30,11 +39,26 @@ lazy_static::lazy_static! {
         ac.insert("F64", TokenKind::ResTy(ResTy::F64));
         ac.insert("Str", TokenKind::ResTy(ResTy::Str));
         ac.insert("I128", TokenKind::ResTy(ResTy::I128));
+        ac.insert("U128", TokenKind::ResTy(ResTy::U128));
+        ac.insert("Bool", TokenKind::ResTy(ResTy::Bool));
         ac.insert("inf", TokenKind::Literal(Literal::Float));
         ac.build()
     };
 }
 
+/// Returns whether the token may end an operand, after which `-` is always an operator
+fn ends_operand(kind: TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Literal(_)
+            | TokenKind::Ident(Ident::Value | Ident::Type | Ident::Unused)
+            | TokenKind::ResVal(_)
+            | TokenKind::ResTy(_)
+            | TokenKind::Pair(Pair::RightParen | Pair::RightBracket)
+            | TokenKind::Symbol(Symbol::Question)
+    )
+}
+
 impl<I> Iterator for Reader<I>
 where
     I: Iterator<Item = char>,
@@ -43,6 +67,10 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         let token = self.next_token();
+        match token.kind {
+            TokenKind::Comment(_) | TokenKind::Spacing(_) | TokenKind::Stray => {}
+            kind => self.operand = ends_operand(kind),
+        }
         if token.kind == TokenKind::Eof {
             None
         } else {
@@ -79,6 +107,7 @@ where
                 EOF => Token::new(TokenKind::Eof, self.diff(begin)),
 
                 '_' => Token::new(self.next_unused(), self.diff(begin)),
+                '\'' => Token::new(self.next_label(), self.diff(begin)),
                 '\"' => Token::new(self.next_string(), self.diff(begin)),
 
                 '(' => Token::new(TokenKind::Pair(Pair::LeftParen), self.diff(begin)),
@@ -89,36 +118,84 @@ where
                 '}' => Token::new(TokenKind::Pair(Pair::RightBrace), self.diff(begin)),
 
                 '@' => Token::new(TokenKind::UnOp(UnOp::Ref), self.diff(begin)),
+                '#' => Token::new(TokenKind::Symbol(Symbol::Hash), self.diff(begin)),
+                '?' => Token::new(TokenKind::Symbol(Symbol::Question), self.diff(begin)),
 
-                '+' => Token::new(TokenKind::BinOp(BinOp::Add), self.diff(begin)),
+                '+' => Token::new(self.next_op(BinOp::Add), self.diff(begin)),
+                // A sign only starts a number where an operand is expected, so `x-1` subtracts
                 '-' => {
-                    if self.first().is_ascii_digit() {
+                    if self.first().is_ascii_digit() && !self.operand {
                         let ch = self.next_char();
                         Token::new(self.next_number(ch), self.diff(begin))
                     } else {
-                        Token::new(TokenKind::BinOp(BinOp::Sub), self.diff(begin))
+                        Token::new(self.next_op(BinOp::Sub), self.diff(begin))
                     }
                 }
-                '*' => Token::new(TokenKind::BinOp(BinOp::Mul), self.diff(begin)),
+                '*' => Token::new(self.next_op(BinOp::Mul), self.diff(begin)),
                 '/' => match self.first() {
                     '/' => Token::new(self.next_comment(), self.diff(begin)),
                     '*' => Token::new(self.next_multi_comment(), self.diff(begin)),
-                    _ => Token::new(TokenKind::BinOp(BinOp::Div), self.diff(begin)),
+                    _ => Token::new(self.next_op(BinOp::Div), self.diff(begin)),
                 },
-                '%' => Token::new(TokenKind::BinOp(BinOp::Mod), self.diff(begin)),
-                '|' => Token::new(TokenKind::BinOp(BinOp::Or), self.diff(begin)),
-                '&' => Token::new(TokenKind::BinOp(BinOp::And), self.diff(begin)),
-                '^' => Token::new(TokenKind::BinOp(BinOp::Xor), self.diff(begin)),
+                '%' => Token::new(self.next_op(BinOp::Mod), self.diff(begin)),
+                '|' => Token::new(self.next_op(BinOp::Or), self.diff(begin)),
+                '&' => Token::new(self.next_op(BinOp::And), self.diff(begin)),
+                '^' => Token::new(self.next_op(BinOp::Xor), self.diff(begin)),
                 ':' => Token::new(TokenKind::Symbol(Symbol::Colon), self.diff(begin)),
                 ',' => Token::new(TokenKind::Symbol(Symbol::Comma), self.diff(begin)),
-                '.' => Token::new(TokenKind::Symbol(Symbol::Dot), self.diff(begin)),
-                '=' => Token::new(TokenKind::Symbol(Symbol::Assign), self.diff(begin)),
+                '.' => match self.first() {
+                    '.' => {
+                        self.advance();
+                        if self.first() == '=' {
+                            self.advance();
+                            Token::new(TokenKind::BinOp(BinOp::RangeEq), self.diff(begin))
+                        } else {
+                            Token::new(TokenKind::BinOp(BinOp::Range), self.diff(begin))
+                        }
+                    }
+                    _ => Token::new(TokenKind::Symbol(Symbol::Dot), self.diff(begin)),
+                },
+                '=' => match self.first() {
+                    '=' => {
+                        self.advance();
+                        Token::new(TokenKind::BinOp(BinOp::Eq), self.diff(begin))
+                    }
+                    '>' => {
+                        self.advance();
+                        Token::new(TokenKind::Symbol(Symbol::Arrow), self.diff(begin))
+                    }
+                    _ => Token::new(TokenKind::Symbol(Symbol::Assign), self.diff(begin)),
+                },
+                '<' => match self.first() {
+                    '=' => {
+                        self.advance();
+                        Token::new(TokenKind::BinOp(BinOp::Le), self.diff(begin))
+                    }
+                    _ => Token::new(TokenKind::BinOp(BinOp::Lt), self.diff(begin)),
+                },
+                '>' => match self.first() {
+                    '=' => {
+                        self.advance();
+                        Token::new(TokenKind::BinOp(BinOp::Ge), self.diff(begin))
+                    }
+                    _ => Token::new(TokenKind::BinOp(BinOp::Gt), self.diff(begin)),
+                },
                 ';' => Token::new(TokenKind::Semicolon, self.diff(begin)),
                 _ => Token::new(TokenKind::LexError(LexError::UnknownChar), self.diff(begin)),
             }
         }
     }
 
+    /// Returns the compound assignment if the operator is followed by '=', or the operator itself
+    fn next_op(&mut self, op: BinOp) -> TokenKind {
+        if self.first() == '=' {
+            self.advance();
+            TokenKind::AssignOp(op)
+        } else {
+            TokenKind::BinOp(op)
+        }
+    }
+
     fn next_comment(&mut self) -> TokenKind {
         self.advance();
         self.advance_while(|reader| {
@@ -175,7 +252,8 @@ where
                     self.advance_while(|reader| reader.first().is_ascii_hexdigit());
                     TokenKind::Literal(Literal::Integer)
                 }
-                '.' => {
+                // A dot not followed by digits is not a part of the number, like "0..n"
+                '.' if self.second().is_ascii_digit() => {
                     self.advance();
                     self.advance_while(|reader| reader.first().is_ascii_digit());
                     TokenKind::Literal(Literal::Float)
@@ -184,7 +262,7 @@ where
             },
             '1'..='9' => {
                 self.advance_while(|reader| reader.first().is_ascii_digit());
-                if self.first() == '.' {
+                if self.first() == '.' && self.second().is_ascii_digit() {
                     self.advance();
                     self.advance_while(|reader| reader.first().is_ascii_digit());
                     if self.first() == 'e' {
@@ -303,4 +381,15 @@ where
         });
         TokenKind::Ident(Ident::Unused)
     }
+
+    fn next_label(&mut self) -> TokenKind {
+        if !self.first().is_alphabetic() {
+            return TokenKind::LexError(LexError::UnknownChar);
+        }
+        self.advance_while(|reader| {
+            let ch = reader.first();
+            ch.is_alphanumeric() || ch == '_'
+        });
+        TokenKind::Ident(Ident::Label)
+    }
 }
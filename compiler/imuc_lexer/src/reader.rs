@@ -85,6 +85,8 @@ where
     iter: I,
     cursor: usize,
     queue: PeekQueue,
+    /// Whether the last token read, apart from spacing and comments, ends an operand
+    pub(crate) operand: bool,
 }
 
 impl<I> Reader<I>
@@ -97,6 +99,7 @@ where
             iter: iter.into_iter(),
             cursor: 0,
             queue: PeekQueue::new(),
+            operand: false,
         }
     }
 
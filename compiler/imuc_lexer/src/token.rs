@@ -10,6 +10,8 @@ pub enum TokenKind {
     ResVal(ResVal),
     Pair(Pair),
     BinOp(BinOp),
+    /// A compound assignment like `+=`, holding the operator applied before assigning
+    AssignOp(BinOp),
     UnOp(UnOp),
     Symbol(Symbol),
     LexError(LexError),
@@ -43,12 +45,13 @@ pub enum Literal {
     MultiString,
 }
 
-/// A part of [`TokenKind`] for identifiers(values / types / ignore names)
+/// A part of [`TokenKind`] for identifiers(values / types / ignore names / loop labels)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Ident {
     Value,
     Type,
     Unused,
+    Label,
 }
 
 /// A part of [`TokenKind`] for keywords
@@ -66,6 +69,15 @@ pub enum Keyword {
     If,
     Else,
     Loop,
+    Break,
+    Continue,
+    Return,
+    While,
+    In,
+    Match,
+    Mod,
+    Extern,
+    Interface,
 }
 
 /// A part of [`TokenKind`] for values using reserved names
@@ -85,10 +97,13 @@ pub enum ResTy {
     I32,
     I64,
     I128,
+    /// The only unsigned integer, for hashes and fixed-point values needing every bit
+    U128,
     Ptr,
     F32,
     F64,
     Str,
+    Bool,
 }
 
 /// A part of [`TokenKind`] for left / right bracket
@@ -118,12 +133,16 @@ pub enum BinOp {
     Le,
     Gt,
     Ge,
+    Range,
+    RangeEq,
 }
 
 /// A part of [`TokenKind`] for unary operators
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum UnOp {
     Ref,
+    /// A negation `-`, lexed as [`BinOp::Sub`] and told apart by the parser
+    Neg,
 }
 
 /// A part of [`TokenKind`] for parser structure symbol
@@ -133,6 +152,10 @@ pub enum Symbol {
     Comma,
     Dot,
     Assign,
+    Arrow,
+    Hash,
+    /// The `?` after a value, propagating its `None` or `Err` out of the function
+    Question,
 }
 
 /// A part of [`TokenKind`] for errors that may happen in lexer
@@ -66,4 +66,103 @@ pub mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_lexer_range() {
+        use token::*;
+
+        let text = "0..n 1..=2.5<=x==y 'outer=>";
+        let reader = Reader::new(text.chars());
+        let mut pos = 0;
+        let tokens = reader
+            .map(|token| {
+                let str = &text[pos..pos + token.len];
+                pos += token.len;
+                (token.kind, str)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            [
+                (TokenKind::Literal(Literal::Integer), "0"),
+                (TokenKind::BinOp(BinOp::Range), ".."),
+                (TokenKind::Ident(Ident::Value), "n"),
+                (TokenKind::Spacing(Spacing::Indent), " "),
+                (TokenKind::Literal(Literal::Integer), "1"),
+                (TokenKind::BinOp(BinOp::RangeEq), "..="),
+                (TokenKind::Literal(Literal::Float), "2.5"),
+                (TokenKind::BinOp(BinOp::Le), "<="),
+                (TokenKind::Ident(Ident::Value), "x"),
+                (TokenKind::BinOp(BinOp::Eq), "=="),
+                (TokenKind::Ident(Ident::Value), "y"),
+                (TokenKind::Spacing(Spacing::Indent), " "),
+                (TokenKind::Ident(Ident::Label), "'outer"),
+                (TokenKind::Symbol(Symbol::Arrow), "=>"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_assign_op() {
+        use token::*;
+
+        let text = "x+=1 y-=z/=-2 a|b";
+        let reader = Reader::new(text.chars());
+        let mut pos = 0;
+        let tokens = reader
+            .map(|token| {
+                let str = &text[pos..pos + token.len];
+                pos += token.len;
+                (token.kind, str)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            [
+                (TokenKind::Ident(Ident::Value), "x"),
+                (TokenKind::AssignOp(BinOp::Add), "+="),
+                (TokenKind::Literal(Literal::Integer), "1"),
+                (TokenKind::Spacing(Spacing::Indent), " "),
+                (TokenKind::Ident(Ident::Value), "y"),
+                (TokenKind::AssignOp(BinOp::Sub), "-="),
+                (TokenKind::Ident(Ident::Value), "z"),
+                (TokenKind::AssignOp(BinOp::Div), "/="),
+                (TokenKind::Literal(Literal::Integer), "-2"),
+                (TokenKind::Spacing(Spacing::Indent), " "),
+                (TokenKind::Ident(Ident::Value), "a"),
+                (TokenKind::BinOp(BinOp::Or), "|"),
+                (TokenKind::Ident(Ident::Value), "b"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_sign() {
+        use token::*;
+
+        let text = "x-1 (-2)-3 f(a)[0]-4";
+        let reader = Reader::new(text.chars());
+        let tokens = reader
+            .filter(|token| token.kind != TokenKind::Spacing(Spacing::Indent))
+            .map(|token| token.kind)
+            .collect::<Vec<_>>();
+        let int = TokenKind::Literal(Literal::Integer);
+        let sub = TokenKind::BinOp(BinOp::Sub);
+        let value = TokenKind::Ident(Ident::Value);
+        let (left, right) = (
+            TokenKind::Pair(Pair::LeftParen),
+            TokenKind::Pair(Pair::RightParen),
+        );
+        let (open, close) = (
+            TokenKind::Pair(Pair::LeftBracket),
+            TokenKind::Pair(Pair::RightBracket),
+        );
+        assert_eq!(
+            tokens,
+            [
+                value, sub, int, left, int, right, sub, int, value, left, value, right, open, int,
+                close, sub, int
+            ]
+        );
+    }
 }
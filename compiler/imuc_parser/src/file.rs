@@ -27,8 +27,7 @@ where
     I: Iterator<Item = Token> + Send + Sync,
 {
     fn into_arc_str(s: String) -> Arc<str> {
-        let s = Box::into_raw(s.into_boxed_str());
-        unsafe { Arc::from_raw(s) }
+        Arc::from(s)
     }
 
     /// Creates a file reader with given file name, content, and reader over tokens
@@ -57,6 +56,10 @@ where
         self.reader.next().and_then(|token| {
             let next_index = self.index + token.len;
             let value = self.content.get(self.index..next_index)?;
+            let loc = imuc_ast::Loc {
+                line: self.info.line,
+                column: self.info.column,
+            };
 
             self.index = next_index;
             match token.kind {
@@ -71,6 +74,7 @@ where
             Some(crate::ParserInput {
                 kind: token.kind,
                 value,
+                loc,
             })
         })
     }
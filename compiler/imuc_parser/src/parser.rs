@@ -9,6 +9,8 @@ where
 {
     seq: I,
     stack: Option<ParserInput<'s>>,
+    /// The last token consumed
+    last: Option<ParserInput<'s>>,
     pub look_up: imuc_ast::name::LookUp,
     pub resolver: imuc_path::Resolver,
     _phantom: std::marker::PhantomData<&'s str>,
@@ -23,6 +25,7 @@ where
         Self {
             seq: seq.into_iter(),
             stack: None,
+            last: None,
             look_up: Default::default(),
             resolver: Default::default(),
             _phantom: Default::default(),
@@ -36,7 +39,7 @@ where
         if let Some(ref input) = self.stack {
             Ok(Some(*input))
         } else {
-            let input = self.next_token()?;
+            let input = self.fetch()?;
             self.stack = input;
             Ok(input)
         }
@@ -52,6 +55,7 @@ where
         let input = self.peek()?;
         if input.is_some_and(|input| kind.contains(&input.kind)) {
             self.stack = None;
+            self.last = input;
             Ok(input)
         } else {
             Ok(None)
@@ -61,10 +65,23 @@ where
     /// Gets the next token, if any, while mapping the possible errors
     /// If the token is an error, an [`Err`] result is returned
     pub fn next_token(&mut self) -> Result<Option<ParserInput<'s>>> {
-        if let Some(input) = std::mem::take(&mut self.stack) {
-            return Ok(Some(input));
+        let input = match std::mem::take(&mut self.stack) {
+            Some(input) => Some(input),
+            None => self.fetch()?,
+        };
+        if input.is_some() {
+            self.last = input;
         }
+        Ok(input)
+    }
+
+    /// Returns the last token consumed, if any
+    pub fn last(&self) -> Option<ParserInput<'s>> {
+        self.last
+    }
 
+    /// Reads a token from the underlying sequence, taking lexer errors as errors
+    fn fetch(&mut self) -> Result<Option<ParserInput<'s>>> {
         let input = self.seq.next();
         if let Some(input) = input {
             if let TokenKind::LexError(error) = input.kind {
@@ -1,11 +1,12 @@
 use imuc_error::*;
 use imuc_lexer::TokenKind;
 
-/// The direct input to parser, holding [`TokenKind`] and its corresponding string slice
+/// The direct input to parser, holding [`TokenKind`], its corresponding string slice and location
 #[derive(Debug, Clone, Copy)]
 pub struct ParserInput<'s> {
     pub kind: TokenKind,
     pub value: &'s str,
+    pub loc: imuc_ast::Loc,
 }
 
 /// Trait for types that can be applied to a parser
@@ -12,7 +12,7 @@ pub trait Priority {
 impl Priority for UnOp {
     fn priority(&self) -> u8 {
         match self {
-            Self::Ref => 5,
+            Self::Ref | Self::Neg => 5,
         }
     }
 
@@ -30,6 +30,7 @@ impl Priority for BinOp {
             Self::Xor => 11,
             Self::Or => 12,
             Self::Eq | Self::Lt | Self::Le | Self::Gt | Self::Ge => 13,
+            Self::Range | Self::RangeEq => 14,
         }
     }
 
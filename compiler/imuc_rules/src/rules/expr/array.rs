@@ -0,0 +1,63 @@
+use crate::prelude::*;
+use imuc_lexer::token::{Pair, Symbol};
+
+/// Parses an array literal, like `[a, b, c]` or `[val; len]`
+pub struct ArrayExprRule;
+
+impl Rule for ArrayExprRule {
+    type Output = expr::Array;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        if parser
+            .next_if(&TokenKind::Pair(Pair::LeftBracket))?
+            .is_none()
+        {
+            return Ok(None);
+        }
+        let mut elem = Vec::new();
+        let mut comma = true;
+        loop {
+            if parser
+                .next_if(&TokenKind::Pair(Pair::RightBracket))?
+                .is_some()
+            {
+                break;
+            } else if !comma {
+                return Err(parser.map_err(errors::SyntaxError::ExpectedToken {
+                    expect: TokenKind::Pair(Pair::RightBracket),
+                }));
+            }
+
+            let expr = rules::ExprRule {
+                end: TokenKind::Symbol(Symbol::Comma),
+            }
+            .parse(parser)?
+            .ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "Expr".to_owned(),
+                    context: "array expression".to_owned(),
+                })
+            })?;
+
+            // Only the first element may be repeated
+            if elem.is_empty() && parser.next_if(&TokenKind::Semicolon)?.is_some() {
+                let len = rules::ArrayLenRule.parse(parser)?.ok_or_else(|| {
+                    parser.map_err(errors::SyntaxError::ExpectedAfter {
+                        expect: "array length".to_owned(),
+                        after: TokenKind::Semicolon,
+                    })
+                })?;
+                parser.next_expected(&TokenKind::Pair(Pair::RightBracket))?;
+                return Ok(Some(expr::Array::Repeat(Box::new(expr), len)));
+            }
+
+            comma = parser.next_if(&TokenKind::Symbol(Symbol::Comma))?.is_some();
+
+            elem.push(expr);
+        }
+        Ok(Some(expr::Array::List(elem)))
+    }
+}
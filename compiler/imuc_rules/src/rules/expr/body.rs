@@ -1,8 +1,25 @@
 use crate::prelude::*;
-use imuc_lexer::token::Pair;
+use imuc_lexer::token::{Ident, Keyword, Pair, Symbol};
 
+lazy_tokens!(FunNameTokens, Ident::Value, Ident::Unused);
+
+/// Parses a body like `{ let a = b; c; d }`, where lets and expressions are separated by `;`
+/// or by line breaks, unless they end with `}`
 pub struct BodyRule;
 
+/// Returns whether the next token is apart from the last one consumed, by a line break or by
+/// being after a `}`
+fn separated<'s, I>(parser: &mut Parser<'s, I>) -> Result<bool>
+where
+    I: ParserSequence<'s>,
+{
+    let (Some(last), Some(next)) = (parser.last(), parser.peek()?) else {
+        return Ok(true);
+    };
+    let line = last.loc.line + last.value.matches('\n').count();
+    Ok(last.kind == TokenKind::Pair(Pair::RightBrace) || next.loc.line > line)
+}
+
 #[derive(Default)]
 struct BodyElem {
     bind: Vec<bind::Bind>,
@@ -31,9 +48,12 @@ impl Rule for BodyRule {
             let mut stack = vec![BodyElem::default()];
             let mut bind_seq = true;
             let mut unit = false;
+            // Whether the last let or expression is separated from what follows
+            let mut sep = true;
             loop {
                 while parser.next_if(&TokenKind::Semicolon)?.is_some() {
                     unit = true;
+                    sep = true;
                 }
                 if parser
                     .next_if(&TokenKind::Pair(Pair::RightBrace))?
@@ -41,8 +61,68 @@ impl Rule for BodyRule {
                 {
                     break;
                 }
+                if !sep && !separated(parser)? {
+                    return parser.error(errors::SyntaxError::ExpectedToken {
+                        expect: TokenKind::Semicolon,
+                    });
+                }
                 unit = false;
-                if let Some(bind) = rules::BindRule.parse(parser)? {
+                // "for" starts a loop inside bodies, while it only starts an item in modules
+                let is_for = parser
+                    .peek()?
+                    .is_some_and(|input| input.kind == TokenKind::Keyword(Keyword::For));
+                // "fun" starts a function item if a name follows, or a closure otherwise
+                let is_fun = parser
+                    .peek()?
+                    .is_some_and(|input| input.kind == TokenKind::Keyword(Keyword::Fun));
+                // Attributes only start items inside bodies
+                let is_attr = parser
+                    .peek()?
+                    .is_some_and(|input| input.kind == TokenKind::Symbol(Symbol::Hash));
+                let mut closure = None;
+                let bind = if is_for {
+                    None
+                } else if is_attr {
+                    let item = rules::ItemRule.parse(parser)?.ok_or_else(|| {
+                        parser.map_err(errors::SyntaxError::ExpectedAfter {
+                            expect: "Item".to_owned(),
+                            after: TokenKind::Symbol(Symbol::Hash),
+                        })
+                    })?;
+                    Some(bind::Bind::Item(item))
+                } else if is_fun {
+                    parser.next_some()?;
+                    if let Some(name) = parser.next_if(&FunNameTokens)? {
+                        let fun = rules::FunRule.parse(parser)?.ok_or_else(|| {
+                            parser.map_err(errors::SyntaxError::ExpectedIn {
+                                expect: "Fun".to_owned(),
+                                context: "function definition".to_owned(),
+                            })
+                        })?;
+                        Some(bind::Bind::Item(item::Item {
+                            attrs: Vec::new(),
+                            public: module::Public::Priv,
+                            name: parser.look_up.insert(name.value),
+                            kind: item::ItemKind::Fun(fun),
+                        }))
+                    } else {
+                        let val = rules::ClosureRule.parse(parser)?.ok_or_else(|| {
+                            parser.map_err(errors::SyntaxError::ExpectedIn {
+                                expect: "Closure".to_owned(),
+                                context: "closure expression".to_owned(),
+                            })
+                        })?;
+                        closure = rules::PostfixExprRule {
+                            val: expr::Expr::Closure(val),
+                        }
+                        .parse(parser)?;
+                        None
+                    }
+                } else {
+                    rules::BindRule.parse(parser)?
+                };
+                if let Some(bind) = bind {
+                    sep = matches!(bind, bind::Bind::Item(_));
                     if bind_seq {
                         stack.last_mut().unwrap().bind.push(bind);
                     } else {
@@ -54,20 +134,26 @@ impl Rule for BodyRule {
                         bind_seq = true;
                     }
                 } else {
-                    let expr = rules::ExprRule { end: () }.parse(parser)?.ok_or_else(|| {
-                        parser.map_err(errors::SyntaxError::ExpectedIn {
-                            expect: "Expr or Bind".to_owned(),
-                            context: "expression body".to_owned(),
-                        })
-                    })?;
+                    // A closure starting a statement is only followed by postfixes
+                    let expr = match closure {
+                        Some(expr) => expr,
+                        None => rules::ExprRule { end: () }.parse(parser)?.ok_or_else(|| {
+                            parser.map_err(errors::SyntaxError::ExpectedIn {
+                                expect: "Expr or Bind".to_owned(),
+                                context: "expression body".to_owned(),
+                            })
+                        })?,
+                    };
                     stack.last_mut().unwrap().body.push(expr);
                     bind_seq = false;
+                    sep = false;
                 }
             }
             // Set the unit-ness for the most inner body
             stack.last_mut().unwrap().unit = unit;
 
-            let body = stack.into_iter().fold(None, |inner, mut elem| {
+            // Bindings following expressions open a scope nested as the last expression of the outer one
+            let body = stack.into_iter().rev().fold(None, |inner, mut elem| {
                 if let Some(inner) = inner {
                     elem.body.push(inner);
                 }
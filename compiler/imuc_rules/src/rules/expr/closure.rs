@@ -0,0 +1,43 @@
+use crate::prelude::*;
+use imuc_lexer::token::Symbol;
+
+/// Only invoked after the keyword `fun` is consumed in an expression
+///
+/// Parses the arguments of a closure, followed by either `=> val` or a body
+pub struct ClosureRule;
+
+impl Rule for ClosureRule {
+    type Output = expr::Closure;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        let args = rules::PatRule.parse(parser)?.ok_or_else(|| {
+            parser.map_err(errors::SyntaxError::ExpectedIn {
+                expect: "Pat".to_owned(),
+                context: "closure arguments".to_owned(),
+            })
+        })?;
+        let body = if parser.next_if(&TokenKind::Symbol(Symbol::Arrow))?.is_some() {
+            rules::ExprRule { end: () }.parse(parser)?.ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedAfter {
+                    expect: "Expr".to_owned(),
+                    after: TokenKind::Symbol(Symbol::Arrow),
+                })
+            })?
+        } else {
+            let body = rules::BodyRule.parse(parser)?.ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "Body or =>".to_owned(),
+                    context: "closure".to_owned(),
+                })
+            })?;
+            expr::Expr::Body(body)
+        };
+        Ok(Some(expr::Closure {
+            args,
+            body: Box::new(body),
+        }))
+    }
+}
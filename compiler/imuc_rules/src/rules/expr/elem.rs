@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use imuc_lexer::token::Keyword;
 
 pub struct ElemExprRule;
 
@@ -11,14 +12,28 @@ impl Rule for ElemExprRule {
     {
         if let Some(prim) = rules::PrimRule.parse(parser)? {
             Ok(Some(expr::Expr::Prim(prim)))
+        } else if let Some(value) = rules::BoolRule.parse(parser)? {
+            Ok(Some(expr::Expr::Prim(prim::Prim::Bool(value))))
         } else if let Some(value) = rules::ValueRule.parse(parser)? {
             Ok(Some(expr::Expr::Value(value)))
         } else if let Some(body) = rules::BodyRule.parse(parser)? {
             Ok(Some(expr::Expr::Body(body)))
         } else if let Some(flow) = rules::FlowRule.parse(parser)? {
             Ok(Some(expr::Expr::Flow(flow)))
+        } else if let Some(expr) = rules::ForRule.parse(parser)? {
+            Ok(Some(expr))
         } else if let Some(tuple) = rules::TupleExprRule.parse(parser)? {
             Ok(Some(tuple))
+        } else if parser.next_if(&TokenKind::Keyword(Keyword::Fun))?.is_some() {
+            let closure = rules::ClosureRule.parse(parser)?.ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "Closure".to_owned(),
+                    context: "closure expression".to_owned(),
+                })
+            })?;
+            Ok(Some(expr::Expr::Closure(closure)))
+        } else if let Some(array) = rules::ArrayExprRule.parse(parser)? {
+            Ok(Some(expr::Expr::Array(array)))
         } else if let Some(struct_stmt) = rules::StructExprRule.parse(parser)? {
             Ok(Some(expr::Expr::Struct(struct_stmt)))
         } else {
@@ -1,13 +1,18 @@
 use crate::prelude::*;
 use crate::Priority;
-use imuc_lexer::token::Pair;
+use imuc_lexer::token::{BinOp, Keyword, Pair, Symbol, UnOp};
 use imuc_parser::TokenKindSet;
 
 lazy_tokens!(EndTokens, Pair::RightParen, Pair::RightBracket, Pair::RightBrace and Semicolon);
+lazy_tokens!(UnOpTokens, UnOp::Ref);
 
 /// [`Self::end`] defines the token to end the expression when meet
 ///
-/// The expression will end anyway if it meets open right brackets ')', ']', '}' or ';' (as a suffix)
+/// The expression will end anyway if it meets open right brackets ')', ']', '}' or ';' (as a suffix),
+/// or if an operand is not followed by an operator. The ending token is never consumed.
+/// An assignment is an expression too, so `place = val` is parsed unless '=' is an ending token
+///
+/// If no operand or operator starts the expression, an [`Ok(None)`] is returned
 pub struct ExprRule<T>
 where
     T: for<'a> TokenKindSet<'a>,
@@ -25,8 +30,8 @@ fn merge_symbols(op: TokenKind, stack: &mut Vec<expr::Expr>) -> Result<()> {
             }));
         }
         TokenKind::BinOp(op) => {
-            let lhs = stack.pop().ok_or(errors::SyntaxError::TooManyOp)?;
             let rhs = stack.pop().ok_or(errors::SyntaxError::TooManyOp)?;
+            let lhs = stack.pop().ok_or(errors::SyntaxError::TooManyOp)?;
             stack.push(expr::Expr::BinExpr(expr::BinExpr {
                 op,
                 lhs: Box::new(lhs),
@@ -40,6 +45,22 @@ fn merge_symbols(op: TokenKind, stack: &mut Vec<expr::Expr>) -> Result<()> {
     Ok(())
 }
 
+/// Pushes an operator to the operator stack, merging all previous operators that bind earlier
+fn push_op(op: TokenKind, ops: &mut Vec<TokenKind>, stack: &mut Vec<expr::Expr>) -> Result<()> {
+    while ops.last().is_some_and(|last| {
+        if last.is_right() {
+            last.priority() < op.priority()
+        } else {
+            last.priority() <= op.priority()
+        }
+    }) {
+        let last = ops.pop().expect("op should not be empty after checking");
+        merge_symbols(last, stack)?;
+    }
+    ops.push(op);
+    Ok(())
+}
+
 impl<T> Rule for ExprRule<T>
 where
     T: for<'a> TokenKindSet<'a>,
@@ -50,48 +71,128 @@ where
     where
         I: ParserSequence<'s>,
     {
-        let end = (self.end, EndTokens);
-        let mut stack = Vec::new();
-        let mut op: Vec<TokenKind> = Vec::new();
-        loop {
-            if let Some(item) = rules::ElemExprRule.parse(parser)? {
-                stack.push(item);
-            } else {
-                let input = parser.next_some()?;
-                match input.kind {
-                    TokenKind::UnOp(_) | TokenKind::BinOp(_) => {
-                        while op.last().is_some_and(|op| {
-                            if op.is_right() {
-                                op.priority() < input.kind.priority()
-                            } else {
-                                op.priority() <= input.kind.priority()
-                            }
-                        }) {
-                            let op = op.pop().expect("op should not be empty after checking");
-                            merge_symbols(op, &mut stack).map_err(|err| parser.map_err(err))?;
-                        }
-                        op.push(input.kind);
-                    }
-                    _ => {
-                        if end.contains(&input.kind) {
-                            break;
-                        } else {
-                            return parser.error(errors::SyntaxError::ExpectedIn {
-                                expect: "Expr or Op".to_owned(),
-                                context: "expression".to_owned(),
-                            });
-                        }
-                    }
-                }
-            }
+        parse_assign(parser, &(self.end, EndTokens))
+    }
+}
+
+/// Parses an assignment like `place = val` or `place += val`, which binds the loosest and to the right
+fn parse_assign<'s, I, T>(parser: &mut Parser<'s, I>, end: &T) -> Result<Option<expr::Expr>>
+where
+    I: ParserSequence<'s>,
+    T: for<'a> TokenKindSet<'a>,
+{
+    let Some(place) = parse_ops(parser, end)? else {
+        return Ok(None);
+    };
+    let op = match parser.peek()? {
+        Some(input) if !end.contains(&input.kind) => match input.kind {
+            TokenKind::Symbol(Symbol::Assign) => None,
+            TokenKind::AssignOp(op) => Some(op),
+            _ => return Ok(Some(place)),
+        },
+        _ => return Ok(Some(place)),
+    };
+    let input = parser.next_some()?;
+    let val = parse_assign(parser, end)?.ok_or_else(|| {
+        parser.map_err(errors::SyntaxError::ExpectedAfter {
+            expect: "Expr".to_owned(),
+            after: input.kind,
+        })
+    })?;
+    Ok(Some(expr::Expr::Assign(expr::Assign {
+        op,
+        place: Box::new(place),
+        val: Box::new(val),
+    })))
+}
+
+/// Applies casts `as Type` to the operand just pushed, if any follow
+///
+/// A cast binds later than the prefix operators of the operand, but earlier than binary operators
+fn parse_cast<'s, I, T>(
+    parser: &mut Parser<'s, I>,
+    end: &T,
+    op: &mut Vec<TokenKind>,
+    stack: &mut Vec<expr::Expr>,
+) -> Result<()>
+where
+    I: ParserSequence<'s>,
+    T: for<'a> TokenKindSet<'a>,
+{
+    let kind = TokenKind::Keyword(Keyword::As);
+    while !end.contains(&kind) && parser.next_if(&kind)?.is_some() {
+        while op
+            .last()
+            .is_some_and(|last| matches!(last, TokenKind::UnOp(_)))
+        {
+            let last = op.pop().expect("op should not be empty after checking");
+            merge_symbols(last, stack).map_err(|err| parser.map_err(err))?;
         }
-        for op in op.into_iter().rev() {
-            merge_symbols(op, &mut stack).map_err(|err| parser.map_err(err))?;
+        let ty = rules::TypeRule.parse(parser)?.ok_or_else(|| {
+            parser.map_err(errors::SyntaxError::ExpectedAfter {
+                expect: "Type".to_owned(),
+                after: kind,
+            })
+        })?;
+        let val = stack.pop().expect("the operand should be pushed");
+        stack.push(expr::Expr::Cast(expr::Cast {
+            val: Box::new(val),
+            ty,
+        }));
+    }
+    Ok(())
+}
+
+/// Parses operands joined by prefix and binary operators
+fn parse_ops<'s, I, T>(parser: &mut Parser<'s, I>, end: &T) -> Result<Option<expr::Expr>>
+where
+    I: ParserSequence<'s>,
+    T: for<'a> TokenKindSet<'a>,
+{
+    let mut stack = Vec::new();
+    let mut op: Vec<TokenKind> = Vec::new();
+    loop {
+        // An operand is expected, possibly after prefix operators, where `-` negates
+        if let Some(input) = parser.next_if(&UnOpTokens)? {
+            push_op(input.kind, &mut op, &mut stack).map_err(|err| parser.map_err(err))?;
+            continue;
+        } else if parser.next_if(&TokenKind::BinOp(BinOp::Sub))?.is_some() {
+            let neg = TokenKind::UnOp(UnOp::Neg);
+            push_op(neg, &mut op, &mut stack).map_err(|err| parser.map_err(err))?;
+            continue;
+        } else if let Some(item) = rules::ElemExprRule.parse(parser)? {
+            let item = rules::PostfixExprRule { val: item }
+                .parse(parser)?
+                .expect("postfix rule should output the operand");
+            stack.push(item);
+            parse_cast(parser, end, &mut op, &mut stack)?;
+        } else if stack.is_empty() && op.is_empty() {
+            return Ok(None);
+        } else {
+            return parser.error(errors::SyntaxError::ExpectedIn {
+                expect: "Expr".to_owned(),
+                context: "expression".to_owned(),
+            });
         }
-        match stack.len() {
-            0 => unreachable!("stack should be empty at this point"),
-            1 => Ok(Some(stack.into_iter().next().unwrap())),
-            _ => parser.error(errors::SyntaxError::TooFewOp),
+
+        // Then an operator continues the expression, or the expression ends here
+        let input = parser.peek()?;
+        match input {
+            Some(input)
+                if matches!(input.kind, TokenKind::BinOp(_)) && !end.contains(&input.kind) =>
+            {
+                parser.next_token()?;
+                push_op(input.kind, &mut op, &mut stack).map_err(|err| parser.map_err(err))?;
+            }
+            _ => break,
         }
     }
+    for op in op.into_iter().rev() {
+        merge_symbols(op, &mut stack).map_err(|err| parser.map_err(err))?;
+    }
+    match stack.len() {
+        0 => unreachable!("stack should not be empty at this point"),
+        1 => Ok(Some(stack.into_iter().next().unwrap())),
+        _ => parser.error(errors::SyntaxError::TooFewOp),
+    }
 }
@@ -1,13 +1,19 @@
+mod array;
 mod body;
+mod closure;
 mod elem;
 mod expr;
+mod postfix;
 mod struct_expr;
 mod tuple;
 mod value;
 
+pub use array::ArrayExprRule;
 pub use body::BodyRule;
+pub use closure::ClosureRule;
 pub use elem::ElemExprRule;
 pub use expr::ExprRule;
+pub use postfix::PostfixExprRule;
 pub use struct_expr::StructExprRule;
 pub use tuple::TupleExprRule;
 pub use value::ValueRule;
@@ -0,0 +1,84 @@
+use crate::prelude::*;
+use imuc_lexer::token::{Ident, Pair, Symbol};
+
+/// Only invoked after an operand is parsed, the operand must be given
+///
+/// Applies field accesses `.name`, indexes `[index]`, calls `(arg, ...)` and tries `?` to the operand as many as possible.
+/// If no postfix follows, the operand is returned unchanged
+pub struct PostfixExprRule {
+    pub val: expr::Expr,
+}
+
+impl Rule for PostfixExprRule {
+    type Output = expr::Expr;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        let mut val = self.val;
+        loop {
+            if parser.next_if(&TokenKind::Symbol(Symbol::Dot))?.is_some() {
+                let name = parser.next_expected(&TokenKind::Ident(Ident::Value))?;
+                val = expr::Expr::Field(expr::Field {
+                    val: Box::new(val),
+                    name: parser.look_up.insert(name.value),
+                });
+            } else if parser
+                .next_if(&TokenKind::Pair(Pair::LeftBracket))?
+                .is_some()
+            {
+                let index = rules::ExprRule { end: () }.parse(parser)?.ok_or_else(|| {
+                    parser.map_err(errors::SyntaxError::ExpectedIn {
+                        expect: "Expr".to_owned(),
+                        context: "index expression".to_owned(),
+                    })
+                })?;
+                parser.next_expected(&TokenKind::Pair(Pair::RightBracket))?;
+                val = expr::Expr::Index(expr::Index {
+                    val: Box::new(val),
+                    index: Box::new(index),
+                });
+            } else if parser.next_if(&TokenKind::Pair(Pair::LeftParen))?.is_some() {
+                let mut args = Vec::new();
+                let mut comma = true;
+                loop {
+                    if parser
+                        .next_if(&TokenKind::Pair(Pair::RightParen))?
+                        .is_some()
+                    {
+                        break;
+                    } else if !comma {
+                        return Err(parser.map_err(errors::SyntaxError::ExpectedToken {
+                            expect: TokenKind::Pair(Pair::RightParen),
+                        }));
+                    }
+                    let arg = rules::ExprRule {
+                        end: TokenKind::Symbol(Symbol::Comma),
+                    }
+                    .parse(parser)?
+                    .ok_or_else(|| {
+                        parser.map_err(errors::SyntaxError::ExpectedIn {
+                            expect: "Expr".to_owned(),
+                            context: "call arguments".to_owned(),
+                        })
+                    })?;
+                    comma = parser.next_if(&TokenKind::Symbol(Symbol::Comma))?.is_some();
+                    args.push(arg);
+                }
+                val = expr::Expr::Call(expr::Call {
+                    fun: Box::new(val),
+                    args,
+                });
+            } else if parser
+                .next_if(&TokenKind::Symbol(Symbol::Question))?
+                .is_some()
+            {
+                val = expr::Expr::Try(expr::Try { val: Box::new(val) });
+            } else {
+                break;
+            }
+        }
+        Ok(Some(val))
+    }
+}
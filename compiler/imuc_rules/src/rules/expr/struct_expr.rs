@@ -1,7 +1,9 @@
 use crate::prelude::*;
-use imuc_lexer::token::{Ident, Pair, Symbol};
+use imuc_lexer::token::{BinOp, Ident, Pair, Symbol};
 use std::collections::BTreeMap;
 
+/// Parses a struct like `Type(name: val, ...)` or a variant like `Type.Name(name: val, ...)`,
+/// where the elements may end with a base like `..base`
 pub struct StructExprRule;
 
 impl Rule for StructExprRule {
@@ -12,9 +14,29 @@ impl Rule for StructExprRule {
         I: ParserSequence<'s>,
     {
         if let Some(ty) = rules::TypeRule.parse(parser)? {
-            parser.next_expected(&TokenKind::Pair(Pair::LeftParen))?;
+            let variant = if parser.next_if(&TokenKind::Symbol(Symbol::Dot))?.is_some() {
+                let name = parser.next_expected(&TokenKind::Ident(Ident::Type))?;
+                Some(parser.look_up.insert(name.value))
+            } else {
+                None
+            };
+            // Variants without elements may omit the parentheses
+            if parser.next_if(&TokenKind::Pair(Pair::LeftParen))?.is_none() {
+                if variant.is_none() {
+                    return Err(parser.map_err(errors::SyntaxError::ExpectedToken {
+                        expect: TokenKind::Pair(Pair::LeftParen),
+                    }));
+                }
+                return Ok(Some(expr::Struct {
+                    ty,
+                    variant,
+                    elem: BTreeMap::new(),
+                    base: None,
+                }));
+            }
             let mut elem = BTreeMap::new();
-            let mut comma = false;
+            let mut base = None;
+            let mut comma = true;
             loop {
                 if parser
                     .next_if(&TokenKind::Pair(Pair::RightParen))?
@@ -27,7 +49,30 @@ impl Rule for StructExprRule {
                     }));
                 }
 
+                // The base is always the last element
+                if parser.next_if(&TokenKind::BinOp(BinOp::Range))?.is_some() {
+                    let expr = rules::ExprRule {
+                        end: TokenKind::Symbol(Symbol::Comma),
+                    }
+                    .parse(parser)?
+                    .ok_or_else(|| {
+                        parser.map_err(errors::SyntaxError::ExpectedIn {
+                            expect: "Expr".to_owned(),
+                            context: "struct base".to_owned(),
+                        })
+                    })?;
+                    base = Some(Box::new(expr));
+                    parser.next_expected(&TokenKind::Pair(Pair::RightParen))?;
+                    break;
+                }
+
                 let name = parser.next_expected(&TokenKind::Ident(Ident::Value))?;
+                if elem.contains_key(name.value) {
+                    return Err(parser.map_err(errors::SyntaxError::Duplicate {
+                        kind: "field".to_owned(),
+                        name: name.value.to_owned(),
+                    }));
+                }
 
                 parser.next_expected(&TokenKind::Symbol(Symbol::Colon))?;
 
@@ -46,7 +91,12 @@ impl Rule for StructExprRule {
 
                 elem.insert(parser.look_up.insert(name.value), expr);
             }
-            Ok(Some(expr::Struct { ty, elem }))
+            Ok(Some(expr::Struct {
+                ty,
+                variant,
+                elem,
+                base,
+            }))
         } else {
             Ok(None)
         }
@@ -13,7 +13,7 @@ impl Rule for TupleExprRule {
     {
         if parser.next_if(&TokenKind::Pair(Pair::LeftParen))?.is_some() {
             let mut elem = Vec::new();
-            let mut comma = false;
+            let mut comma = true;
             loop {
                 if parser
                     .next_if(&TokenKind::Pair(Pair::RightParen))?
@@ -1,8 +1,6 @@
 use crate::prelude::*;
 use imuc_lexer::token::{Ident, ResVal};
 
-lazy_tokens!(ResValTokens, ResVal::True, ResVal::False, ResVal::SelfValue);
-
 pub struct ValueRule;
 
 impl Rule for ValueRule {
@@ -13,15 +11,14 @@ impl Rule for ValueRule {
         I: ParserSequence<'s>,
     {
         if let Some(input) = parser.next_if(&TokenKind::Ident(Ident::Value))? {
-            Ok(Some(expr::Value::Name(parser.look_up.insert(input.value))))
+            Ok(Some(expr::Value::Name(
+                parser.look_up.insert(input.value),
+                input.loc,
+            )))
         } else if let Some(_input) = parser.next_if(&TokenKind::Ident(Ident::Unused))? {
             Ok(Some(expr::Value::Unused))
-        } else if let Some(input) = parser.next_if(&ResValTokens)? {
-            if let TokenKind::ResVal(res) = input.kind {
-                Ok(Some(expr::Value::Res(res)))
-            } else {
-                unreachable!("the token kind should be TokenKind::ResVal")
-            }
+        } else if let Some(_input) = parser.next_if(&TokenKind::ResVal(ResVal::SelfValue))? {
+            Ok(Some(expr::Value::Res(ResVal::SelfValue)))
         } else {
             Ok(None)
         }
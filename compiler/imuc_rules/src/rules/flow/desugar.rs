@@ -0,0 +1,236 @@
+//! Desugaring of conditional and iterating loops onto [`flow::Loop`] and `break`
+//!
+//! The names starting with '#' are hidden locals that can never be written in source code.
+//! `while 'label cond { body }` becomes
+//!
+//! ```text
+//! loop 'label { if cond {} else { break }; body }
+//! ```
+//!
+//! `for 'label pat in a..b { body }` becomes
+//!
+//! ```text
+//! {
+//!     let mut #iter = a;
+//!     let #end = b;
+//!     loop 'label {
+//!         if #iter < #end {} else { break };
+//!         let pat = #iter;
+//!         #iter = #iter + 1;
+//!         body
+//!     }
+//! }
+//! ```
+//!
+//! `for 'label pat in a..=b { body }` uses a flag instead, so that `b` may be the maximum value
+//!
+//! ```text
+//! {
+//!     let mut #iter = a;
+//!     let #end = b;
+//!     let mut #done = #iter > #end;
+//!     loop 'label {
+//!         if #done { break };
+//!         let pat = #iter;
+//!         if #iter == #end { #done = true } else { #iter = #iter + 1 };
+//!         body
+//!     }
+//! }
+//! ```
+//!
+//! Iterating any other value `for 'label pat in coll { body }` goes through its length and indexes
+//!
+//! ```text
+//! {
+//!     let #coll = coll;
+//!     let mut #iter = 0;
+//!     let #end = #coll.len;
+//!     loop 'label {
+//!         if #iter < #end {} else { break };
+//!         let pat = #coll[#iter];
+//!         #iter = #iter + 1;
+//!         body
+//!     }
+//! }
+//! ```
+//!
+//! As the index is stepped before the body runs, `continue` never skips the step.
+//! Since the generated `break` carries no value, a `while` or `for` loop is always of unit type
+
+use crate::prelude::*;
+use imuc_lexer::token::BinOp;
+
+fn name(look_up: &mut name::LookUp, name: &str) -> expr::Expr {
+    expr::Expr::Value(expr::Value::Name(look_up.insert(name), Loc::default()))
+}
+
+fn let_bind(look_up: &mut name::LookUp, name: &str, val: expr::Expr, mutable: bool) -> bind::Bind {
+    bind::Bind::Let(bind::Let {
+        pat: pat::Pat::Ident(pat::IdentPat {
+            ident: pat::IdentKind::Value(look_up.insert(name)),
+            ty: None,
+            mutable,
+            loc: Loc::default(),
+        }),
+        val,
+    })
+}
+
+fn bin(op: BinOp, lhs: expr::Expr, rhs: expr::Expr) -> expr::Expr {
+    expr::Expr::BinExpr(expr::BinExpr {
+        op,
+        lhs: Box::new(lhs),
+        rhs: Box::new(rhs),
+    })
+}
+
+fn assign(place: expr::Expr, val: expr::Expr) -> expr::Expr {
+    expr::Expr::Assign(expr::Assign {
+        op: None,
+        place: Box::new(place),
+        val: Box::new(val),
+    })
+}
+
+/// `#iter = #iter + 1`
+fn step(look_up: &mut name::LookUp) -> expr::Expr {
+    let one = expr::Expr::Prim(prim::Prim::Integer(prim::Integer::I64(1)));
+    assign(
+        name(look_up, "#iter"),
+        bin(BinOp::Add, name(look_up, "#iter"), one),
+    )
+}
+
+fn body(bind: Vec<bind::Bind>, body: Vec<expr::Expr>) -> expr::Body {
+    expr::Body {
+        bind,
+        body,
+        unit: false,
+    }
+}
+
+fn break_unit() -> expr::Expr {
+    expr::Expr::Flow(flow::Flow::Break(flow::Break {
+        label: None,
+        val: None,
+    }))
+}
+
+fn if_else(cond: expr::Expr, then: expr::Body, other: expr::Body) -> expr::Expr {
+    expr::Expr::Flow(flow::Flow::If(flow::If {
+        cond: Box::new(cond),
+        body: then,
+        other: Some(Box::new(expr::Expr::Body(other))),
+    }))
+}
+
+/// `if cond {} else { break }`
+fn break_unless(cond: expr::Expr) -> expr::Expr {
+    if_else(cond, body(vec![], vec![]), body(vec![], vec![break_unit()]))
+}
+
+/// Desugars a `while` loop
+pub(crate) fn while_loop(label: Option<StrRef>, cond: expr::Expr, user: expr::Body) -> flow::Loop {
+    flow::Loop {
+        label,
+        body: body(vec![], vec![break_unless(cond), expr::Expr::Body(user)]),
+    }
+}
+
+/// Desugars a `for` loop, the output is a body containing hidden locals and the loop
+pub(crate) fn for_loop(
+    look_up: &mut name::LookUp,
+    label: Option<StrRef>,
+    pat: pat::Pat,
+    iter: expr::Expr,
+    user: expr::Body,
+) -> expr::Expr {
+    let (bind, head, elem, step) = match iter {
+        expr::Expr::BinExpr(expr::BinExpr {
+            op: BinOp::Range,
+            lhs,
+            rhs,
+        }) => (
+            vec![
+                let_bind(look_up, "#iter", *lhs, true),
+                let_bind(look_up, "#end", *rhs, false),
+            ],
+            break_unless(bin(
+                BinOp::Lt,
+                name(look_up, "#iter"),
+                name(look_up, "#end"),
+            )),
+            name(look_up, "#iter"),
+            step(look_up),
+        ),
+        expr::Expr::BinExpr(expr::BinExpr {
+            op: BinOp::RangeEq,
+            lhs,
+            rhs,
+        }) => {
+            let done = bin(BinOp::Gt, name(look_up, "#iter"), name(look_up, "#end"));
+            let finish = assign(
+                name(look_up, "#done"),
+                expr::Expr::Prim(prim::Prim::Bool(true)),
+            );
+            (
+                vec![
+                    let_bind(look_up, "#iter", *lhs, true),
+                    let_bind(look_up, "#end", *rhs, false),
+                    let_bind(look_up, "#done", done, true),
+                ],
+                expr::Expr::Flow(flow::Flow::If(flow::If {
+                    cond: Box::new(name(look_up, "#done")),
+                    body: body(vec![], vec![break_unit()]),
+                    other: None,
+                })),
+                name(look_up, "#iter"),
+                if_else(
+                    bin(BinOp::Eq, name(look_up, "#iter"), name(look_up, "#end")),
+                    body(vec![], vec![finish]),
+                    body(vec![], vec![step(look_up)]),
+                ),
+            )
+        }
+        iter => {
+            let len = expr::Expr::Field(expr::Field {
+                val: Box::new(name(look_up, "#coll")),
+                name: look_up.insert("len"),
+            });
+            let zero = expr::Expr::Prim(prim::Prim::Integer(prim::Integer::I64(0)));
+            (
+                vec![
+                    let_bind(look_up, "#coll", iter, false),
+                    let_bind(look_up, "#iter", zero, true),
+                    let_bind(look_up, "#end", len, false),
+                ],
+                break_unless(bin(
+                    BinOp::Lt,
+                    name(look_up, "#iter"),
+                    name(look_up, "#end"),
+                )),
+                expr::Expr::Index(expr::Index {
+                    val: Box::new(name(look_up, "#coll")),
+                    index: Box::new(name(look_up, "#iter")),
+                }),
+                step(look_up),
+            )
+        }
+    };
+    expr::Expr::Body(body(
+        bind,
+        vec![expr::Expr::Flow(flow::Flow::Loop(flow::Loop {
+            label,
+            body: body(
+                vec![],
+                vec![
+                    head,
+                    expr::Expr::Body(body(
+                        vec![bind::Bind::Let(bind::Let { pat, val: elem })],
+                        vec![step, expr::Expr::Body(user)],
+                    )),
+                ],
+            ),
+        }))],
+    ))
+}
@@ -13,6 +13,16 @@ impl Rule for FlowRule {
             Ok(Some(flow::Flow::If(flow)))
         } else if let Some(flow) = rules::LoopRule.parse(parser)? {
             Ok(Some(flow::Flow::Loop(flow)))
+        } else if let Some(flow) = rules::WhileRule.parse(parser)? {
+            Ok(Some(flow::Flow::Loop(flow)))
+        } else if let Some(flow) = rules::BreakRule.parse(parser)? {
+            Ok(Some(flow::Flow::Break(flow)))
+        } else if let Some(flow) = rules::ContinueRule.parse(parser)? {
+            Ok(Some(flow::Flow::Continue(flow)))
+        } else if let Some(flow) = rules::ReturnRule.parse(parser)? {
+            Ok(Some(flow::Flow::Return(flow)))
+        } else if let Some(flow) = rules::MatchRule.parse(parser)? {
+            Ok(Some(flow::Flow::Match(flow)))
         } else {
             Ok(None)
         }
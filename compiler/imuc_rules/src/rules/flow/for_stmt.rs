@@ -0,0 +1,52 @@
+use crate::prelude::*;
+use imuc_lexer::token::{Keyword, Pair};
+
+/// Parses `for 'label pat in iter { body }`, desugared as described in [`super::desugar`]
+///
+/// The output is a body holding hidden locals and the loop itself
+pub struct ForRule;
+
+impl Rule for ForRule {
+    type Output = expr::Expr;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        if parser.next_if(&TokenKind::Keyword(Keyword::For))?.is_some() {
+            let label = rules::LabelRule.parse(parser)?;
+            let pat = rules::PatRule.parse(parser)?.ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedAfter {
+                    expect: "Pat".to_owned(),
+                    after: TokenKind::Keyword(Keyword::For),
+                })
+            })?;
+            parser.next_expected(&TokenKind::Keyword(Keyword::In))?;
+            let iter = rules::ExprRule {
+                end: TokenKind::Pair(Pair::LeftBrace),
+            }
+            .parse(parser)?
+            .ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedAfter {
+                    expect: "Expr".to_owned(),
+                    after: TokenKind::Keyword(Keyword::In),
+                })
+            })?;
+            let body = rules::BodyRule.parse(parser)?.ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "Body".to_owned(),
+                    context: "for statement".to_owned(),
+                })
+            })?;
+            Ok(Some(super::desugar::for_loop(
+                &mut parser.look_up,
+                label,
+                pat,
+                iter,
+                body,
+            )))
+        } else {
+            Ok(None)
+        }
+    }
+}
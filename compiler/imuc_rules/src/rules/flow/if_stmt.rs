@@ -27,9 +27,28 @@ impl Rule for IfRule {
                     context: "if statement".to_owned(),
                 })
             })?;
+            let other = if parser
+                .next_if(&TokenKind::Keyword(Keyword::Else))?
+                .is_some()
+            {
+                if let Some(flow) = IfRule.parse(parser)? {
+                    Some(expr::Expr::Flow(flow::Flow::If(flow)))
+                } else {
+                    let body = rules::BodyRule.parse(parser)?.ok_or_else(|| {
+                        parser.map_err(errors::SyntaxError::ExpectedAfter {
+                            expect: "Body or If".to_owned(),
+                            after: TokenKind::Keyword(Keyword::Else),
+                        })
+                    })?;
+                    Some(expr::Expr::Body(body))
+                }
+            } else {
+                None
+            };
             Ok(Some(flow::If {
                 cond: Box::new(cond),
                 body,
+                other: other.map(Box::new),
             }))
         } else {
             Ok(None)
@@ -0,0 +1,66 @@
+use crate::prelude::*;
+use imuc_lexer::token::Keyword;
+
+pub struct BreakRule;
+
+impl Rule for BreakRule {
+    type Output = flow::Break;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        if parser
+            .next_if(&TokenKind::Keyword(Keyword::Break))?
+            .is_some()
+        {
+            let label = rules::LabelRule.parse(parser)?;
+            let val = rules::ExprRule { end: () }.parse(parser)?.map(Box::new);
+            Ok(Some(flow::Break { label, val }))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+pub struct ContinueRule;
+
+impl Rule for ContinueRule {
+    type Output = flow::Continue;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        if parser
+            .next_if(&TokenKind::Keyword(Keyword::Continue))?
+            .is_some()
+        {
+            let label = rules::LabelRule.parse(parser)?;
+            Ok(Some(flow::Continue { label }))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+pub struct ReturnRule;
+
+impl Rule for ReturnRule {
+    type Output = flow::Return;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        if parser
+            .next_if(&TokenKind::Keyword(Keyword::Return))?
+            .is_some()
+        {
+            let val = rules::ExprRule { end: () }.parse(parser)?.map(Box::new);
+            Ok(Some(flow::Return { val }))
+        } else {
+            Ok(None)
+        }
+    }
+}
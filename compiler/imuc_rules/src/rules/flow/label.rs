@@ -0,0 +1,20 @@
+use crate::prelude::*;
+use imuc_lexer::token::Ident;
+
+/// Parses an optional loop label such as `'outer`, outputting the name without the quote
+pub struct LabelRule;
+
+impl Rule for LabelRule {
+    type Output = StrRef;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        if let Some(input) = parser.next_if(&TokenKind::Ident(Ident::Label))? {
+            Ok(Some(parser.look_up.insert(&input.value[1..])))
+        } else {
+            Ok(None)
+        }
+    }
+}
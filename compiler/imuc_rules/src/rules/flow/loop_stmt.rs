@@ -14,13 +14,14 @@ impl Rule for LoopRule {
             .next_if(&TokenKind::Keyword(Keyword::Loop))?
             .is_some()
         {
+            let label = rules::LabelRule.parse(parser)?;
             let body = rules::BodyRule.parse(parser)?.ok_or_else(|| {
                 parser.map_err(errors::SyntaxError::ExpectedAfter {
                     expect: "Body".to_owned(),
                     after: TokenKind::Keyword(Keyword::Loop),
                 })
             })?;
-            Ok(Some(flow::Loop { body }))
+            Ok(Some(flow::Loop { label, body }))
         } else {
             Ok(None)
         }
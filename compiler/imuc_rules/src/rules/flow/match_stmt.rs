@@ -0,0 +1,91 @@
+use crate::prelude::*;
+use imuc_lexer::token::{Keyword, Pair, Symbol};
+
+/// Parses `match val { pat if guard => val, ... }`
+///
+/// The comma after an arm may be omitted if the arm is a body or the last one
+pub struct MatchRule;
+
+impl Rule for MatchRule {
+    type Output = flow::Match;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        if parser
+            .next_if(&TokenKind::Keyword(Keyword::Match))?
+            .is_some()
+        {
+            let val = rules::ExprRule {
+                end: TokenKind::Pair(Pair::LeftBrace),
+            }
+            .parse(parser)?
+            .ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedAfter {
+                    expect: "Expr".to_owned(),
+                    after: TokenKind::Keyword(Keyword::Match),
+                })
+            })?;
+            parser.next_expected(&TokenKind::Pair(Pair::LeftBrace))?;
+            let mut arms = Vec::new();
+            let mut comma = true;
+            loop {
+                if parser
+                    .next_if(&TokenKind::Pair(Pair::RightBrace))?
+                    .is_some()
+                {
+                    break;
+                } else if !comma {
+                    return Err(parser.map_err(errors::SyntaxError::ExpectedToken {
+                        expect: TokenKind::Pair(Pair::RightBrace),
+                    }));
+                }
+
+                let pat = rules::PatRule.parse(parser)?.ok_or_else(|| {
+                    parser.map_err(errors::SyntaxError::ExpectedIn {
+                        expect: "Pat".to_owned(),
+                        context: "match arm".to_owned(),
+                    })
+                })?;
+                let guard = if parser.next_if(&TokenKind::Keyword(Keyword::If))?.is_some() {
+                    let guard = rules::ExprRule {
+                        end: TokenKind::Symbol(Symbol::Arrow),
+                    }
+                    .parse(parser)?
+                    .ok_or_else(|| {
+                        parser.map_err(errors::SyntaxError::ExpectedAfter {
+                            expect: "Expr".to_owned(),
+                            after: TokenKind::Keyword(Keyword::If),
+                        })
+                    })?;
+                    Some(guard)
+                } else {
+                    None
+                };
+                parser.next_expected(&TokenKind::Symbol(Symbol::Arrow))?;
+                let val = rules::ExprRule {
+                    end: TokenKind::Symbol(Symbol::Comma),
+                }
+                .parse(parser)?
+                .ok_or_else(|| {
+                    parser.map_err(errors::SyntaxError::ExpectedAfter {
+                        expect: "Expr".to_owned(),
+                        after: TokenKind::Symbol(Symbol::Arrow),
+                    })
+                })?;
+
+                comma = parser.next_if(&TokenKind::Symbol(Symbol::Comma))?.is_some()
+                    || matches!(val, expr::Expr::Body(_));
+
+                arms.push(flow::Arm { pat, guard, val });
+            }
+            Ok(Some(flow::Match {
+                val: Box::new(val),
+                arms,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
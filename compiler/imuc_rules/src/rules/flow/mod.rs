@@ -1,7 +1,18 @@
+pub mod desugar;
 mod flow;
+mod for_stmt;
 mod if_stmt;
+mod jump;
+mod label;
 mod loop_stmt;
+mod match_stmt;
+mod while_stmt;
 
 pub use flow::*;
+pub use for_stmt::ForRule;
 pub use if_stmt::IfRule;
+pub use jump::{BreakRule, ContinueRule, ReturnRule};
+pub use label::LabelRule;
 pub use loop_stmt::LoopRule;
+pub use match_stmt::MatchRule;
+pub use while_stmt::WhileRule;
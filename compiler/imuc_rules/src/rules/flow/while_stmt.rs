@@ -0,0 +1,40 @@
+use crate::prelude::*;
+use imuc_lexer::token::{Keyword, Pair};
+
+/// Parses `while 'label cond { body }`, desugared as described in [`super::desugar`]
+pub struct WhileRule;
+
+impl Rule for WhileRule {
+    type Output = flow::Loop;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        if parser
+            .next_if(&TokenKind::Keyword(Keyword::While))?
+            .is_some()
+        {
+            let label = rules::LabelRule.parse(parser)?;
+            let cond = rules::ExprRule {
+                end: TokenKind::Pair(Pair::LeftBrace),
+            }
+            .parse(parser)?
+            .ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedAfter {
+                    expect: "Expr".to_owned(),
+                    after: TokenKind::Keyword(Keyword::While),
+                })
+            })?;
+            let body = rules::BodyRule.parse(parser)?.ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "Body".to_owned(),
+                    context: "while statement".to_owned(),
+                })
+            })?;
+            Ok(Some(super::desugar::while_loop(label, cond, body)))
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::prelude::*;
+use imuc_lexer::token::{Ident, Keyword, Pair, Symbol};
+
+// Keywords naming attributes are allowed as names
+lazy_tokens!(AttrNameTokens, Ident::Value, Keyword::Extern);
+
+/// Parses the attributes preceding an item, an element or an argument, like `#[name(arg, ...)]`
+///
+/// Always matches, giving an empty list if no attributes are found
+pub struct AttrRule;
+
+/// Parses an attribute without the surrounding `#[` and `]`, like `name` or `name(arg, ...)`
+struct AttrBodyRule;
+
+impl Rule for AttrBodyRule {
+    type Output = attr::Attr;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        let Some(name) = parser.next_if(&AttrNameTokens)? else {
+            return Ok(None);
+        };
+        let mut args = Vec::new();
+        if parser.next_if(&TokenKind::Pair(Pair::LeftParen))?.is_some() {
+            let mut comma = true;
+            loop {
+                if parser
+                    .next_if(&TokenKind::Pair(Pair::RightParen))?
+                    .is_some()
+                {
+                    break;
+                } else if !comma {
+                    return Err(parser.map_err(errors::SyntaxError::ExpectedToken {
+                        expect: TokenKind::Pair(Pair::RightParen),
+                    }));
+                }
+
+                let arg = if let Some(prim) = rules::PrimRule.parse(parser)? {
+                    attr::AttrArg::Prim(prim)
+                } else if let Some(attr) = AttrBodyRule.parse(parser)? {
+                    attr::AttrArg::Attr(attr)
+                } else {
+                    return Err(parser.map_err(errors::SyntaxError::ExpectedIn {
+                        expect: "attribute argument".to_owned(),
+                        context: "attribute".to_owned(),
+                    }));
+                };
+
+                comma = parser.next_if(&TokenKind::Symbol(Symbol::Comma))?.is_some();
+
+                args.push(arg);
+            }
+        }
+        Ok(Some(attr::Attr {
+            name: parser.look_up.insert(name.value),
+            args,
+            loc: name.loc,
+        }))
+    }
+}
+
+impl Rule for AttrRule {
+    type Output = Vec<attr::Attr>;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        let mut attrs = Vec::new();
+        while parser.next_if(&TokenKind::Symbol(Symbol::Hash))?.is_some() {
+            parser.next_expected(&TokenKind::Pair(Pair::LeftBracket))?;
+            let attr = AttrBodyRule.parse(parser)?.ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "attribute name".to_owned(),
+                    context: "attribute".to_owned(),
+                })
+            })?;
+            parser.next_expected(&TokenKind::Pair(Pair::RightBracket))?;
+            attrs.push(attr);
+        }
+        Ok(Some(attrs))
+    }
+}
@@ -1,5 +1,8 @@
 use crate::prelude::*;
+use imuc_lexer::token::{Ident, Pair, Symbol};
 
+/// Parses the elements of a struct like `(#[attr] pub name: Type = val, ...)`,
+/// or the variants of a sum type like `{ Name(name: Type, ...), Name, ... }`
 pub struct CusRule;
 
 impl Rule for CusRule {
@@ -9,12 +12,57 @@ impl Rule for CusRule {
     where
         I: ParserSequence<'s>,
     {
-        let elem = rules::PatRule.parse(parser)?.ok_or_else(|| {
-            parser.map_err(errors::SyntaxError::ExpectedIn {
-                expect: "pat".to_owned(),
-                context: "custom elements".to_owned(),
-            })
-        })?;
-        Ok(Some(item::Cus { elem }))
+        if parser.next_if(&TokenKind::Pair(Pair::LeftBrace))?.is_some() {
+            let mut variants: Vec<item::Variant> = Vec::new();
+            let mut comma = true;
+            loop {
+                if parser
+                    .next_if(&TokenKind::Pair(Pair::RightBrace))?
+                    .is_some()
+                {
+                    break;
+                } else if !comma {
+                    return Err(parser.map_err(errors::SyntaxError::ExpectedToken {
+                        expect: TokenKind::Pair(Pair::RightBrace),
+                    }));
+                }
+
+                let name = parser.next_expected(&TokenKind::Ident(Ident::Type))?;
+                if variants.iter().any(|variant| &*variant.name == name.value) {
+                    return Err(parser.map_err(errors::SyntaxError::Duplicate {
+                        kind: "variant".to_owned(),
+                        name: name.value.to_owned(),
+                    }));
+                }
+                let name = parser.look_up.insert(name.value);
+                let elem = rules::PatRule.parse(parser)?;
+
+                comma = parser.next_if(&TokenKind::Symbol(Symbol::Comma))?.is_some();
+
+                variants.push(item::Variant { name, elem });
+            }
+            Ok(Some(item::Cus::Union(variants)))
+        } else if let Some((elem, elems)) = (rules::ElemListRule {
+            public: true,
+            default: true,
+            context: "custom elements",
+        })
+        .parse(parser)?
+        {
+            Ok(Some(item::Cus::Struct(elem, elems)))
+        } else {
+            let elem = rules::PatRule.parse(parser)?.ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "pat".to_owned(),
+                    context: "custom elements".to_owned(),
+                })
+            })?;
+            let elems = vec![item::Elem {
+                public: module::Public::Priv,
+                attrs: Vec::new(),
+                default: None,
+            }];
+            Ok(Some(item::Cus::Struct(elem, elems)))
+        }
     }
 }
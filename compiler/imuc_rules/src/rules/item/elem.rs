@@ -0,0 +1,88 @@
+use crate::prelude::*;
+use imuc_lexer::token::{Pair, Symbol};
+
+/// Parses a list of elements like `(#[attr] pub name: Type = val, ...)` into a tuple pattern,
+/// together with the visibility, attributes and default value of each element
+///
+/// Visibility is only allowed if [`Self::public`] is set, and is private otherwise. Default
+/// values are only allowed if [`Self::default`] is set
+pub struct ElemListRule {
+    pub public: bool,
+    pub default: bool,
+    pub context: &'static str,
+}
+
+impl Rule for ElemListRule {
+    type Output = (pat::Pat, Vec<item::Elem>);
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        if parser.next_if(&TokenKind::Pair(Pair::LeftParen))?.is_none() {
+            return Ok(None);
+        }
+        let mut list = Vec::new();
+        let mut elems = Vec::new();
+        let mut comma = true;
+        loop {
+            if parser
+                .next_if(&TokenKind::Pair(Pair::RightParen))?
+                .is_some()
+            {
+                break;
+            } else if !comma {
+                return Err(parser.map_err(errors::SyntaxError::ExpectedToken {
+                    expect: TokenKind::Pair(Pair::RightParen),
+                }));
+            }
+
+            let attrs = rules::AttrRule
+                .parse(parser)?
+                .expect("attribute rule should not return None");
+            let public = if self.public {
+                rules::PublicRule
+                    .parse(parser)?
+                    .expect("publicity rule should not return None")
+            } else {
+                module::Public::Priv
+            };
+            let pat = rules::PatRule.parse(parser)?.ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "Pat".to_owned(),
+                    context: self.context.to_owned(),
+                })
+            })?;
+
+            let default = if self.default
+                && parser
+                    .next_if(&TokenKind::Symbol(Symbol::Assign))?
+                    .is_some()
+            {
+                let expr = rules::ExprRule {
+                    end: TokenKind::Symbol(Symbol::Comma),
+                }
+                .parse(parser)?
+                .ok_or_else(|| {
+                    parser.map_err(errors::SyntaxError::ExpectedIn {
+                        expect: "Expr".to_owned(),
+                        context: "default value".to_owned(),
+                    })
+                })?;
+                Some(expr)
+            } else {
+                None
+            };
+
+            comma = parser.next_if(&TokenKind::Symbol(Symbol::Comma))?.is_some();
+
+            list.push(pat);
+            elems.push(item::Elem {
+                public,
+                attrs,
+                default,
+            });
+        }
+        Ok(Some((pat::Pat::Tuple(pat::TuplePat(list)), elems)))
+    }
+}
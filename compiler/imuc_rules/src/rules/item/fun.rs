@@ -2,25 +2,58 @@ use crate::prelude::*;
 
 pub struct FunRule;
 
-impl Rule for FunRule {
-    type Output = item::Fun;
+/// Parses the arguments of a function, with the attributes of each top-level argument
+pub(crate) struct ArgsRule;
+
+impl Rule for ArgsRule {
+    type Output = (pat::Pat, Vec<Vec<attr::Attr>>);
 
     fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
     where
         I: ParserSequence<'s>,
     {
+        let list = rules::ElemListRule {
+            public: false,
+            default: false,
+            context: "function arguments",
+        }
+        .parse(parser)?;
+        if let Some((args, elems)) = list {
+            let attrs = elems.into_iter().map(|elem| elem.attrs).collect();
+            return Ok(Some((args, attrs)));
+        }
         let args = rules::PatRule.parse(parser)?.ok_or_else(|| {
             parser.map_err(errors::SyntaxError::ExpectedIn {
                 expect: "Pat".to_owned(),
                 context: "function arguments".to_owned(),
             })
         })?;
+        Ok(Some((args, vec![Vec::new()])))
+    }
+}
+
+impl Rule for FunRule {
+    type Output = item::Fun;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        let (args, attrs) = ArgsRule
+            .parse(parser)?
+            .expect("arguments rule should not return None");
         let body = rules::BodyRule.parse(parser)?.ok_or_else(|| {
             parser.map_err(errors::SyntaxError::ExpectedIn {
                 expect: "Body".to_owned(),
                 context: "function body".to_owned(),
             })
         })?;
-        Ok(Some(item::Fun { args, body }))
+        Ok(Some(item::Fun {
+            templ: Vec::new(),
+            args,
+            attrs,
+            body,
+            abi: None,
+        }))
     }
 }
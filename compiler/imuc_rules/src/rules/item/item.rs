@@ -1,11 +1,14 @@
 use crate::prelude::*;
-use imuc_lexer::token::{Ident, Keyword};
+use imuc_lexer::token::{Ident, Keyword, Pair, ResTy, Symbol};
 
 lazy_tokens!(
     ItemTokens,
     Keyword::Fun,
+    Keyword::Extern,
     Keyword::Cus,
+    Keyword::Mod,
     Keyword::For,
+    Keyword::Interface,
     Keyword::Val
 );
 
@@ -14,6 +17,162 @@ lazy_tokens!(TypeTokens, Ident::Type, Ident::Unused);
 
 pub struct ItemRule;
 
+impl ItemRule {
+    /// Parses the rest of a function after `extern`, like `("C") fun name(...) { ... }`,
+    /// which is either exported with a body or declared with an optional return type
+    fn parse_extern<'s, I>(parser: &mut Parser<'s, I>) -> Result<(StrRef, item::ItemKind)>
+    where
+        I: ParserSequence<'s>,
+    {
+        let abi = if parser.next_if(&TokenKind::Pair(Pair::LeftParen))?.is_some() {
+            let Some(prim::Prim::String(abi)) = rules::PrimRule.parse(parser)? else {
+                return Err(parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "ABI string".to_owned(),
+                    context: "extern function".to_owned(),
+                }));
+            };
+            parser.next_expected(&TokenKind::Pair(Pair::RightParen))?;
+            parser.look_up.insert(&abi)
+        } else {
+            parser.look_up.insert("C")
+        };
+        parser.next_expected(&TokenKind::Keyword(Keyword::Fun))?;
+        let name = parser.next_expected(&ValueTokens)?;
+        let name = parser.look_up.insert(name.value);
+        let (args, attrs) = rules::ArgsRule
+            .parse(parser)?
+            .expect("arguments rule should not return None");
+        let ret = if parser.next_if(&TokenKind::Symbol(Symbol::Arrow))?.is_some() {
+            let ret = rules::TypeRule.parse(parser)?.ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedAfter {
+                    expect: "Type".to_owned(),
+                    after: TokenKind::Symbol(Symbol::Arrow),
+                })
+            })?;
+            Some(ret)
+        } else if let Some(body) = rules::BodyRule.parse(parser)? {
+            let fun = item::Fun {
+                templ: Vec::new(),
+                args,
+                attrs,
+                body,
+                abi: Some(abi),
+            };
+            return Ok((name, item::ItemKind::Fun(fun)));
+        } else {
+            None
+        };
+        // A declaration may end with a semicolon
+        parser.next_if(&TokenKind::Semicolon)?;
+        let ext = item::Extern {
+            abi,
+            args,
+            attrs,
+            ret,
+        };
+        Ok((name, item::ItemKind::Extern(ext)))
+    }
+
+    /// Parses the rest of an implementation after `for`, like `Type: Interface { ... }`, which
+    /// is named after the type
+    fn parse_for<'s, I>(parser: &mut Parser<'s, I>) -> Result<(StrRef, item::ItemKind)>
+    where
+        I: ParserSequence<'s>,
+    {
+        let expect_type = |parser: &mut Parser<'s, I>| {
+            rules::TypeRule.parse(parser)?.ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "Type".to_owned(),
+                    context: "implementation".to_owned(),
+                })
+            })
+        };
+        let ty = expect_type(parser)?;
+        let name = match &ty.kind {
+            pat::TypeKind::Single(name) | pat::TypeKind::Template(name, _) => name.clone(),
+            pat::TypeKind::Res(res) if *res != ResTy::SelfType => {
+                parser.look_up.insert(&format!("{:?}", res))
+            }
+            _ => {
+                return Err(parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "type name".to_owned(),
+                    context: "implementation".to_owned(),
+                }))
+            }
+        };
+        let interface = if parser.next_if(&TokenKind::Symbol(Symbol::Colon))?.is_some() {
+            Some(expect_type(parser)?)
+        } else {
+            None
+        };
+        parser.next_expected(&TokenKind::Pair(Pair::LeftBrace))?;
+        let mut items = Vec::new();
+        while parser
+            .next_if(&TokenKind::Pair(Pair::RightBrace))?
+            .is_none()
+        {
+            let item = rules::ItemRule.parse(parser)?.ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "Item".to_owned(),
+                    context: "implementation".to_owned(),
+                })
+            })?;
+            items.push(item);
+        }
+        let kind = item::ItemKind::For(item::For {
+            ty,
+            interface,
+            items,
+        });
+        Ok((name, kind))
+    }
+
+    /// Parses the functions an interface requires, like `{ fun name(...) => Type; ... }`
+    fn parse_interface<'s, I>(parser: &mut Parser<'s, I>) -> Result<item::Interface>
+    where
+        I: ParserSequence<'s>,
+    {
+        parser.next_expected(&TokenKind::Pair(Pair::LeftBrace))?;
+        let mut decls: Vec<item::Decl> = Vec::new();
+        while parser
+            .next_if(&TokenKind::Pair(Pair::RightBrace))?
+            .is_none()
+        {
+            parser.next_expected(&TokenKind::Keyword(Keyword::Fun))?;
+            let name = parser.next_expected(&ValueTokens)?;
+            if decls.iter().any(|decl| &*decl.name == name.value) {
+                return Err(parser.map_err(errors::SyntaxError::Duplicate {
+                    kind: "function".to_owned(),
+                    name: name.value.to_owned(),
+                }));
+            }
+            let name = parser.look_up.insert(name.value);
+            let (args, attrs) = rules::ArgsRule
+                .parse(parser)?
+                .expect("arguments rule should not return None");
+            let ret = if parser.next_if(&TokenKind::Symbol(Symbol::Arrow))?.is_some() {
+                let ret = rules::TypeRule.parse(parser)?.ok_or_else(|| {
+                    parser.map_err(errors::SyntaxError::ExpectedAfter {
+                        expect: "Type".to_owned(),
+                        after: TokenKind::Symbol(Symbol::Arrow),
+                    })
+                })?;
+                Some(ret)
+            } else {
+                None
+            };
+            parser.next_if(&TokenKind::Semicolon)?;
+            decls.push(item::Decl {
+                name,
+                args,
+                attrs,
+                ret,
+            });
+        }
+        Ok(item::Interface { decls })
+    }
+}
+
 impl Rule for ItemRule {
     type Output = item::Item;
 
@@ -21,27 +180,61 @@ impl Rule for ItemRule {
     where
         I: ParserSequence<'s>,
     {
+        let attrs = rules::AttrRule
+            .parse(parser)?
+            .expect("attribute rule should not return None");
         let public = rules::PublicRule
             .parse(parser)?
             .expect("publicity rule should not return None");
+        ItemBodyRule { attrs, public }.parse(parser)
+    }
+}
+
+/// Parses an item after its attributes and publicity, which are parsed by the caller
+pub(crate) struct ItemBodyRule {
+    pub attrs: Vec<attr::Attr>,
+    pub public: module::Public,
+}
+
+impl Rule for ItemBodyRule {
+    type Output = item::Item;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        let ItemBodyRule { attrs, public } = self;
         let input = parser.next_if(&ItemTokens)?;
         if let Some(input) = input {
             match input.kind {
                 TokenKind::Keyword(keyword) => match keyword {
                     Keyword::Fun => {
                         let name = parser.next_expected(&ValueTokens)?;
-                        let fun = rules::FunRule.parse(parser)?.ok_or_else(|| {
+                        let name = parser.look_up.insert(name.value);
+                        let templ = rules::TemplRule.parse(parser)?.unwrap_or_default();
+                        let mut fun = rules::FunRule.parse(parser)?.ok_or_else(|| {
                             parser.map_err(errors::SyntaxError::ExpectedIn {
                                 expect: "Fun".to_owned(),
                                 context: "function defintion".to_owned(),
                             })
                         })?;
+                        fun.templ = templ;
                         Ok(Some(item::Item {
+                            attrs,
                             public,
-                            name: parser.look_up.insert(name.value),
+                            name,
                             kind: item::ItemKind::Fun(fun),
                         }))
                     }
+                    Keyword::Extern => {
+                        let (name, kind) = ItemRule::parse_extern(parser)?;
+                        Ok(Some(item::Item {
+                            attrs,
+                            public,
+                            name,
+                            kind,
+                        }))
+                    }
                     Keyword::Cus => {
                         let name = parser.next_expected(&TypeTokens)?;
                         let cus = rules::CusRule.parse(parser)?.ok_or_else(|| {
@@ -51,17 +244,58 @@ impl Rule for ItemRule {
                             })
                         })?;
                         Ok(Some(item::Item {
+                            attrs,
                             public,
                             name: parser.look_up.insert(name.value),
                             kind: item::ItemKind::Cus(cus),
                         }))
                     }
-                    Keyword::For => todo!(),
+                    Keyword::Mod => {
+                        let name = parser.next_expected(&ValueTokens)?;
+                        let name = parser.look_up.insert(name.value);
+                        parser.next_expected(&TokenKind::Pair(Pair::LeftBrace))?;
+                        let module = rules::ModuleRules
+                            .parse(parser)?
+                            .expect("module rule should always match");
+                        parser.next_expected(&TokenKind::Pair(Pair::RightBrace))?;
+                        Ok(Some(item::Item {
+                            attrs,
+                            public,
+                            name,
+                            kind: item::ItemKind::Mod(module),
+                        }))
+                    }
+                    Keyword::For => {
+                        let (name, kind) = ItemRule::parse_for(parser)?;
+                        Ok(Some(item::Item {
+                            attrs,
+                            public,
+                            name,
+                            kind,
+                        }))
+                    }
+                    Keyword::Interface => {
+                        let name = parser.next_expected(&TypeTokens)?;
+                        let name = parser.look_up.insert(name.value);
+                        let interface = ItemRule::parse_interface(parser)?;
+                        Ok(Some(item::Item {
+                            attrs,
+                            public,
+                            name,
+                            kind: item::ItemKind::Interface(interface),
+                        }))
+                    }
                     Keyword::Val => todo!(),
                     _ => filtered!(),
                 },
                 _ => filtered!(),
             }
+        } else if !attrs.is_empty() {
+            // Attributes are not followed by an item. This is an error
+            parser.error(errors::SyntaxError::ExpectedAfter {
+                expect: "Item".to_owned(),
+                after: TokenKind::Symbol(Symbol::Hash),
+            })
         } else if let module::Public::Priv = public {
             // No publicity keywords matched, thus the rule is not matched
             Ok(None)
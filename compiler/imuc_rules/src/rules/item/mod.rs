@@ -1,9 +1,16 @@
+mod attr;
 mod cus;
+mod elem;
 mod fun;
 mod item;
 mod public;
+mod templ;
 
+pub use attr::AttrRule;
 pub(crate) use cus::CusRule;
-pub(crate) use fun::FunRule;
+pub(crate) use elem::ElemListRule;
+pub(crate) use fun::{ArgsRule, FunRule};
+pub(crate) use item::ItemBodyRule;
 pub use item::ItemRule;
 pub use public::PublicRule;
+pub(crate) use templ::TemplRule;
@@ -1,8 +1,9 @@
 use crate::prelude::*;
-use imuc_lexer::token::Keyword;
+use imuc_lexer::token::{Keyword, Pair};
 
 lazy_tokens!(PublicTokens, Keyword::Pub);
 
+/// Parses `pub` or `pub(mod)`, giving [`module::Public::Priv`] if neither is found
 pub struct PublicRule;
 
 impl Rule for PublicRule {
@@ -15,10 +16,15 @@ impl Rule for PublicRule {
         let input = parser.next_if(&PublicTokens)?;
         if let Some(input) = input {
             match input.kind {
-                TokenKind::Keyword(keyword) => match keyword {
-                    Keyword::Pub => Ok(Some(module::Public::Pub)),
-                    _ => filtered!(),
-                },
+                TokenKind::Keyword(Keyword::Pub) => {
+                    if parser.next_if(&TokenKind::Pair(Pair::LeftParen))?.is_some() {
+                        parser.next_expected(&TokenKind::Keyword(Keyword::Mod))?;
+                        parser.next_expected(&TokenKind::Pair(Pair::RightParen))?;
+                        Ok(Some(module::Public::Module))
+                    } else {
+                        Ok(Some(module::Public::Pub))
+                    }
+                }
                 _ => filtered!(),
             }
         } else {
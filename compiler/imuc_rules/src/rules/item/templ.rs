@@ -0,0 +1,67 @@
+use crate::prelude::*;
+use imuc_lexer::token::{BinOp, Ident, Pair, Symbol};
+
+lazy_tokens!(TemplNameTokens, Ident::Type, Ident::Unused);
+
+/// Parses the template parameters of a function like `[T: Show + Eq, _, ...]`
+pub(crate) struct TemplRule;
+
+impl Rule for TemplRule {
+    type Output = Vec<item::Templ>;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        if parser
+            .next_if(&TokenKind::Pair(Pair::LeftBracket))?
+            .is_none()
+        {
+            return Ok(None);
+        }
+        let mut templ = Vec::new();
+        let mut comma = true;
+        loop {
+            if parser
+                .next_if(&TokenKind::Pair(Pair::RightBracket))?
+                .is_some()
+            {
+                break;
+            } else if !comma {
+                return Err(parser.map_err(errors::SyntaxError::ExpectedToken {
+                    expect: TokenKind::Pair(Pair::RightBracket),
+                }));
+            }
+
+            let name = parser.next_expected(&TemplNameTokens)?;
+            let item = match name.kind {
+                TokenKind::Ident(Ident::Unused) => item::Templ::Unused,
+                TokenKind::Ident(Ident::Type) => {
+                    let name = parser.look_up.insert(name.value);
+                    let mut req = Vec::new();
+                    if parser.next_if(&TokenKind::Symbol(Symbol::Colon))?.is_some() {
+                        loop {
+                            let ty = rules::TypeRule.parse(parser)?.ok_or_else(|| {
+                                parser.map_err(errors::SyntaxError::ExpectedIn {
+                                    expect: "Type".to_owned(),
+                                    context: "template bounds".to_owned(),
+                                })
+                            })?;
+                            req.push(ty);
+                            if parser.next_if(&TokenKind::BinOp(BinOp::Add))?.is_none() {
+                                break;
+                            }
+                        }
+                    }
+                    item::Templ::Item(item::TemplItem { name, req })
+                }
+                _ => filtered!(),
+            };
+
+            comma = parser.next_if(&TokenKind::Symbol(Symbol::Comma))?.is_some();
+
+            templ.push(item);
+        }
+        Ok(Some(templ))
+    }
+}
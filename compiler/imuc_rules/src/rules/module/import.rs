@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use imuc_lexer::token::{Ident, Keyword, Pair, Symbol};
+use imuc_lexer::token::{BinOp, Ident, Keyword, Pair, Symbol};
 use std::path::PathBuf;
 
 lazy_tokens!(ImportTokens, Ident::Value, Ident::Type);
@@ -84,9 +84,13 @@ impl Rule for ImportItemRule {
     }
 }
 
-/// Imports a single "use" statement from the parser
+/// Imports a single "use" statement from the parser, with the publicity parsed before it
+///
+/// After the file, the statement either lists the items like `.x` or `.(x, Y as Z)`,
+/// imports every item like `.*`, or names the file like `as f`
 pub struct ImportRule<'a> {
     pub import: &'a mut Vec<module::Import>,
+    pub public: module::Public,
 }
 
 impl Rule for ImportRule<'_> {
@@ -120,26 +124,49 @@ impl Rule for ImportRule<'_> {
                     break None;
                 }
             };
-            match file {
-                Some(file) => {
-                    let _ = parser.next_expected(&TokenKind::Symbol(Symbol::Dot))?;
-                    let item = ImportItemRule.parse(parser)?.ok_or_else(|| {
-                        parser.map_err(errors::SyntaxError::ExpectedIn {
-                            expect: "Item".to_owned(),
-                            context: "import statement".to_owned(),
-                        })
-                    })?;
-                    self.import.push(module::Import {
-                        file: file.clone(),
-                        item,
-                    });
-                    let _ = parser.next_expected(&TokenKind::Semicolon);
-                }
-                None => {
-                    // TODO: Add reference alias
-                    return Err(parser.map_err(errors::PathError::BrokenPath));
+            // Items are only defined in files, thus a path ending at a module imports nothing
+            let file = file.ok_or_else(|| parser.map_err(errors::PathError::BrokenPath))?;
+            let mut path = Vec::new();
+            let kind = if parser.next_if(&TokenKind::Keyword(Keyword::As))?.is_some() {
+                let alias = parser.next_expected(&TokenKind::Ident(Ident::Value))?;
+                module::ImportKind::Alias(parser.look_up.insert(alias.value))
+            } else {
+                let _ = parser.next_expected(&TokenKind::Symbol(Symbol::Dot))?;
+                loop {
+                    if parser.next_if(&TokenKind::BinOp(BinOp::Mul))?.is_some() {
+                        break module::ImportKind::Glob;
+                    }
+                    let is_list = parser
+                        .peek()?
+                        .is_some_and(|input| input.kind == TokenKind::Pair(Pair::LeftParen));
+                    if is_list {
+                        let item = ImportItemRule
+                            .parse(parser)?
+                            .expect("import item rule should match a list");
+                        break module::ImportKind::Items(item);
+                    }
+                    let item = parser.next_expected(&ImportTokens)?;
+                    // A value followed by a dot is an inline module of the file
+                    if item.kind == TokenKind::Ident(Ident::Value)
+                        && parser.next_if(&TokenKind::Symbol(Symbol::Dot))?.is_some()
+                    {
+                        path.push(parser.look_up.insert(item.value));
+                        continue;
+                    }
+                    let alias = ImportItemRule::next_alias(item.kind, parser)?;
+                    break module::ImportKind::Items(vec![module::ImportItem {
+                        kind: ImportItemRule::into_item(item, parser.look_up.insert(item.value)),
+                        alias,
+                    }]);
                 }
-            }
+            };
+            self.import.push(module::Import {
+                public: self.public,
+                file: file.clone(),
+                path,
+                kind,
+            });
+            let _ = parser.next_expected(&TokenKind::Semicolon);
             Ok(Some(()))
         } else {
             Ok(None)
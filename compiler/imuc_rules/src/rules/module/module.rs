@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use imuc_lexer::token::Keyword;
 
 pub struct ModuleRules;
 
@@ -9,27 +10,40 @@ impl Rule for ModuleRules {
     where
         I: ParserSequence<'s>,
     {
-        // Consumes imports
+        // Consumes imports, where a publicity keyword may start either a re-export or an item
+        let mut items = Vec::new();
         let import = {
             let mut import = Vec::new();
             loop {
+                let public = rules::PublicRule
+                    .parse(parser)?
+                    .expect("publicity rule should not return None");
+                let is_use = parser
+                    .peek()?
+                    .is_some_and(|input| input.kind == TokenKind::Keyword(Keyword::Use));
+                if !is_use {
+                    if public != module::Public::Priv {
+                        let rule = rules::ItemBodyRule {
+                            attrs: Vec::new(),
+                            public,
+                        };
+                        // No items following is an error reported by the rule
+                        items.extend(rule.parse(parser)?);
+                    }
+                    break;
+                }
                 let rule = rules::ImportRule {
                     import: &mut import,
+                    public,
                 };
-                if rule.parse(parser)?.is_none() {
-                    break;
-                }
+                rule.parse(parser)?;
                 while parser.next_if(&TokenKind::Semicolon)?.is_some() {}
             }
             import
         };
-        let items = {
-            let mut items = Vec::new();
-            while let Some(item) = rules::ItemRule.parse(parser)? {
-                items.push(item);
-            }
-            items
-        };
+        while let Some(item) = rules::ItemRule.parse(parser)? {
+            items.push(item);
+        }
         Ok(Some(module::Module { import, items }))
     }
 }
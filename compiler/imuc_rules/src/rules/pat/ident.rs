@@ -1,9 +1,10 @@
 use crate::prelude::*;
-use imuc_lexer::token::{Ident, Symbol};
+use imuc_lexer::token::{Ident, Keyword, ResVal, Symbol};
 
 pub struct IdentPatRule;
 
-lazy_tokens!(IdentTokens, Ident::Value, Ident::Unused);
+// The receiver of a method is bound like `self`
+lazy_tokens!(IdentTokens, Ident::Value, Ident::Unused, ResVal::SelfValue);
 
 impl Rule for IdentPatRule {
     type Output = pat::IdentPat;
@@ -12,7 +13,12 @@ impl Rule for IdentPatRule {
     where
         I: ParserSequence<'s>,
     {
-        let input = parser.next_if(&IdentTokens)?;
+        let mutable = parser.next_if(&TokenKind::Keyword(Keyword::Mut))?.is_some();
+        let input = if mutable {
+            Some(parser.next_expected(&TokenKind::Ident(Ident::Value))?)
+        } else {
+            parser.next_if(&IdentTokens)?
+        };
         if let Some(input) = input {
             let ty = if parser.next_if(&TokenKind::Symbol(Symbol::Colon))?.is_some() {
                 let ty = rules::TypeRule.parse(parser)?.ok_or_else(|| {
@@ -30,7 +36,12 @@ impl Rule for IdentPatRule {
             } else {
                 pat::IdentKind::Value(parser.look_up.insert(input.value))
             };
-            Ok(Some(pat::IdentPat { ident, ty }))
+            Ok(Some(pat::IdentPat {
+                ident,
+                ty,
+                mutable,
+                loc: input.loc,
+            }))
         } else {
             Ok(None)
         }
@@ -0,0 +1,20 @@
+use crate::prelude::*;
+
+pub struct LitPatRule;
+
+impl Rule for LitPatRule {
+    type Output = pat::LitPat;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        if let Some(prim) = rules::PrimRule.parse(parser)? {
+            Ok(Some(pat::LitPat::Prim(prim)))
+        } else if let Some(value) = rules::BoolRule.parse(parser)? {
+            Ok(Some(pat::LitPat::Bool(value)))
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -1,11 +1,15 @@
 mod any;
 mod ident;
+mod lit;
 mod pat;
+mod struct_pat;
 mod tuple;
 mod types;
 
 pub use any::AnyPatRule;
 pub use ident::IdentPatRule;
+pub use lit::LitPatRule;
 pub use pat::PatRule;
+pub use struct_pat::StructPatRule;
 pub use tuple::TuplePatRule;
 pub use types::{TemplArgsRule, TypeRule};
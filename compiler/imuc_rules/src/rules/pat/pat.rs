@@ -14,6 +14,10 @@ impl Rule for PatRule {
             pat::Pat::Tuple(first)
         } else if let Some(first) = rules::IdentPatRule.parse(parser)? {
             pat::Pat::Ident(first)
+        } else if let Some(first) = rules::LitPatRule.parse(parser)? {
+            pat::Pat::Lit(first)
+        } else if let Some(first) = rules::StructPatRule.parse(parser)? {
+            pat::Pat::Struct(first)
         } else {
             return Ok(None);
         };
@@ -0,0 +1,81 @@
+use crate::prelude::*;
+use imuc_lexer::token::{Ident, Keyword, Pair, Symbol};
+use std::collections::BTreeMap;
+
+/// Parses a struct pattern like `Type(name: pat, other, mut last)`, where a field given without
+/// a pattern binds a name like the field
+pub struct StructPatRule;
+
+impl Rule for StructPatRule {
+    type Output = pat::StructPat;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        if let Some(ty) = rules::TypeRule.parse(parser)? {
+            let variant = if parser.next_if(&TokenKind::Symbol(Symbol::Dot))?.is_some() {
+                let name = parser.next_expected(&TokenKind::Ident(Ident::Type))?;
+                Some(parser.look_up.insert(name.value))
+            } else {
+                None
+            };
+            // Variants without elements may omit the parentheses
+            if parser.next_if(&TokenKind::Pair(Pair::LeftParen))?.is_none() {
+                if variant.is_none() {
+                    return Err(parser.map_err(errors::SyntaxError::ExpectedToken {
+                        expect: TokenKind::Pair(Pair::LeftParen),
+                    }));
+                }
+                return Ok(Some(pat::StructPat {
+                    ty,
+                    variant,
+                    elem: BTreeMap::new(),
+                }));
+            }
+            let mut elem = BTreeMap::new();
+            let mut comma = true;
+            loop {
+                if parser
+                    .next_if(&TokenKind::Pair(Pair::RightParen))?
+                    .is_some()
+                {
+                    break;
+                } else if !comma {
+                    return Err(parser.map_err(errors::SyntaxError::ExpectedToken {
+                        expect: TokenKind::Pair(Pair::RightParen),
+                    }));
+                }
+
+                // A field without a pattern, like `x` or `mut x`, binds a name of its own
+                let mutable = parser.next_if(&TokenKind::Keyword(Keyword::Mut))?.is_some();
+                let name = parser.next_expected(&TokenKind::Ident(Ident::Value))?;
+                let field = parser.look_up.insert(name.value);
+
+                let pat =
+                    if !mutable && parser.next_if(&TokenKind::Symbol(Symbol::Colon))?.is_some() {
+                        rules::PatRule.parse(parser)?.ok_or_else(|| {
+                            parser.map_err(errors::SyntaxError::ExpectedIn {
+                                expect: "Pat".to_owned(),
+                                context: "struct pattern".to_owned(),
+                            })
+                        })?
+                    } else {
+                        pat::Pat::Ident(pat::IdentPat {
+                            ident: pat::IdentKind::Value(field.clone()),
+                            ty: None,
+                            mutable,
+                            loc: name.loc,
+                        })
+                    };
+
+                comma = parser.next_if(&TokenKind::Symbol(Symbol::Comma))?.is_some();
+
+                elem.insert(field, pat);
+            }
+            Ok(Some(pat::StructPat { ty, variant, elem }))
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -12,7 +12,7 @@ impl Rule for TuplePatRule {
     {
         if parser.next_if(&TokenKind::Pair(Pair::LeftParen))?.is_some() {
             let mut list = Vec::new();
-            let mut comma = false;
+            let mut comma = true;
             loop {
                 if parser
                     .next_if(&TokenKind::Pair(Pair::RightParen))?
@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use imuc_lexer::token::{Ident, Pair, ResTy, Symbol, UnOp};
+use imuc_lexer::token::{Ident, Keyword, Pair, ResTy, Symbol, UnOp};
 
 pub struct TemplArgsRule;
 
@@ -18,9 +18,9 @@ impl Rule for TemplArgsRule {
                 let next = parser.next_if(&TokenKind::Pair(Pair::RightBracket))?;
                 if next.is_some() {
                     break;
-                } else if comma {
+                } else if !comma {
                     return Err(parser.map_err(errors::SyntaxError::ExpectedToken {
-                        expect: TokenKind::Pair(Pair::RightParen),
+                        expect: TokenKind::Pair(Pair::RightBracket),
                     }));
                 }
 
@@ -51,11 +51,14 @@ lazy_tokens!(
     ResTy::I16,
     ResTy::I32,
     ResTy::I64,
+    ResTy::I128,
+    ResTy::U128,
     ResTy::F32,
     ResTy::F64,
     ResTy::Ptr,
     ResTy::SelfType,
-    ResTy::Str
+    ResTy::Str,
+    ResTy::Bool
 );
 
 impl Rule for TypeRule {
@@ -108,6 +111,69 @@ impl Rule for TypeRule {
                 flags,
                 kind: pat::TypeKind::Res(res),
             }))
+        } else if parser
+            .next_if(&TokenKind::Pair(Pair::LeftBracket))?
+            .is_some()
+        {
+            let item = TypeRule.parse(parser)?.ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "Type".to_owned(),
+                    context: "array type".to_owned(),
+                })
+            })?;
+            let kind = if parser.next_if(&TokenKind::Semicolon)?.is_some() {
+                let len = rules::ArrayLenRule.parse(parser)?.ok_or_else(|| {
+                    parser.map_err(errors::SyntaxError::ExpectedAfter {
+                        expect: "array length".to_owned(),
+                        after: TokenKind::Semicolon,
+                    })
+                })?;
+                pat::TypeKind::Array(Box::new(item), len)
+            } else {
+                pat::TypeKind::Slice(Box::new(item))
+            };
+            parser.next_expected(&TokenKind::Pair(Pair::RightBracket))?;
+            Ok(Some(pat::Type { flags, kind }))
+        } else if parser.next_if(&TokenKind::Keyword(Keyword::Fun))?.is_some() {
+            parser.next_expected(&TokenKind::Pair(Pair::LeftParen))?;
+            let mut args = Vec::new();
+            let mut comma = true;
+            loop {
+                let next = parser.next_if(&TokenKind::Pair(Pair::RightParen))?;
+                if next.is_some() {
+                    break;
+                } else if !comma {
+                    return Err(parser.map_err(errors::SyntaxError::ExpectedToken {
+                        expect: TokenKind::Pair(Pair::RightParen),
+                    }));
+                }
+
+                let item = TypeRule.parse(parser)?.ok_or_else(|| {
+                    parser.map_err(errors::SyntaxError::ExpectedIn {
+                        expect: "Type".to_owned(),
+                        context: "function type arguments".to_owned(),
+                    })
+                })?;
+
+                comma = parser.next_if(&TokenKind::Symbol(Symbol::Comma))?.is_some();
+
+                args.push(item);
+            }
+            let ret = if parser.next_if(&TokenKind::Symbol(Symbol::Arrow))?.is_some() {
+                let ret = TypeRule.parse(parser)?.ok_or_else(|| {
+                    parser.map_err(errors::SyntaxError::ExpectedAfter {
+                        expect: "Type".to_owned(),
+                        after: TokenKind::Symbol(Symbol::Arrow),
+                    })
+                })?;
+                Some(Box::new(ret))
+            } else {
+                None
+            };
+            Ok(Some(pat::Type {
+                flags,
+                kind: pat::TypeKind::Fun(args, ret),
+            }))
         } else if let pat::PatFlags::Shared = flags {
             Err(parser.map_err(errors::SyntaxError::ExpectedAfter {
                 expect: "Type".to_owned(),
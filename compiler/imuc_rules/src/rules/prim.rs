@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use imuc_lexer::token::Literal;
+use imuc_lexer::token::{Literal, ResVal};
 
 lazy_tokens!(
     PrimTokens,
@@ -9,35 +9,44 @@ lazy_tokens!(
     Literal::String
 );
 
+lazy_tokens!(BoolTokens, ResVal::True, ResVal::False);
+
 pub struct PrimRule;
 
 impl PrimRule {
     fn parse_int(value: &str) -> Result<prim::Integer> {
         let mut iter = value.chars();
-        let first = iter.next();
-        let second = iter.next();
-        let options = lexical::ParseIntegerOptions::new();
-        let value = match (first, second) {
-            (Some('0'), Some('x')) => {
-                const FORMAT: u128 = lexical::NumberFormatBuilder::new().radix(16).build();
-                let value: i64 =
-                    lexical::parse_with_options::<_, _, FORMAT>(&value[2..], &options)?;
-                prim::Integer::I64(value)
-            }
-            (Some('0'), Some('b')) => {
-                const FORMAT: u128 = lexical::NumberFormatBuilder::new().radix(2).build();
-                let value: i64 =
-                    lexical::parse_with_options::<_, _, FORMAT>(&value[2..], &options)?;
-                prim::Integer::I64(value)
-            }
-            _ => {
-                let value: i64 = lexical::parse(value)?;
-                prim::Integer::I64(value)
-            }
+        let (radix, digits) = match (iter.next(), iter.next()) {
+            (Some('0'), Some('x')) => (16, &value[2..]),
+            (Some('0'), Some('b')) => (2, &value[2..]),
+            _ => (10, value),
+        };
+        // Literals too large for `I64` take the first of `I128` and `U128` holding them
+        let value = match Self::parse_radix(digits, radix) {
+            Ok(value) => prim::Integer::I64(value),
+            Err(err) => Self::parse_radix(digits, radix)
+                .map(prim::Integer::I128)
+                .or_else(|_| Self::parse_radix(digits, radix).map(prim::Integer::U128))
+                .map_err(|_| err)?,
         };
         Ok(value)
     }
 
+    fn parse_radix<T>(digits: &str, radix: u8) -> lexical::Result<T>
+    where
+        T: lexical::FromLexicalWithOptions<Options = lexical::ParseIntegerOptions>,
+    {
+        const DEC: u128 = lexical::NumberFormatBuilder::new().build();
+        const HEX: u128 = lexical::NumberFormatBuilder::new().radix(16).build();
+        const BIN: u128 = lexical::NumberFormatBuilder::new().radix(2).build();
+        let options = lexical::ParseIntegerOptions::new();
+        match radix {
+            16 => lexical::parse_with_options::<T, _, HEX>(digits, &options),
+            2 => lexical::parse_with_options::<T, _, BIN>(digits, &options),
+            _ => lexical::parse_with_options::<T, _, DEC>(digits, &options),
+        }
+    }
+
     fn parse_float(value: &str) -> Result<prim::Float> {
         let value: f32 = lexical::parse(value.as_bytes())?;
         Ok(prim::Float::F32(value))
@@ -85,3 +94,54 @@ impl Rule for PrimRule {
         }
     }
 }
+
+/// Parses the length of an array as a non-negative integer literal
+pub struct ArrayLenRule;
+
+impl Rule for ArrayLenRule {
+    type Output = u64;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        let input = parser.next_if(&TokenKind::Literal(Literal::Integer))?;
+        if let Some(input) = input {
+            let len = match PrimRule::parse_int(input.value) {
+                Ok(prim::Integer::I64(value)) => u64::try_from(value).ok(),
+                Ok(_) => None,
+                Err(err) => return Err(parser.map_err(err)),
+            };
+            len.map(Some).ok_or_else(|| {
+                parser.map_err(errors::SyntaxError::ExpectedIn {
+                    expect: "non-negative length".to_owned(),
+                    context: "array".to_owned(),
+                })
+            })
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Parses the boolean constants `true` and `false`
+pub struct BoolRule;
+
+impl Rule for BoolRule {
+    type Output = bool;
+
+    fn parse<'s, I>(self, parser: &mut Parser<'s, I>) -> Result<Option<Self::Output>>
+    where
+        I: ParserSequence<'s>,
+    {
+        if let Some(input) = parser.next_if(&BoolTokens)? {
+            match input.kind {
+                TokenKind::ResVal(ResVal::True) => Ok(Some(true)),
+                TokenKind::ResVal(ResVal::False) => Ok(Some(false)),
+                _ => filtered!(),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,385 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ast::*;
+    use imuc_lexer::token::BinOp;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+
+    macro_rules! test_expr {
+        ($name: literal, $content: expr) => {{
+            let content = $content;
+            let mut parser = Parser::new(FileReader::new(
+                $name,
+                content,
+                Reader::new(content.chars()),
+            ));
+            let expr = rules::ExprRule { end: () }
+                .parse(&mut parser)
+                .expect("no errors should occur")
+                .expect("expression rule should match");
+            assert!(parser.is_empty().expect("parser should consume all tokens"));
+            expr
+        }};
+    }
+
+    #[test]
+    fn parse_assign() {
+        let expr::Expr::Assign(assign) = test_expr!("parse_assign: plain", "x = y + 1") else {
+            panic!("expected an assignment");
+        };
+        assert!(assign.op.is_none());
+        assert!(matches!(
+            assign.place.as_ref(),
+            expr::Expr::Value(expr::Value::Name(..))
+        ));
+        assert!(matches!(assign.val.as_ref(), expr::Expr::BinExpr(_)));
+
+        let expr::Expr::Assign(assign) = test_expr!("parse_assign: compound", "p.xs[i] *= 2 + k")
+        else {
+            panic!("expected an assignment");
+        };
+        assert_eq!(assign.op, Some(BinOp::Mul));
+        assert!(matches!(assign.place.as_ref(), expr::Expr::Index(_)));
+
+        let expr::Expr::Assign(assign) = test_expr!("parse_assign: right", "a = b = c") else {
+            panic!("expected an assignment");
+        };
+        assert!(matches!(assign.val.as_ref(), expr::Expr::Assign(_)));
+    }
+
+    #[test]
+    fn parse_bool() {
+        assert!(matches!(
+            test_expr!("parse_bool: true", "true"),
+            expr::Expr::Prim(prim::Prim::Bool(true))
+        ));
+        let expr::Expr::BinExpr(expr) = test_expr!("parse_bool: compare", "false == x") else {
+            panic!("expected a binary expression");
+        };
+        assert!(matches!(
+            expr.lhs.as_ref(),
+            expr::Expr::Prim(prim::Prim::Bool(false))
+        ));
+        assert!(matches!(
+            test_expr!("parse_bool: self", "self"),
+            expr::Expr::Value(expr::Value::Res(_))
+        ));
+    }
+
+    #[test]
+    fn parse_neg() {
+        let expr::Expr::BinExpr(expr) = test_expr!("parse_neg: prefix", "-a * b - c") else {
+            panic!("expected a binary expression");
+        };
+        assert_eq!(expr.op, BinOp::Sub);
+        let expr::Expr::BinExpr(lhs) = expr.lhs.as_ref() else {
+            panic!("expected a product on the left");
+        };
+        assert!(matches!(
+            lhs.lhs.as_ref(),
+            expr::Expr::UnExpr(expr::UnExpr {
+                op: token::UnOp::Neg,
+                ..
+            })
+        ));
+
+        let expr::Expr::BinExpr(expr) = test_expr!("parse_neg: operand", "a - -b") else {
+            panic!("expected a binary expression");
+        };
+        assert!(matches!(expr.rhs.as_ref(), expr::Expr::UnExpr(_)));
+    }
+
+    #[test]
+    fn parse_try() {
+        let expr::Expr::Try(expr) = test_expr!("parse_try: chain", "a?.b?") else {
+            panic!("expected a try");
+        };
+        let expr::Expr::Field(field) = expr.val.as_ref() else {
+            panic!("expected a field access");
+        };
+        assert!(matches!(field.val.as_ref(), expr::Expr::Try(_)));
+    }
+
+    #[test]
+    fn parse_cast() {
+        let expr::Expr::BinExpr(expr) = test_expr!("parse_cast: binary", "x * y as I64") else {
+            panic!("expected a binary expression");
+        };
+        let expr::Expr::Cast(cast) = expr.rhs.as_ref() else {
+            panic!("expected a cast on the right");
+        };
+        assert!(matches!(cast.ty.kind, pat::TypeKind::Res(_)));
+
+        let expr::Expr::Cast(cast) = test_expr!("parse_cast: chain", "@x as Ptr as F64") else {
+            panic!("expected a cast");
+        };
+        let expr::Expr::Cast(inner) = cast.val.as_ref() else {
+            panic!("expected a nested cast");
+        };
+        assert!(matches!(inner.val.as_ref(), expr::Expr::UnExpr(_)));
+
+        let content = "x as";
+        let mut parser = Parser::new(FileReader::new(
+            "parse_cast: missing type",
+            content,
+            Reader::new(content.chars()),
+        ));
+        assert!(rules::ExprRule { end: () }.parse(&mut parser).is_err());
+    }
+
+    #[test]
+    fn parse_closure() {
+        let expr::Expr::Closure(closure) = test_expr!(
+            "parse_closure: arrow",
+            "fun(x: I64, f: fun(I64) => I64) => f(x) + y"
+        ) else {
+            panic!("expected a closure");
+        };
+        let pat::Pat::Tuple(pat::TuplePat(args)) = &closure.args else {
+            panic!("expected a tuple pattern");
+        };
+        assert!(matches!(
+            args.as_slice(),
+            [
+                _,
+                pat::Pat::Ident(pat::IdentPat {
+                    ty: Some(pat::Type {
+                        kind: pat::TypeKind::Fun(fun_args, Some(_)),
+                        ..
+                    }),
+                    ..
+                })
+            ] if fun_args.len() == 1
+        ));
+        let expr::Expr::BinExpr(body) = closure.body.as_ref() else {
+            panic!("expected the body to take the whole expression");
+        };
+        assert!(matches!(body.lhs.as_ref(), expr::Expr::Call(call) if call.args.len() == 1));
+
+        let expr::Expr::Call(call) = test_expr!("parse_closure: body", "fun(x) { x }(1, 2,)")
+        else {
+            panic!("expected a call");
+        };
+        assert!(matches!(call.fun.as_ref(), expr::Expr::Closure(_)));
+        assert_eq!(call.args.len(), 2);
+
+        let content = "{ fun f(x) { x }; fun(x) { x }(1) }";
+        let mut parser = Parser::new(FileReader::new(
+            "parse_closure: statement",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let body = rules::BodyRule
+            .parse(&mut parser)
+            .expect("no errors should occur")
+            .expect("body rule should match");
+        assert!(matches!(body.bind.as_slice(), [bind::Bind::Item(_)]));
+        assert!(matches!(body.body.as_slice(), [expr::Expr::Call(_)]));
+    }
+
+    #[test]
+    fn parse_array() {
+        let expr::Expr::Array(expr::Array::List(elem)) =
+            test_expr!("parse_array: list", "[1, x + 1, 3,]")
+        else {
+            panic!("expected an array");
+        };
+        assert_eq!(elem.len(), 3);
+        assert!(matches!(
+            test_expr!("parse_array: repeat", "[(1, 2); 4]"),
+            expr::Expr::Array(expr::Array::Repeat(_, 4))
+        ));
+        assert!(matches!(
+            test_expr!("parse_array: empty", "[]"),
+            expr::Expr::Array(expr::Array::List(elem)) if elem.is_empty()
+        ));
+        let expr::Expr::Index(index) = test_expr!("parse_array: slice", "[1, 2][0..1]") else {
+            panic!("expected an index");
+        };
+        assert!(matches!(index.index.as_ref(), expr::Expr::BinExpr(_)));
+
+        let content = "[0; -1]";
+        let mut parser = Parser::new(FileReader::new(
+            "parse_array: negative",
+            content,
+            Reader::new(content.chars()),
+        ));
+        assert!(rules::ExprRule { end: () }.parse(&mut parser).is_err());
+    }
+
+    #[test]
+    fn parse_array_type() {
+        let content = "{ let (a: [I64; 3], b: @[[F64; 2]]) = x }";
+        let mut parser = Parser::new(FileReader::new(
+            "parse_array_type",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let body = rules::BodyRule
+            .parse(&mut parser)
+            .expect("no errors should occur")
+            .expect("body rule should match");
+        let [bind::Bind::Let(bind)] = body.bind.as_slice() else {
+            panic!("expected a binding");
+        };
+        let pat::Pat::Tuple(pat::TuplePat(list)) = &bind.pat else {
+            panic!("expected a tuple pattern");
+        };
+        let [pat::Pat::Ident(a), pat::Pat::Ident(b)] = list.as_slice() else {
+            panic!("expected two identifiers");
+        };
+        assert!(matches!(
+            &a.ty,
+            Some(pat::Type {
+                kind: pat::TypeKind::Array(_, 3),
+                ..
+            })
+        ));
+        let Some(pat::Type {
+            flags: pat::PatFlags::Shared,
+            kind: pat::TypeKind::Slice(item),
+        }) = &b.ty
+        else {
+            panic!("expected a shared slice");
+        };
+        assert!(matches!(item.kind, pat::TypeKind::Array(_, 2)));
+    }
+
+    #[test]
+    fn parse_body_scope() {
+        let content = "{ let x = 1; x; let y = x; y }";
+        let mut parser = Parser::new(FileReader::new(
+            "parse_body_scope",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let body = rules::BodyRule
+            .parse(&mut parser)
+            .expect("no errors should occur")
+            .expect("body rule should match");
+        // A binding after an expression opens an inner scope at the end of the outer one
+        assert_eq!(body.bind.len(), 1);
+        let [expr::Expr::Value(_), expr::Expr::Body(inner)] = body.body.as_slice() else {
+            panic!("expected the inner scope to follow the expression");
+        };
+        assert_eq!(inner.bind.len(), 1);
+        assert!(matches!(inner.body.as_slice(), [expr::Expr::Value(_)]));
+    }
+
+    #[test]
+    fn parse_body_sep() {
+        let content = "{ let y = x-1; y }";
+        let mut parser = Parser::new(FileReader::new(
+            "parse_body_sep: subtraction",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let body = rules::BodyRule
+            .parse(&mut parser)
+            .expect("no errors should occur")
+            .expect("body rule should match");
+        let [bind::Bind::Let(bind)] = body.bind.as_slice() else {
+            panic!("expected a binding");
+        };
+        assert!(matches!(
+            &bind.val,
+            expr::Expr::BinExpr(expr::BinExpr { op: BinOp::Sub, .. })
+        ));
+        assert!(matches!(body.body.as_slice(), [expr::Expr::Value(_)]));
+
+        // Line breaks and closing braces separate expressions too
+        for (name, content) in [
+            ("parse_body_sep: line break", "{ let a = 1\n a\n a }"),
+            ("parse_body_sep: brace", "{ loop { break } a }"),
+        ] {
+            let mut parser =
+                Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+            assert!(rules::BodyRule.parse(&mut parser).is_ok());
+        }
+        for (name, content) in [
+            ("parse_body_sep: expressions", "{ 1 2 }"),
+            ("parse_body_sep: let", "{ let a = b c }"),
+        ] {
+            let mut parser =
+                Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+            assert!(rules::BodyRule.parse(&mut parser).is_err());
+        }
+    }
+
+    #[test]
+    fn parse_let_mut() {
+        let content = "{ let mut x = 1; let (mut a, b) = x; x += a }";
+        let mut parser = Parser::new(FileReader::new(
+            "parse_let_mut",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let body = rules::BodyRule
+            .parse(&mut parser)
+            .expect("no errors should occur")
+            .expect("body rule should match");
+        let [bind::Bind::Let(first), bind::Bind::Let(second)] = body.bind.as_slice() else {
+            panic!("expected two bindings");
+        };
+        let pat::Pat::Ident(ident) = &first.pat else {
+            panic!("expected an identifier pattern");
+        };
+        assert!(ident.mutable);
+        assert_eq!(
+            ident.loc,
+            Loc {
+                line: 1,
+                column: 11
+            }
+        );
+        let pat::Pat::Tuple(pat::TuplePat(list)) = &second.pat else {
+            panic!("expected a tuple pattern");
+        };
+        assert!(matches!(
+            list.as_slice(),
+            [
+                pat::Pat::Ident(pat::IdentPat { mutable: true, .. }),
+                pat::Pat::Ident(pat::IdentPat { mutable: false, .. })
+            ]
+        ));
+        assert!(matches!(body.body.as_slice(), [expr::Expr::Assign(_)]));
+    }
+
+    #[test]
+    fn parse_let_destructure() {
+        let content = "{ let (a, Point(x, mut y, z: (_, c))) = p; a }";
+        let mut parser = Parser::new(FileReader::new(
+            "parse_let_destructure",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let body = rules::BodyRule
+            .parse(&mut parser)
+            .expect("no errors should occur")
+            .expect("body rule should match");
+        let [bind::Bind::Let(bind)] = body.bind.as_slice() else {
+            panic!("expected a binding");
+        };
+        let pat::Pat::Tuple(pat::TuplePat(list)) = &bind.pat else {
+            panic!("expected a tuple pattern");
+        };
+        let [_, pat::Pat::Struct(cus)] = list.as_slice() else {
+            panic!("expected a struct pattern");
+        };
+        // Fields given without a pattern bind names of their own
+        assert!(matches!(
+            cus.elem.get("x"),
+            Some(pat::Pat::Ident(pat::IdentPat {
+                ident: pat::IdentKind::Value(name),
+                mutable: false,
+                ..
+            })) if &**name == "x"
+        ));
+        assert!(matches!(
+            cus.elem.get("y"),
+            Some(pat::Pat::Ident(pat::IdentPat { mutable: true, .. }))
+        ));
+        assert!(matches!(cus.elem.get("z"), Some(pat::Pat::Tuple(_))));
+    }
+}
@@ -0,0 +1,199 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ast::*;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+
+    macro_rules! test_flow {
+        ($name: literal, $content: expr) => {{
+            let content = $content;
+            let mut parser = Parser::new(FileReader::new(
+                $name,
+                content,
+                Reader::new(content.chars()),
+            ));
+            let flow = rules::FlowRule
+                .parse(&mut parser)
+                .expect("no errors should occur")
+                .expect("flow rule should match");
+            assert!(parser.is_empty().expect("parser should consume all tokens"));
+            flow
+        }};
+    }
+
+    #[test]
+    fn parse_loop() {
+        let flow::Flow::Loop(flow) = test_flow!("parse_loop: labelled", "loop 'outer { }") else {
+            panic!("expected a loop");
+        };
+        assert_eq!(flow.label.as_deref(), Some("outer"));
+
+        let flow::Flow::Loop(flow) = test_flow!("parse_loop: plain", "loop { break; }") else {
+            panic!("expected a loop");
+        };
+        assert!(flow.label.is_none());
+        assert!(flow.body.unit);
+        assert!(matches!(
+            flow.body.body.as_slice(),
+            [expr::Expr::Flow(flow::Flow::Break(flow::Break {
+                label: None,
+                val: None
+            }))]
+        ));
+    }
+
+    #[test]
+    fn parse_jump() {
+        let flow::Flow::Break(flow) = test_flow!("parse_jump: break value", "break 'outer x + 1")
+        else {
+            panic!("expected a break");
+        };
+        assert_eq!(flow.label.as_deref(), Some("outer"));
+        assert!(matches!(
+            flow.val.as_deref(),
+            Some(expr::Expr::BinExpr(expr::BinExpr { .. }))
+        ));
+
+        let flow::Flow::Continue(flow) = test_flow!("parse_jump: continue", "continue") else {
+            panic!("expected a continue");
+        };
+        assert!(flow.label.is_none());
+
+        let flow::Flow::Return(flow) = test_flow!("parse_jump: return", "return (1, 2)") else {
+            panic!("expected a return");
+        };
+        assert!(matches!(flow.val.as_deref(), Some(expr::Expr::Tuple(_))));
+
+        let flow::Flow::Return(flow) = test_flow!("parse_jump: return unit", "return") else {
+            panic!("expected a return");
+        };
+        assert!(flow.val.is_none());
+    }
+
+    #[test]
+    fn parse_if_else() {
+        let flow::Flow::If(flow) =
+            test_flow!("parse_if_else: chain", "if x {} else if y {} else {}")
+        else {
+            panic!("expected an if");
+        };
+        let Some(expr::Expr::Flow(flow::Flow::If(other))) = flow.other.as_deref() else {
+            panic!("expected an else if");
+        };
+        assert!(matches!(other.other.as_deref(), Some(expr::Expr::Body(_))));
+    }
+
+    #[test]
+    fn parse_while() {
+        let flow::Flow::Loop(flow) = test_flow!("parse_while: label", "while 'outer x < 10 { }")
+        else {
+            panic!("expected a desugared loop");
+        };
+        assert_eq!(flow.label.as_deref(), Some("outer"));
+        let [expr::Expr::Flow(flow::Flow::If(guard)), expr::Expr::Body(_)] =
+            flow.body.body.as_slice()
+        else {
+            panic!("expected a guard followed by the body");
+        };
+        assert!(matches!(
+            guard.cond.as_ref(),
+            expr::Expr::BinExpr(expr::BinExpr {
+                op: imuc_lexer::token::BinOp::Lt,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_for() {
+        let content = "{ for (i, _) in 0..n + 1 { } }";
+        let mut parser = Parser::new(FileReader::new(
+            "parse_for: range",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let body = rules::BodyRule
+            .parse(&mut parser)
+            .expect("no errors should occur")
+            .expect("body rule should match");
+        let [expr::Expr::Body(body)] = body.body.as_slice() else {
+            panic!("expected a desugared body");
+        };
+        assert_eq!(body.bind.len(), 2);
+        let [expr::Expr::Flow(flow::Flow::Loop(flow))] = body.body.as_slice() else {
+            panic!("expected a loop after hidden locals");
+        };
+        let [_, expr::Expr::Body(inner)] = flow.body.body.as_slice() else {
+            panic!("expected a guard followed by the body");
+        };
+        assert!(matches!(
+            inner.bind.as_slice(),
+            [bind::Bind::Let(bind::Let {
+                pat: pat::Pat::Tuple(_),
+                ..
+            })]
+        ));
+        assert!(matches!(
+            inner.body.as_slice(),
+            [expr::Expr::Assign(_), expr::Expr::Body(_)]
+        ));
+
+        let content = "{ for x in xs.items { } }";
+        let mut parser = Parser::new(FileReader::new(
+            "parse_for: collection",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let body = rules::BodyRule
+            .parse(&mut parser)
+            .expect("no errors should occur")
+            .expect("body rule should match");
+        let [expr::Expr::Body(body)] = body.body.as_slice() else {
+            panic!("expected a desugared body");
+        };
+        assert_eq!(body.bind.len(), 3);
+    }
+
+    #[test]
+    fn parse_match() {
+        let flow::Flow::Match(flow) = test_flow!(
+            "parse_match: arms",
+            "match x { (0, true) => 1, Point(x: 1 | 2, y: _) if y > 0 => { 2 } _ => 3 }"
+        ) else {
+            panic!("expected a match");
+        };
+        let [first, second, third] = flow.arms.as_slice() else {
+            panic!("expected three arms");
+        };
+        let pat::Pat::Tuple(pat::TuplePat(list)) = &first.pat else {
+            panic!("expected a tuple pattern");
+        };
+        assert!(matches!(
+            list.as_slice(),
+            [
+                pat::Pat::Lit(pat::LitPat::Prim(prim::Prim::Integer(_))),
+                pat::Pat::Lit(pat::LitPat::Bool(true))
+            ]
+        ));
+        let pat::Pat::Struct(cus) = &second.pat else {
+            panic!("expected a struct pattern");
+        };
+        assert!(matches!(cus.elem.get("x"), Some(pat::Pat::Any(_))));
+        assert!(second.guard.is_some());
+        assert!(matches!(second.val, expr::Expr::Body(_)));
+        assert!(matches!(
+            third.pat,
+            pat::Pat::Ident(pat::IdentPat {
+                ident: pat::IdentKind::Unused,
+                ty: None,
+                ..
+            })
+        ));
+
+        let flow::Flow::Match(flow) = test_flow!("parse_match: empty", "match x { }") else {
+            panic!("expected a match");
+        };
+        assert!(flow.arms.is_empty());
+    }
+}
@@ -33,4 +33,225 @@ mod tests {
         test_item!(error "parse_item: EOF", "pub");
         test_item!(some "parse_item: fun", "fun dog() {}");
     }
+
+    #[test]
+    fn parse_cus() {
+        test_item!(some "parse_cus: struct", "cus Point(x: I64, y: I64)");
+        test_item!(some "parse_cus: union", "cus Shape { Circle(r: F64), Rect(w: F64, h: F64), Empty }");
+        test_item!(some "parse_cus: empty union", "cus Never { }");
+        test_item!(error "parse_cus: duplicate variant", "cus Shape { Empty, Empty }");
+        test_item!(error "parse_cus: missing comma", "cus Shape { Circle(r: F64) Empty }");
+    }
+
+    #[test]
+    fn parse_default() {
+        let item = test_item!(parse "parse_default: elems", "cus Config(pub size: I64 = 4 * 2, name: Str, on = true)")
+            .expect("no errors should occur")
+            .expect("item rule should match");
+        let imuc_ast::item::ItemKind::Cus(imuc_ast::item::Cus::Struct(_, elems)) = item.kind else {
+            panic!("expected a struct");
+        };
+        let defaults: Vec<_> = elems.iter().map(|elem| elem.default.is_some()).collect();
+        assert_eq!(defaults, [true, false, true]);
+        test_item!(error "parse_default: arguments", "fun f(x: I64 = 1) {}");
+        test_item!(error "parse_default: missing value", "cus Config(size: I64 = )");
+    }
+
+    #[test]
+    fn parse_public() {
+        let item = test_item!(parse "parse_public: module", "pub(mod) cus Point(pub x: I64, pub(mod) y: I64, z: I64)")
+            .expect("no errors should occur")
+            .expect("item rule should match");
+        assert_eq!(item.public, imuc_ast::module::Public::Module);
+        let imuc_ast::item::ItemKind::Cus(imuc_ast::item::Cus::Struct(_, elems)) = item.kind else {
+            panic!("expected a struct");
+        };
+        use imuc_ast::module::Public;
+        let public: Vec<_> = elems.iter().map(|elem| elem.public).collect();
+        assert_eq!(public, vec![Public::Pub, Public::Module, Public::Priv]);
+        test_item!(some "parse_public: pub", "pub fun dog() {}");
+        test_item!(error "parse_public: unknown", "pub(dog) fun dog() {}");
+        test_item!(error "parse_public: unclosed", "pub(mod fun dog() {}");
+    }
+
+    #[test]
+    fn parse_attr() {
+        let item = test_item!(parse "parse_attr: item", "#[inline(always)] #[deprecated(\"old\", since(1))] pub fun dog(#[unused] x, y) {}")
+            .expect("no errors should occur")
+            .expect("item rule should match");
+        assert_eq!(item.public, imuc_ast::module::Public::Pub);
+        let names: Vec<_> = item
+            .attrs
+            .iter()
+            .map(|attr| attr.name.to_string())
+            .collect();
+        assert_eq!(names, ["inline", "deprecated"]);
+        assert!(matches!(
+            item.attrs[1].args.as_slice(),
+            [
+                imuc_ast::attr::AttrArg::Prim(imuc_ast::prim::Prim::String(_)),
+                imuc_ast::attr::AttrArg::Attr(since)
+            ] if since.args.len() == 1
+        ));
+        let imuc_ast::item::ItemKind::Fun(fun) = item.kind else {
+            panic!("expected a function");
+        };
+        assert_eq!(fun.attrs.len(), 2);
+        assert!(matches!(fun.attrs[0].as_slice(), [attr] if &*attr.name == "unused"));
+        assert!(fun.attrs[1].is_empty());
+
+        let item = test_item!(parse "parse_attr: elem", "cus Point(#[skip] pub x: I64, y: I64)")
+            .expect("no errors should occur")
+            .expect("item rule should match");
+        let imuc_ast::item::ItemKind::Cus(imuc_ast::item::Cus::Struct(_, elems)) = item.kind else {
+            panic!("expected a struct");
+        };
+        assert_eq!(elems[0].attrs.len(), 1);
+        assert_eq!(elems[0].public, imuc_ast::module::Public::Pub);
+        assert!(elems[1].attrs.is_empty());
+
+        test_item!(error "parse_attr: no item", "#[test]");
+        test_item!(error "parse_attr: unclosed", "#[test fun dog() {}");
+        test_item!(error "parse_attr: bad arg", "#[cfg(+)] fun dog() {}");
+    }
+
+    #[test]
+    fn parse_extern() {
+        let item = test_item!(parse "parse_extern: declare", "pub extern fun puts(#[cfg(test)] s: Ptr) => I32;")
+            .expect("no errors should occur")
+            .expect("item rule should match");
+        assert_eq!(&*item.name, "puts");
+        let imuc_ast::item::ItemKind::Extern(ext) = item.kind else {
+            panic!("expected an extern function");
+        };
+        assert_eq!(&*ext.abi, "C");
+        assert_eq!(ext.attrs[0].len(), 1);
+        assert!(ext.ret.is_some());
+
+        let item = test_item!(parse "parse_extern: export", "extern(\"C\") fun add(x: I64, y: I64) { x + y }")
+            .expect("no errors should occur")
+            .expect("item rule should match");
+        let imuc_ast::item::ItemKind::Fun(fun) = item.kind else {
+            panic!("expected a function");
+        };
+        assert_eq!(fun.abi.as_deref(), Some("C"));
+
+        let item = test_item!(parse "parse_extern: unit", "extern fun exit(code: I32)")
+            .expect("no errors should occur")
+            .expect("item rule should match");
+        assert!(matches!(item.kind, imuc_ast::item::ItemKind::Extern(ext) if ext.ret.is_none()));
+
+        test_item!(error "parse_extern: no fun", "extern cus Point(x: I64)");
+        test_item!(error "parse_extern: no abi", "extern() fun exit(code: I32)");
+    }
+
+    #[test]
+    fn parse_interface() {
+        let item = test_item!(parse "parse_interface: declare", "interface Show { fun show(self) => Str; fun size(self, scale: I64) => I64 }")
+            .expect("no errors should occur")
+            .expect("item rule should match");
+        assert_eq!(&*item.name, "Show");
+        let imuc_ast::item::ItemKind::Interface(interface) = item.kind else {
+            panic!("expected an interface");
+        };
+        let names: Vec<_> = interface
+            .decls
+            .iter()
+            .map(|decl| decl.name.to_string())
+            .collect();
+        assert_eq!(names, ["show", "size"]);
+
+        let item = test_item!(parse "parse_interface: implement", "for Point: Show { fun show(self) { \"point\" } }")
+            .expect("no errors should occur")
+            .expect("item rule should match");
+        assert_eq!(&*item.name, "Point");
+        let imuc_ast::item::ItemKind::For(items) = item.kind else {
+            panic!("expected an implementation");
+        };
+        assert!(items.interface.is_some());
+        assert_eq!(items.items.len(), 1);
+        let item = test_item!(parse "parse_interface: primitive", "for I64 { }")
+            .expect("no errors should occur")
+            .expect("item rule should match");
+        assert_eq!(&*item.name, "I64");
+
+        let item = test_item!(parse "parse_interface: template", "fun show_all[T: Show + Eq, _](x: T) { x.show() }")
+            .expect("no errors should occur")
+            .expect("item rule should match");
+        let imuc_ast::item::ItemKind::Fun(fun) = item.kind else {
+            panic!("expected a function");
+        };
+        assert!(matches!(
+            fun.templ.as_slice(),
+            [imuc_ast::item::Templ::Item(templ), imuc_ast::item::Templ::Unused] if templ.req.len() == 2
+        ));
+
+        test_item!(error "parse_interface: duplicate", "interface Show { fun show(self) fun show(self) }");
+        test_item!(error "parse_interface: body", "interface Show { fun show(self) { } }");
+        test_item!(error "parse_interface: no type", "for { }");
+        test_item!(error "parse_interface: no bound", "fun f[T: ](x) { }");
+    }
+
+    #[test]
+    fn parse_variant_expr() {
+        let content = "Shape.Circle(r: 1.0)";
+        let mut parser = Parser::new(FileReader::new(
+            "parse_variant_expr: payload",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let Some(imuc_ast::expr::Expr::Struct(cus)) = rules::ExprRule { end: () }
+            .parse(&mut parser)
+            .expect("no errors should occur")
+        else {
+            panic!("expected a constructor");
+        };
+        assert_eq!(cus.variant.as_deref(), Some("Circle"));
+        assert_eq!(cus.elem.len(), 1);
+
+        let content = "Shape.Empty";
+        let mut parser = Parser::new(FileReader::new(
+            "parse_variant_expr: no payload",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let Some(imuc_ast::expr::Expr::Struct(cus)) = rules::ExprRule { end: () }
+            .parse(&mut parser)
+            .expect("no errors should occur")
+        else {
+            panic!("expected a constructor");
+        };
+        assert_eq!(cus.variant.as_deref(), Some("Empty"));
+        assert!(cus.elem.is_empty());
+    }
+
+    #[test]
+    fn parse_struct_base() {
+        let content = "Config(size: 1, ..base.config)";
+        let mut parser = Parser::new(FileReader::new(
+            "parse_struct_base: base",
+            content,
+            Reader::new(content.chars()),
+        ));
+        let Some(imuc_ast::expr::Expr::Struct(cus)) = rules::ExprRule { end: () }
+            .parse(&mut parser)
+            .expect("no errors should occur")
+        else {
+            panic!("expected a constructor");
+        };
+        assert_eq!(cus.elem.len(), 1);
+        assert!(matches!(
+            cus.base.as_deref(),
+            Some(imuc_ast::expr::Expr::Field(_))
+        ));
+
+        for (name, content) in [
+            ("parse_struct_base: not last", "Config(..base, size: 1)"),
+            ("parse_struct_base: duplicate", "Config(size: 1, size: 2)"),
+        ] {
+            let mut parser =
+                Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+            assert!(rules::ExprRule { end: () }.parse(&mut parser).is_err());
+        }
+    }
 }
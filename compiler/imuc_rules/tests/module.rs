@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use imuc_ast::*;
+    use imuc_lexer::*;
+    use imuc_parser::*;
+    use imuc_rules::*;
+
+    /// Parses the module, resolving imports from the fixture directory of the crate
+    fn parse(name: &str, content: &str) -> Result<module::Module, imuc_error::Error> {
+        let mut parser = Parser::new(FileReader::new(name, content, Reader::new(content.chars())));
+        rules::ModuleRules
+            .parse(&mut parser)
+            .map(|module| module.expect("module rule should always match"))
+    }
+
+    #[test]
+    fn parse_import() {
+        let module = parse(
+            "parse_import",
+            "
+            use fixture.geo.shape.(Point as P, origin);
+            pub use fixture.geo.shape.*;
+            pub(mod) use fixture.geo.shape as shape;
+            pub fun start() { shape.origin() }
+            ",
+        )
+        .expect("no errors should occur");
+        let [items, glob, alias] = module.import.as_slice() else {
+            panic!("expected three imports");
+        };
+        assert!(items.file.path().ends_with("fixture/geo/shape"));
+        assert_eq!(items.public, module::Public::Priv);
+        assert!(matches!(&items.kind, module::ImportKind::Items(list) if list.len() == 2));
+        assert_eq!(glob.public, module::Public::Pub);
+        assert!(matches!(glob.kind, module::ImportKind::Glob));
+        assert_eq!(alias.public, module::Public::Module);
+        assert!(matches!(&alias.kind, module::ImportKind::Alias(name) if &**name == "shape"));
+        assert_eq!(module.items.len(), 1);
+        assert_eq!(module.items[0].public, module::Public::Pub);
+
+        assert!(parse("parse_import: module", "use fixture.geo as geo;").is_err());
+        assert!(parse(
+            "parse_import: alias type",
+            "use fixture.geo.shape as Shape;"
+        )
+        .is_err());
+        assert!(parse("parse_import: pub", "pub").is_err());
+    }
+    #[test]
+    fn parse_inline() {
+        let module = parse(
+            "parse_inline",
+            "
+            use fixture.geo.shape.helpers.inner.(twice, Pair as P);
+            use fixture.geo.shape.helpers.*;
+            pub mod helpers {
+                use fixture.geo.shape.Point;
+                pub fun twice(x) { x * 2 }
+                pub(mod) mod inner { cus Pair(a: I64, b: I64) }
+            }
+            fun start() { helpers.twice(1) }
+            ",
+        )
+        .expect("no errors should occur");
+        let [items, glob] = module.import.as_slice() else {
+            panic!("expected two imports");
+        };
+        let path: Vec<_> = items.path.iter().map(|name| name.to_string()).collect();
+        assert_eq!(path, ["helpers", "inner"]);
+        assert!(matches!(&items.kind, module::ImportKind::Items(list) if list.len() == 2));
+        assert_eq!(glob.path.len(), 1);
+        assert!(matches!(glob.kind, module::ImportKind::Glob));
+
+        let [helpers, _] = module.items.as_slice() else {
+            panic!("expected two items");
+        };
+        assert_eq!(&*helpers.name, "helpers");
+        let item::ItemKind::Mod(inline) = &helpers.kind else {
+            panic!("expected an inline module");
+        };
+        assert_eq!(inline.import.len(), 1);
+        let [_, inner] = inline.items.as_slice() else {
+            panic!("expected two items in the module");
+        };
+        assert_eq!(inner.public, module::Public::Module);
+        assert!(matches!(&inner.kind, item::ItemKind::Mod(inner) if inner.items.len() == 1));
+
+        assert!(parse("parse_inline: unclosed", "mod helpers { fun f() {}").is_err());
+        assert!(parse("parse_inline: type", "mod Helpers {}").is_err());
+    }
+}
@@ -48,6 +48,20 @@ mod tests {
             "0b10011011",
             prim::Prim::Integer(prim::Integer::I64(0b10011011))
         );
+        test_prim!(
+            "parse_integer: i128",
+            "9223372036854775808",
+            prim::Prim::Integer(prim::Integer::I128(value)),
+            value,
+            1 << 63
+        );
+        test_prim!(
+            "parse_integer: u128",
+            "0xffffffffffffffffffffffffffffffff",
+            prim::Prim::Integer(prim::Integer::U128(value)),
+            value,
+            u128::MAX
+        );
     }
 
     #[test]